@@ -0,0 +1,114 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PartOfSpeech, Word, POS};
+
+/// The kinds of adjectives, which inflect differently
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AdjectiveType {
+    /// 形容詞, like 高い
+    I,
+    /// 形容動詞, like 静か(な)
+    Na,
+    /// タリ活用, like 堂々たる
+    Taru,
+}
+
+impl Word {
+    /// Whether the word is an i-, na- or taru-adjective, including na-adjectives used as
+    /// adverbs like the 静か of 静かに. None for other words.
+    ///
+    /// Taru-adjectives are only recognized with their たる, IPADIC keeps 堂々たる as a
+    /// determiner and 堂々と as an adverb.
+    pub fn adjective_type(&self) -> Option<AdjectiveType> {
+        let first = self.tokens.first()?;
+
+        match self.part_of_speech {
+            PartOfSpeech::Adjective if first.pos == POS::Keiyoushi => Some(AdjectiveType::I),
+            PartOfSpeech::Adjective | PartOfSpeech::Adverb
+                if first.pos2 == POS::Keiyoudoushigokan =>
+            {
+                if self.tokens[1..].iter().any(|token| token.literal == "たる") {
+                    Some(AdjectiveType::Taru)
+                } else {
+                    Some(AdjectiveType::Na)
+                }
+            }
+            PartOfSpeech::Determiner
+                if self.word.ends_with("たる") && self.word.chars().count() > 2 =>
+            {
+                Some(AdjectiveType::Taru)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    fn adjective_types(tokens: &[(&str, &str)]) -> Vec<(String, Option<AdjectiveType>)> {
+        words_from(tokens, &ParseConfig::default())
+            .into_iter()
+            .map(|word| {
+                let adjective_type = word.adjective_type();
+                (word.word, adjective_type)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn tells_i_and_na_adjectives_apart() {
+        assert_eq!(
+            adjective_types(&[
+                (
+                    "高い",
+                    "形容詞,自立,*,*,形容詞・アウオ段,基本形,高い,タカイ,タカイ"
+                ),
+                ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+            ]),
+            [("高い".into(), Some(AdjectiveType::I)), ("猫".into(), None)]
+        );
+        assert_eq!(
+            adjective_types(&[
+                ("静か", "名詞,形容動詞語幹,*,*,*,*,静か,シズカ,シズカ"),
+                ("な", "助動詞,*,*,*,特殊・ダ,体言接続,だ,ナ,ナ"),
+            ])[0],
+            ("静かな".into(), Some(AdjectiveType::Na))
+        );
+    }
+
+    #[test]
+    fn na_adjectives_used_as_adverbs_are_na_adjectives() {
+        let words = words_from(
+            &[
+                ("静か", "名詞,形容動詞語幹,*,*,*,*,静か,シズカ,シズカ"),
+                ("に", "助詞,副詞化,*,*,*,*,に,ニ,ニ"),
+            ],
+            &ParseConfig::default(),
+        );
+
+        assert_eq!(words[0].part_of_speech, PartOfSpeech::Adverb);
+        assert_eq!(words[0].adjective_type(), Some(AdjectiveType::Na));
+    }
+
+    #[test]
+    fn recognizes_taru_adjectives_by_their_taru() {
+        assert_eq!(
+            adjective_types(&[(
+                "堂々たる",
+                "連体詞,*,*,*,*,*,堂々たる,ドウドウタル,ドードータル"
+            )]),
+            [("堂々たる".into(), Some(AdjectiveType::Taru))]
+        );
+        assert_eq!(
+            adjective_types(&[("堂々", "副詞,一般,*,*,*,*,堂々,ドウドウ,ドードー")]),
+            [("堂々".into(), None)]
+        );
+    }
+}
@@ -0,0 +1,134 @@
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::SearchToken;
+
+/// A token of an Elasticsearch `_analyze` response
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnalyzeToken {
+    pub token: String,
+    /// Offset in UTF-16 code units, like Java strings count them
+    pub start_offset: usize,
+    pub end_offset: usize,
+    /// Always `word`, like the kuromoji tokenizer's
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub kind: String,
+    pub position: usize,
+}
+
+/// Turns search tokens of `text` into tokens of an `_analyze` response, to compare them
+/// with the output of Elasticsearch analyzers like kuromoji
+pub fn analyze_tokens(text: &str, tokens: &[SearchToken]) -> Vec<AnalyzeToken> {
+    let utf16_offset = |byte: usize| text[..byte].encode_utf16().count();
+
+    tokens
+        .iter()
+        .map(|token| AnalyzeToken {
+            token: token.term.clone(),
+            start_offset: utf16_offset(token.start),
+            end_offset: utf16_offset(token.end),
+            kind: "word".into(),
+            position: token.position,
+        })
+        .collect()
+}
+
+/// The JSON body of an `_analyze` response, `{"tokens": [...]}`
+pub fn analyze_json(text: &str, tokens: &[SearchToken]) -> String {
+    let mut json = String::from("{\"tokens\":[");
+
+    for (i, token) in analyze_tokens(text, tokens).iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        json.push_str("{\"token\":");
+        push_json_string(&mut json, &token.token);
+        let _ = write!(
+            json,
+            ",\"start_offset\":{},\"end_offset\":{},\"type\":",
+            token.start_offset, token.end_offset
+        );
+        push_json_string(&mut json, &token.kind);
+        let _ = write!(json, ",\"position\":{}}}", token.position);
+    }
+
+    json.push_str("]}");
+    json
+}
+
+fn push_json_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::search_tokens;
+    use crate::testing::Lexicon;
+    use crate::{FilterChain, ParseConfig};
+
+    fn tokens(text: &str) -> Vec<SearchToken> {
+        search_tokens(
+            &Lexicon,
+            text,
+            &ParseConfig::default(),
+            &FilterChain::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn counts_offsets_in_utf16() {
+        let text = "𠮷猫が嘘";
+
+        assert_eq!(
+            analyze_tokens(text, &tokens(text)),
+            [
+                AnalyzeToken {
+                    token: "猫".into(),
+                    start_offset: 2,
+                    end_offset: 3,
+                    kind: "word".into(),
+                    position: 0
+                },
+                AnalyzeToken {
+                    token: "嘘".into(),
+                    start_offset: 4,
+                    end_offset: 5,
+                    kind: "word".into(),
+                    position: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_an_analyze_response() {
+        let mut tokens = tokens("猫");
+        tokens[0].term = "\"猫\"\t".into();
+
+        assert_eq!(
+            analyze_json("猫", &tokens),
+            "{\"tokens\":[{\"token\":\"\\\"猫\\\"\\t\",\"start_offset\":0,\"end_offset\":1,\
+             \"type\":\"word\",\"position\":0}]}"
+        );
+        assert_eq!(analyze_json("", &[]), "{\"tokens\":[]}");
+    }
+}
@@ -0,0 +1,279 @@
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::{is_kana, to_hiragana};
+use crate::postprocess::merge_adjacent;
+use crate::Word;
+
+/// A reading the author gave for a part of the text
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ruby {
+    /// Byte offsets of the text the reading is for, in `AozoraText::text`
+    pub range: Range<usize>,
+    pub reading: String,
+}
+
+/// Text read from Aozora Bunko markup, with the markup taken out
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AozoraText {
+    /// The plain text, ready to be tokenized
+    pub text: String,
+    pub ruby: Vec<Ruby>,
+}
+
+/// A word whose reading differs from the one the text gave for it
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReadingMismatch {
+    /// Index of the word in the words that were checked
+    pub index: usize,
+    pub word: String,
+    pub range: Range<usize>,
+    /// The reading the text gave, from `WordExtra::ruby`
+    pub ruby: String,
+    /// The reading from the dictionary, from `WordExtra::reading`
+    pub reading: String,
+}
+
+impl fmt::Display for ReadingMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {:?}: ruby {}, read as {}",
+            self.word, self.range, self.ruby, self.reading
+        )
+    }
+}
+
+fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々' | '〆' | 'ヶ')
+}
+
+/// Reads text with Aozora Bunko markup: ruby like ｜青空文庫《あおぞらぶんこ》 or
+/// 漢字《かんじ》 (where the reading is for the kanji before it) and annotations
+/// like ［＃「青空」に傍点］, which are dropped.
+pub fn read_aozora(source: &str) -> AozoraText {
+    let mut result = AozoraText::default();
+    // Where a base explicitly started with ｜
+    let mut base_start = None;
+    let mut rest = source;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(annotation) = rest.strip_prefix("［＃") {
+            rest = match annotation.find('］') {
+                Some(end) => &annotation[end + '］'.len_utf8()..],
+                None => "",
+            };
+            continue;
+        }
+
+        rest = &rest[c.len_utf8()..];
+
+        match c {
+            '｜' | '|' => base_start = Some(result.text.len()),
+            '《' => {
+                let (reading, after) = match rest.find('》') {
+                    Some(end) => (&rest[..end], &rest[end + '》'.len_utf8()..]),
+                    None => (rest, ""),
+                };
+                rest = after;
+
+                let start = base_start.take().unwrap_or_else(|| {
+                    let base = result.text.trim_end_matches(is_kanji);
+                    base.len()
+                });
+                if start < result.text.len() && !reading.is_empty() {
+                    result.ruby.push(Ruby {
+                        range: start..result.text.len(),
+                        reading: reading.into(),
+                    });
+                }
+            }
+            _ => result.text.push(c),
+        }
+    }
+
+    result
+}
+
+/// Attaches the readings in `ruby` to the words they're for, as `WordExtra::ruby`.
+/// Words that share a ruby, like 東京 and 都 in ｜東京都《とうきょうと》, are merged into one.
+///
+/// `words` need to have been parsed from the text the ruby ranges point into, e.g. with
+/// `parse_text(&tokenizer, &aozora.text, &config)`.
+pub fn apply_ruby(words: &mut Vec<Word>, ruby: &[Ruby]) {
+    for ruby in ruby {
+        let overlaps = |word: &Word| {
+            let range = word.range();
+            range.start < ruby.range.end && ruby.range.start < range.end
+        };
+
+        if let Some(first) = words.iter().position(overlaps) {
+            let count = words[first..].iter().take_while(|w| overlaps(w)).count();
+            merge_adjacent(words, first..first + count);
+        }
+    }
+
+    for word in words.iter_mut() {
+        word.extra.ruby = ruby_reading(word, ruby);
+    }
+}
+
+/// The reading of the whole word, from its ruby and its kana. None if the word has
+/// no ruby or kanji that no ruby covers.
+fn ruby_reading(word: &Word, ruby: &[Ruby]) -> Option<String> {
+    let range = word.range();
+    let mut covering = ruby
+        .iter()
+        .filter(|r| range.start <= r.range.start && r.range.end <= range.end)
+        .peekable();
+    covering.peek()?;
+
+    let mut reading = String::new();
+    let mut offset = range.start;
+
+    for (i, c) in word.word.char_indices() {
+        let position = range.start + i;
+        if position < offset {
+            continue;
+        }
+
+        match covering.next_if(|r| r.range.start == position) {
+            Some(ruby) => {
+                reading.push_str(&ruby.reading);
+                offset = ruby.range.end;
+            }
+            None if is_kana(c) => reading.push(c),
+            None => return None,
+        }
+    }
+
+    Some(reading)
+}
+
+/// Compares the readings of words that have a `WordExtra::ruby` against it, e.g. after
+/// [`apply_ruby`], and returns the words where they differ. Hiragana and katakana count
+/// as the same, so ruby can be in either.
+///
+/// Useful to see how well the dictionary does on texts that come with furigana.
+pub fn verify_readings(words: &[Word]) -> Vec<ReadingMismatch> {
+    words
+        .iter()
+        .enumerate()
+        .filter_map(|(index, word)| {
+            let ruby = word.extra.ruby.as_ref()?;
+            if to_hiragana(ruby) == to_hiragana(&word.extra.reading) {
+                return None;
+            }
+
+            Some(ReadingMismatch {
+                index,
+                word: word.word.clone(),
+                range: word.range(),
+                ruby: ruby.clone(),
+                reading: word.extra.reading.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    fn ruby(range: Range<usize>, reading: &str) -> Ruby {
+        Ruby {
+            range,
+            reading: reading.into(),
+        }
+    }
+
+    fn annotated(source: &str) -> Vec<Word> {
+        let aozora = read_aozora(source);
+        let mut words = words(&aozora.text);
+        apply_ruby(&mut words, &aozora.ruby);
+        words
+    }
+
+    #[test]
+    fn reads_ruby_for_the_kanji_before_it() {
+        let aozora = read_aozora("その猫《ねこ》が");
+
+        assert_eq!(aozora.text, "その猫が");
+        assert_eq!(aozora.ruby, [ruby(6..9, "ねこ")]);
+    }
+
+    #[test]
+    fn reads_ruby_from_the_bar_on() {
+        let aozora = read_aozora("｜青空文庫《あおぞらぶんこ》と|ABC《えーびーしー》");
+
+        assert_eq!(aozora.text, "青空文庫とABC");
+        assert_eq!(
+            aozora.ruby,
+            [ruby(0..12, "あおぞらぶんこ"), ruby(15..18, "えーびーしー")]
+        );
+    }
+
+    #[test]
+    fn drops_annotations() {
+        let aozora = read_aozora("猫［＃「猫」に傍点］が《》");
+
+        assert_eq!(aozora.text, "猫が");
+        assert!(aozora.ruby.is_empty());
+    }
+
+    #[test]
+    fn reads_words_from_their_ruby_and_kana() {
+        let words = annotated("猫《ねこ》が食《た》べました");
+
+        let ruby: Vec<_> = words
+            .iter()
+            .map(|word| word.extra.ruby.as_deref())
+            .collect();
+        assert_eq!(ruby, [Some("ねこ"), None, Some("たべました")]);
+    }
+
+    #[test]
+    fn merges_words_sharing_a_ruby() {
+        let words = annotated("｜東京都《とうきょうと》へ");
+
+        assert_eq!(words[0].word, "東京都");
+        assert_eq!(words[0].extra.ruby.as_deref(), Some("とうきょうと"));
+        assert_eq!(words[1].word, "へ");
+    }
+
+    #[test]
+    fn needs_ruby_for_every_kanji() {
+        let mut words = words("勉強");
+        apply_ruby(&mut words, &[ruby(3..6, "きょう")]);
+
+        assert_eq!(words[0].extra.ruby, None);
+    }
+
+    #[test]
+    fn reports_words_read_differently_from_their_ruby() {
+        let words = annotated("猫《いぬ》が嘘《ウソ》");
+
+        let mismatches = verify_readings(&words);
+        assert_eq!(
+            mismatches,
+            [ReadingMismatch {
+                index: 0,
+                word: "猫".into(),
+                range: 0..3,
+                ruby: "いぬ".into(),
+                reading: "ネコ".into()
+            }]
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "猫 at 0..3: ruby いぬ, read as ネコ"
+        );
+    }
+}
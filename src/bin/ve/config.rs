@@ -0,0 +1,202 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use toml::{Table, Value};
+use ve::ParseConfig;
+
+/// Name of the config file that's picked up from the working directory
+/// when no `--config` path is given
+pub const DEFAULT_CONFIG_FILE: &str = "ve.toml";
+
+const DEFAULT_MAX_GROUPING_LEN: usize = 24;
+
+/// Keys of the config file that are the CLI's own rather than `ParseConfig`'s
+const CLI_KEYS: [&str; 2] = ["dictionary", "grouping"];
+
+/// Contents of a `ve.toml` file.
+///
+/// The top level holds the base settings, which are those of `ParseConfig` plus
+/// `dictionary` and `[grouping]`. `[profile.<name>]` tables override any subset of them,
+/// with tables merged key by key. Relative dictionary paths are relative to the file.
+///
+/// ```toml
+/// dictionary = "system.dic.zst"
+/// strictness = "lenient"
+/// reading_script = "hiragana"
+/// fillers = "drop"
+///
+/// [normalize]
+/// width = true
+///
+/// [transcription]
+/// wo_as_o = true
+///
+/// [romaji]
+/// system = "hepburn"
+/// long_vowels = "doubled"
+///
+/// [expressions]
+/// date_time = true
+/// quantities = false
+///
+/// [entities]
+/// person_names = true
+/// organizations = true
+/// places = true
+///
+/// [grouping]
+/// max_grouping_len = 24
+///
+/// [rules]
+/// order = ["attach-suffix", "merge-numbers"]
+/// attach-auxiliary = false
+///
+/// [pos_overrides]
+/// "名詞,固有名詞,人名" = "Noun"
+///
+/// [reading_overrides]
+/// "今日" = "コンニチ"
+///
+/// [profile.neologd]
+/// dictionary = "/opt/dic/neologd.dic.zst"
+/// grouping = { max_grouping_len = 0 }
+/// ```
+#[derive(Default, Debug)]
+pub struct ConfigFile {
+    base: Table,
+    profiles: Table,
+    /// Directory of the file, which relative dictionary paths are resolved against
+    dir: PathBuf,
+}
+
+/// The settings of the config file that `ParseConfig` doesn't have
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct CliSettings {
+    dictionary: Option<PathBuf>,
+    #[serde(default)]
+    grouping: GroupingSettings,
+}
+
+#[derive(Deserialize, Default, Debug)]
+#[serde(deny_unknown_fields)]
+struct GroupingSettings {
+    ignore_space: Option<bool>,
+    max_grouping_len: Option<usize>,
+}
+
+/// Fully resolved settings the CLI runs with, after applying
+/// the selected profile on top of the base settings
+#[derive(Clone, Debug)]
+pub struct Config {
+    /// None if neither the config nor `--dictionary` name one
+    pub dictionary: Option<PathBuf>,
+    pub ignore_space: bool,
+    pub max_grouping_len: usize,
+    pub parse: ParseConfig,
+}
+
+impl ConfigFile {
+    /// Reads the config file at `path`, checking the base settings and every profile
+    pub fn read(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Couldn't read config file {}", path.display()))?;
+        let mut base: Table = toml::from_str(&contents)
+            .with_context(|| format!("Couldn't parse config file {}", path.display()))?;
+
+        let profiles = match base.remove("profile") {
+            Some(Value::Table(profiles)) => profiles,
+            Some(_) => return Err(anyhow!("`profile` in {} isn't a table", path.display())),
+            None => Table::new(),
+        };
+        let file = Self {
+            base,
+            profiles,
+            dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+
+        file.resolve(None)
+            .with_context(|| format!("Couldn't parse config file {}", path.display()))?;
+        for name in file.profiles.keys() {
+            file.resolve(Some(name)).with_context(|| {
+                format!("Couldn't parse profile '{}' of {}", name, path.display())
+            })?;
+        }
+
+        Ok(file)
+    }
+
+    /// Reads `ve.toml` from the working directory if there is one
+    pub fn discover() -> Result<Option<Self>> {
+        let path = Path::new(DEFAULT_CONFIG_FILE);
+        if path.is_file() {
+            Self::read(path).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Merges the given profile (if any) on top of the base settings
+    pub fn resolve(&self, profile: Option<&str>) -> Result<Config> {
+        let mut settings = self.base.clone();
+        if let Some(name) = profile {
+            let overrides = self
+                .profiles
+                .get(name)
+                .ok_or_else(|| anyhow!("Profile '{}' isn't defined in the config file", name))?;
+            let overrides = overrides
+                .as_table()
+                .ok_or_else(|| anyhow!("Profile '{}' isn't a table", name))?;
+            merge(&mut settings, overrides);
+        }
+
+        let cli: Table = CLI_KEYS
+            .iter()
+            .filter_map(|key| Some((key.to_string(), settings.remove(*key)?)))
+            .collect();
+        let cli: CliSettings = Value::Table(cli).try_into()?;
+        let parse: ParseConfig = Value::Table(settings).try_into()?;
+
+        Ok(Config {
+            dictionary: cli.dictionary.map(|path| self.dir.join(path)),
+            ignore_space: cli.grouping.ignore_space.unwrap_or(true),
+            max_grouping_len: cli
+                .grouping
+                .max_grouping_len
+                .unwrap_or(DEFAULT_MAX_GROUPING_LEN),
+            parse,
+        })
+    }
+}
+
+/// Overrides the values of `base` with those of `overrides`, merging tables in both
+fn merge(base: &mut Table, overrides: &Table) {
+    for (key, value) in overrides {
+        match (base.get_mut(key), value) {
+            (Some(Value::Table(base)), Value::Table(overrides)) => merge(base, overrides),
+            _ => {
+                base.insert(key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl Config {
+    /// The dictionary to load, looked for with `resolve_dictionary` if none was named
+    pub fn dictionary_path(&self) -> Result<PathBuf> {
+        Ok(ve::resolve_dictionary(self.dictionary.as_deref())?)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            dictionary: None,
+            ignore_space: true,
+            max_grouping_len: DEFAULT_MAX_GROUPING_LEN,
+            parse: ParseConfig::default(),
+        }
+    }
+}
@@ -0,0 +1,563 @@
+mod config;
+
+use std::io::{self, IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use ve::{DictionarySchema, Hook, ParseEvent, Strictness};
+use vibrato::Tokenizer;
+
+use config::{Config, ConfigFile};
+
+const EXCERPT: &str = r#"
+    イスラエル軍は27日夜、イスラム組織ハマスが実効支配するガザ地区にこれまでにない激しい空爆を行うとともに、地上での軍事行動を拡大していると発表しました。
+    一方、ガザ地区では空爆による死者が増え続け、地区の保健当局はこれまでに3000人を超える子どもが死亡したと発表しました。
+    最新の動きを随時更新でお伝えしています
+    "#;
+
+/// Lines between redraws of the progress bar
+const PROGRESS_INTERVAL: usize = 100;
+
+const USAGE: &str = "\
+Usage: ve [OPTIONS] [TEXT]...
+
+Splits Japanese text into words. Reads TEXT, or stdin if no TEXT is given.
+
+Options:
+  -c, --config <PATH>      Config file to use (default: ./ve.toml if present)
+  -p, --profile <NAME>     Profile from the config file to apply
+  -d, --dictionary <PATH>  Dictionary file, overriding the config
+  -s, --subtitles <PATH>   Parse the cues of an SRT or ASS file, printing
+                           their timing, words and readings
+      --compare <PATH>     Also parse with the dictionary at PATH and print
+                           where its words and lemmas differ
+      --debug              Print raw tokens, prepared tokens and words,
+                           and parse events to stderr
+      --dictionary-info    Print the dictionary's size and entry counts and exit
+      --verify-ruby        Read the input as Aozora Bunko text and print the words
+                           whose readings differ from the text's ruby
+      --verify             Check that the words of each line add up to the line,
+                           printing where they don't to stderr
+      --progress           Show a progress bar with the time left on stderr
+      --report <FORMAT>    Print a summary of the input instead of its words:
+                           parts of speech, sentence lengths, unknown tokens
+                           and top lemmas, as text or json
+      --output <FORMAT>    Print the words of each line as words (the default),
+                           as jsonl with one JSON object per line, as mecab
+                           with a line per word like MeCab prints tokens, as
+                           kuromoji with a JSON array of Kuromoji's tokens, as
+                           csv or tsv with a row per word, or as anki with
+                           furigana like 食[た]べました
+      --columns <LIST>     Columns of csv and tsv, separated by commas (default:
+                           surface,lemma,reading,transcription,pos,tokens)
+  -h, --help               Print this help";
+
+/// Lemmas listed in `--report`
+const TOP_LEMMAS: usize = 50;
+
+#[derive(Clone, Copy)]
+enum ReportFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+enum OutputFormat {
+    #[default]
+    Words,
+    Jsonl,
+    Mecab,
+    Kuromoji,
+    Csv,
+    Tsv,
+    Anki,
+}
+
+#[derive(Default)]
+struct Args {
+    config: Option<PathBuf>,
+    profile: Option<String>,
+    dictionary: Option<PathBuf>,
+    subtitles: Option<PathBuf>,
+    compare: Option<PathBuf>,
+    debug: bool,
+    dictionary_info: bool,
+    verify_ruby: bool,
+    verify: bool,
+    progress: bool,
+    report: Option<ReportFormat>,
+    output: OutputFormat,
+    columns: Option<Vec<ve::WordColumn>>,
+    help: bool,
+    text: Vec<String>,
+}
+
+impl Args {
+    fn parse() -> Result<Self> {
+        let mut args = Args::default();
+        let mut iter = std::env::args().skip(1);
+
+        while let Some(arg) = iter.next() {
+            let mut value = |name: &str| {
+                iter.next()
+                    .with_context(|| format!("{} expects a value", name))
+            };
+
+            match arg.as_str() {
+                "-c" | "--config" => args.config = Some(value(&arg)?.into()),
+                "-p" | "--profile" => args.profile = Some(value(&arg)?),
+                "-d" | "--dictionary" => args.dictionary = Some(value(&arg)?.into()),
+                "-s" | "--subtitles" => args.subtitles = Some(value(&arg)?.into()),
+                "--compare" => args.compare = Some(value(&arg)?.into()),
+                "--debug" => args.debug = true,
+                "--dictionary-info" => args.dictionary_info = true,
+                "--verify-ruby" => args.verify_ruby = true,
+                "--verify" => args.verify = true,
+                "--progress" => args.progress = true,
+                "--report" => {
+                    args.report = Some(match value(&arg)?.as_str() {
+                        "text" => ReportFormat::Text,
+                        "json" => ReportFormat::Json,
+                        other => bail!("Unknown report format '{}', expected text or json", other),
+                    })
+                }
+                "--output" => {
+                    args.output = match value(&arg)?.as_str() {
+                        "words" => OutputFormat::Words,
+                        "jsonl" => OutputFormat::Jsonl,
+                        "mecab" => OutputFormat::Mecab,
+                        "kuromoji" => OutputFormat::Kuromoji,
+                        "csv" => OutputFormat::Csv,
+                        "tsv" => OutputFormat::Tsv,
+                        "anki" => OutputFormat::Anki,
+                        other => bail!(
+                            "Unknown output format '{}', expected words, jsonl, mecab, kuromoji, csv, tsv or anki",
+                            other
+                        ),
+                    }
+                }
+                "--columns" => {
+                    let columns = value(&arg)?
+                        .split(',')
+                        .map(|name| {
+                            ve::WordColumn::ALL
+                                .into_iter()
+                                .find(|column| column.name() == name.trim())
+                                .with_context(|| format!("Unknown column '{}'", name))
+                        })
+                        .collect::<Result<_>>()?;
+                    args.columns = Some(columns);
+                }
+                "-h" | "--help" => args.help = true,
+                flag if flag.starts_with('-') && flag.len() > 1 => {
+                    bail!("Unknown option '{}'\n\n{}", flag, USAGE)
+                }
+                _ => args.text.push(arg),
+            }
+        }
+
+        Ok(args)
+    }
+
+    /// Loads the config file and applies the selected profile and CLI overrides
+    fn config(&self) -> Result<Config> {
+        let file = match &self.config {
+            Some(path) => Some(ConfigFile::read(path)?),
+            None => ConfigFile::discover()?,
+        };
+
+        let mut config = match (&file, &self.profile) {
+            (Some(file), profile) => file.resolve(profile.as_deref())?,
+            (None, Some(profile)) => {
+                bail!(
+                    "Profile '{}' was requested, but no config file was found",
+                    profile
+                )
+            }
+            (None, None) => Config::default(),
+        };
+
+        if let Some(dictionary) = &self.dictionary {
+            config.dictionary = Some(dictionary.clone());
+        }
+
+        Ok(config)
+    }
+
+    /// Input text from the arguments or stdin, falling back to
+    /// the bundled excerpt when run interactively without any text
+    fn input(&self) -> Result<String> {
+        if !self.text.is_empty() {
+            return Ok(self.text.join(" "));
+        }
+
+        let mut stdin = io::stdin();
+        if stdin.is_terminal() {
+            return Ok(EXCERPT.into());
+        }
+
+        let mut input = String::new();
+        stdin.read_to_string(&mut input)?;
+        Ok(input)
+    }
+}
+
+fn load_tokenizer(config: &Config) -> Result<Tokenizer> {
+    let dict = ve::load_dictionary(config.dictionary_path()?)?;
+
+    let tokenizer = Tokenizer::new(dict)
+        .ignore_space(config.ignore_space)?
+        .max_grouping_len(config.max_grouping_len);
+
+    Ok(tokenizer)
+}
+
+fn print_dictionary_info(config: &Config) -> Result<()> {
+    let dictionary = config.dictionary_path()?;
+    let info = ve::inspect_dictionary(&dictionary)?;
+    let mib = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+
+    println!("Dictionary:     {}", dictionary.display());
+    println!("File size:      {:.1} MiB", mib(info.file_size));
+    println!("Memory size:    {:.1} MiB", mib(info.memory_size));
+    println!("System entries: {}", info.system_entries);
+    match info.user_entries {
+        Some(entries) => println!("User entries:   {}", entries),
+        None => println!("User entries:   no user lexicon"),
+    }
+
+    Ok(())
+}
+
+/// Parses Aozora Bunko text and prints where the readings differ from its ruby
+fn verify_ruby(tokenizer: &Tokenizer, config: &Config, input: &str) -> Result<()> {
+    let aozora = ve::read_aozora(input);
+    let mut words = ve::parse_text(tokenizer, &aozora.text, &config.parse)?
+        .into_iter()
+        .flat_map(|sentence| sentence.words)
+        .collect();
+    ve::apply_ruby(&mut words, &aozora.ruby);
+
+    let mismatches = ve::verify_readings(&words);
+    for mismatch in &mismatches {
+        println!("{}", mismatch);
+    }
+
+    let checked = words.iter().filter(|w| w.extra.ruby.is_some()).count();
+    println!(
+        "{} of {} words with ruby read differently",
+        mismatches.len(),
+        checked
+    );
+
+    Ok(())
+}
+
+/// Parses the input line by line and prints a summary of the words instead of the words
+fn print_report(
+    tokenizer: &Tokenizer,
+    config: &Config,
+    input: &str,
+    format: ReportFormat,
+    show_progress: bool,
+) -> Result<()> {
+    // Collected up front, so the progress bar knows how many lines there are
+    let lines: Vec<String> = input
+        .lines()
+        .map(|line| config.parse.normalize.apply(line))
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let mut parse = config.parse.clone();
+    if show_progress {
+        parse.progress = Some(Hook(Arc::new(|progress: &ve::Progress| {
+            let done = progress.fraction() == Some(1.0);
+            if done || progress.sentences.is_multiple_of(PROGRESS_INTERVAL) {
+                print_progress(progress);
+            }
+        })));
+    }
+
+    let mut collector = ve::ReportCollector::default();
+    ve::parse_pipelined(tokenizer, lines, &parse, |_, words| collector.add(&words))?;
+    if show_progress {
+        eprintln!();
+    }
+    let report = collector.finish(TOP_LEMMAS);
+
+    match format {
+        ReportFormat::Text => print!("{}", report),
+        ReportFormat::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
+
+/// Parses the input with a second dictionary and prints the sentences it splits
+/// or lemmatizes differently. The other dictionary's schema is detected on its own,
+/// unless `configured_schema` is set.
+fn compare_dictionaries(
+    tokenizer: &Tokenizer,
+    config: &Config,
+    configured_schema: Option<DictionarySchema>,
+    dictionary: &Path,
+    input: &str,
+) -> Result<()> {
+    let mut other_config = Config {
+        dictionary: Some(dictionary.into()),
+        ..config.clone()
+    };
+    other_config.parse.schema = configured_schema;
+    let other = load_tokenizer(&other_config)?;
+    other_config.parse.detect_schema(&other);
+
+    let sentences = ve::compare_parses(
+        (tokenizer, &config.parse),
+        (&other, &other_config.parse),
+        input,
+    )?;
+    for sentence in &sentences {
+        println!("{}", sentence.text);
+        for difference in &sentence.differences {
+            println!("  {}", difference);
+        }
+    }
+
+    let total = ve::split_sentences(input).len();
+    println!(
+        "{} of {} sentences differ between {} and {}",
+        sentences.len(),
+        total,
+        config.dictionary_path()?.display(),
+        dictionary.display()
+    );
+
+    Ok(())
+}
+
+/// Draws a progress bar on stderr, over the one drawn before
+fn print_progress(progress: &ve::Progress) {
+    const WIDTH: usize = 30;
+
+    let fraction = progress.fraction().unwrap_or(0.0);
+    let filled = (fraction * WIDTH as f64) as usize;
+    let left = match progress.eta() {
+        Some(eta) => format!(", {}s left", eta.as_secs()),
+        None => String::new(),
+    };
+
+    eprint!(
+        "\r[{}{}] {:3.0}% {} lines{}  ",
+        "#".repeat(filled),
+        " ".repeat(WIDTH - filled),
+        fraction * 100.0,
+        progress.sentences,
+        left
+    );
+}
+
+fn format_timestamp(time: Duration) -> String {
+    let millis = time.as_millis();
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Parses each cue of a subtitle file and prints it with its words and their readings
+fn annotate_subtitles(tokenizer: &Tokenizer, config: &Config, path: &Path) -> Result<()> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read subtitles {}", path.display()))?;
+    let cues = ve::annotate_cues(tokenizer, ve::read_subtitles(&source), &config.parse)?;
+
+    for (index, cue) in cues.iter().enumerate() {
+        let join = |part: fn(&ve::Word) -> &str| {
+            cue.words.iter().map(part).collect::<Vec<&str>>().join(" ")
+        };
+
+        println!("{}", index + 1);
+        println!(
+            "{} --> {}",
+            format_timestamp(cue.start),
+            format_timestamp(cue.end)
+        );
+        println!("{}", join(|w| w.word.as_str()));
+        println!("{}", join(|w| w.extra.reading.as_str()));
+        println!();
+    }
+
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<()> {
+    let mut config = args.config()?;
+    if args.dictionary_info {
+        return print_dictionary_info(&config);
+    }
+
+    if args.debug {
+        config.parse.observer = Some(Hook(Arc::new(|event: &ParseEvent| {
+            eprintln!("{:?}", event)
+        })));
+    } else if config.parse.strictness == Strictness::Lenient {
+        config.parse.observer = Some(Hook(Arc::new(|event: &ParseEvent| {
+            if let ParseEvent::Warning(warning) = event {
+                eprintln!("warning: {}", warning);
+            }
+        })));
+    }
+    let tokenizer = load_tokenizer(&config)?;
+    let configured_schema = config.parse.schema;
+    config.parse.detect_schema(&tokenizer);
+
+    if let Some(path) = &args.subtitles {
+        return annotate_subtitles(&tokenizer, &config, path);
+    }
+
+    let mut input = args.input()?;
+    if args.verify_ruby {
+        return verify_ruby(&tokenizer, &config, &input);
+    }
+    if let Some(path) = &args.compare {
+        return compare_dictionaries(&tokenizer, &config, configured_schema, path, &input);
+    }
+    if config.parse.normalize.join_lines {
+        input = ve::join_soft_line_breaks(&input);
+    }
+    if let Some(format) = args.report {
+        return print_report(&tokenizer, &config, &input, format, args.progress);
+    }
+
+    let mut diverged = 0;
+    let mut jsonl = ve::JsonlWriter::new(io::stdout().lock());
+    let mut table = match args.output {
+        OutputFormat::Tsv => ve::TableWriter::tsv(io::stdout().lock()),
+        _ => ve::TableWriter::csv(io::stdout().lock()),
+    };
+    if let Some(columns) = &args.columns {
+        table = table.columns(columns);
+    }
+    if matches!(args.output, OutputFormat::Csv | OutputFormat::Tsv) {
+        table.write_header()?;
+    }
+    let mut progress = ve::Progress::new(Some(input.len()));
+    for (number, line) in input.lines().enumerate() {
+        progress.advance(line.len() + 1);
+        if args.progress && progress.sentences.is_multiple_of(PROGRESS_INTERVAL) {
+            print_progress(&progress);
+        }
+
+        let line = config.parse.normalize.apply(line);
+        if line.is_empty() {
+            continue;
+        }
+
+        let raw_tokens = ve::tokenize(&tokenizer, &line);
+
+        if args.debug {
+            let debug_str = raw_tokens
+                .iter()
+                .map(|t| format!("{}\t [{}]", t.surface, t.feature))
+                .collect::<Vec<String>>()
+                .join("\n");
+
+            println!("Raw Tokens");
+            println!("{}", debug_str);
+        }
+
+        let prepared_tokens = ve::prepare_tokens_with(raw_tokens, &config.parse)?;
+
+        if args.debug {
+            println!("Prepared Tokens");
+            println!("{:#?}", prepared_tokens);
+        }
+
+        let words = ve::parse_into_words_with(prepared_tokens, &config.parse)?;
+
+        if args.debug {
+            println!("Words");
+            println!("{:#?}", words);
+        }
+
+        if args.verify {
+            if let Err(divergence) = ve::verify_reconstruction(&line, &words) {
+                eprintln!("line {}: {}", number + 1, divergence);
+                diverged += 1;
+            }
+        }
+
+        match args.output {
+            OutputFormat::Words => {}
+            OutputFormat::Jsonl => {
+                jsonl.write_sentence(&line, &words)?;
+                continue;
+            }
+            OutputFormat::Mecab => {
+                print!("{}", ve::words_to_mecab(&words));
+                continue;
+            }
+            OutputFormat::Kuromoji => {
+                println!("{}", ve::kuromoji_json(&line, &words));
+                continue;
+            }
+            OutputFormat::Csv | OutputFormat::Tsv => {
+                table.write_words(&words)?;
+                continue;
+            }
+            OutputFormat::Anki => {
+                println!("{}", ve::anki_furigana(&line, &words));
+                continue;
+            }
+        }
+
+        let sentence = words
+            .iter()
+            .map(|w| w.word.as_str())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        println!("{}", sentence);
+
+        if let Some(options) = &config.parse.romaji {
+            println!("{}", ve::romanize_sentence(&words, options));
+        }
+    }
+
+    if args.progress {
+        print_progress(&progress);
+        eprintln!();
+    }
+    if diverged > 0 {
+        bail!("Lines whose words don't add up to them: {}", diverged);
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {:#}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
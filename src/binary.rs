@@ -0,0 +1,99 @@
+use std::io::{self, Read, Write};
+
+use crate::Word;
+
+/// Starts every sentence written, with a version that's bumped whenever `Word` changes
+/// shape, so caches from other versions of ve are rejected instead of misread
+const HEADER: &[u8] = b"ve-words\x01";
+
+/// The words in a compact binary form, see [`write_binary`]
+pub fn to_binary(words: &[Word]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_binary(&mut bytes, words).expect("words serialize to bincode");
+    bytes
+}
+
+/// Reads words back from [`to_binary`]
+pub fn from_binary(bytes: &[u8]) -> bincode::Result<Vec<Word>> {
+    read_binary(bytes)?.ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof).into())
+}
+
+/// Writes the words of a sentence in a compact binary form (bincode), so parses can be
+/// cached on disk and loaded again much faster than parsing the text anew.
+///
+/// Can be called for each sentence of a corpus on the same writer, and the sentences
+/// read back one by one with [`read_binary`].
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::{BufReader, BufWriter};
+///
+/// let ve = ve::Ve::builder().build().unwrap();
+/// let mut cache = BufWriter::new(File::create("corpus.bin").unwrap());
+/// for line in ["今日はいい天気ですね", "明日は雨です"] {
+///     ve::write_binary(&mut cache, &ve.parse(line).unwrap()).unwrap();
+/// }
+/// drop(cache);
+///
+/// let mut cache = BufReader::new(File::open("corpus.bin").unwrap());
+/// while let Some(words) = ve::read_binary(&mut cache).unwrap() {
+///     println!("{}", words.len());
+/// }
+/// ```
+pub fn write_binary(mut writer: impl Write, words: &[Word]) -> bincode::Result<()> {
+    writer.write_all(HEADER)?;
+    bincode::serialize_into(writer, words)
+}
+
+/// Reads the words of the next sentence written with [`write_binary`], None at the end
+pub fn read_binary(mut reader: impl Read) -> bincode::Result<Option<Vec<Word>>> {
+    let mut header = [0; HEADER.len()];
+    if reader.read(&mut header[..1])? == 0 {
+        return Ok(None);
+    }
+    reader.read_exact(&mut header[1..])?;
+    if header != HEADER {
+        return Err(Box::new(bincode::ErrorKind::Custom(
+            "not words written by this version of ve".into(),
+        )));
+    }
+
+    bincode::deserialize_from(reader).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn reads_back_the_words_written() {
+        let words = words("猫が食べました。");
+
+        let read = from_binary(&to_binary(&words)).unwrap();
+        assert_eq!(format!("{read:?}"), format!("{words:?}"));
+    }
+
+    #[test]
+    fn reads_sentences_one_by_one() {
+        let mut bytes = Vec::new();
+        write_binary(&mut bytes, &words("猫")).unwrap();
+        write_binary(&mut bytes, &[]).unwrap();
+
+        let mut reader = &bytes[..];
+        assert_eq!(read_binary(&mut reader).unwrap().unwrap()[0].word, "猫");
+        assert!(read_binary(&mut reader).unwrap().unwrap().is_empty());
+        assert!(read_binary(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_other_versions_and_truncated_input() {
+        let mut bytes = to_binary(&words("猫"));
+
+        assert!(from_binary(&bytes[..HEADER.len() - 1]).is_err());
+        assert!(from_binary(&[]).is_err());
+
+        bytes[HEADER.len() - 1] += 1;
+        assert!(from_binary(&bytes).is_err());
+    }
+}
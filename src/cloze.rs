@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::{PartOfSpeech, Sentence};
+
+/// The word of a sentence to blank out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClozeTarget<'a> {
+    /// Index into `Sentence::words`
+    Index(usize),
+    /// The first word with this lemma
+    Lemma(&'a str),
+}
+
+/// A sentence with one word blanked out
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cloze {
+    /// The sentence with `ClozeOptions::blank` in place of the word
+    pub text: String,
+    /// The word as it is in the sentence
+    pub answer: String,
+    pub lemma: String,
+    /// Reading of the answer
+    pub reading: String,
+    pub part_of_speech: PartOfSpeech,
+    /// Lemmas of other words with the same part of speech, the most frequent first
+    pub distractors: Vec<String>,
+}
+
+/// Settings for `make_cloze`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct ClozeOptions {
+    /// Put in place of the word
+    pub blank: String,
+    /// How many distractors to pick at most
+    pub distractors: usize,
+}
+
+impl Default for ClozeOptions {
+    fn default() -> Self {
+        Self {
+            blank: "＿＿".into(),
+            distractors: 3,
+        }
+    }
+}
+
+/// Blanks out `target` in `sentence`, with distractors drawn from the words of `corpus`
+/// (which may include `sentence` itself). None if there's no such word.
+pub fn make_cloze(
+    sentence: &Sentence,
+    target: ClozeTarget,
+    corpus: &[Sentence],
+    options: &ClozeOptions,
+) -> Option<Cloze> {
+    let index = match target {
+        ClozeTarget::Index(index) => index,
+        ClozeTarget::Lemma(wanted) => sentence.words.iter().position(|w| lemma(w) == wanted)?,
+    };
+    let word = sentence.words.get(index)?;
+
+    let range = word.range();
+    let start = range.start - sentence.span.start;
+    let end = range.end - sentence.span.start;
+    let text = format!(
+        "{}{}{}",
+        &sentence.text[..start],
+        options.blank,
+        &sentence.text[end..]
+    );
+
+    // Counts and first occurrence of each candidate lemma
+    let mut candidates: HashMap<&str, (usize, usize)> = HashMap::new();
+    for other in corpus.iter().flat_map(|sentence| &sentence.words) {
+        if other.part_of_speech == word.part_of_speech && lemma(other) != lemma(word) {
+            let first = candidates.len();
+            candidates.entry(lemma(other)).or_insert((0, first)).0 += 1;
+        }
+    }
+
+    let mut candidates: Vec<_> = candidates.into_iter().collect();
+    candidates.sort_by_key(|(_, (count, first))| (std::cmp::Reverse(*count), *first));
+
+    Some(Cloze {
+        text,
+        answer: sentence.text[start..end].into(),
+        lemma: lemma(word).into(),
+        reading: word.extra.reading.clone(),
+        part_of_speech: word.part_of_speech,
+        distractors: candidates
+            .into_iter()
+            .take(options.distractors)
+            .map(|(lemma, _)| lemma.to_string())
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+    use crate::{parse_text, ParseConfig};
+
+    fn sentences(text: &str) -> Vec<Sentence> {
+        parse_text(&Lexicon, text, &ParseConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn blanks_out_the_word() {
+        let corpus = sentences("猫を食べました。");
+        let cloze = make_cloze(
+            &corpus[0],
+            ClozeTarget::Index(2),
+            &corpus,
+            &ClozeOptions::default(),
+        );
+
+        assert_eq!(
+            cloze,
+            Some(Cloze {
+                text: "猫を＿＿。".into(),
+                answer: "食べました".into(),
+                lemma: "食べる".into(),
+                reading: "タベマシタ".into(),
+                part_of_speech: PartOfSpeech::Verb,
+                distractors: Vec::new()
+            })
+        );
+    }
+
+    #[test]
+    fn picks_the_most_frequent_distractors() {
+        let corpus = sentences("猫が嘘。大人へ食べ物。嘘。今日。食べ物が嘘。");
+        let options = ClozeOptions {
+            blank: "___".into(),
+            distractors: 2,
+        };
+        let cloze = make_cloze(&corpus[0], ClozeTarget::Lemma("猫"), &corpus, &options).unwrap();
+
+        assert_eq!(cloze.text, "___が嘘。");
+        assert_eq!(cloze.distractors, ["嘘", "食べ物"]);
+    }
+
+    #[test]
+    fn needs_the_target_in_the_sentence() {
+        let corpus = sentences("猫が嘘。");
+        let options = ClozeOptions::default();
+
+        assert_eq!(
+            make_cloze(&corpus[0], ClozeTarget::Lemma("犬"), &corpus, &options),
+            None
+        );
+        assert_eq!(
+            make_cloze(&corpus[0], ClozeTarget::Index(4), &corpus, &options),
+            None
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::{parse_text, ParseConfig, Result, Tokenize, Word};
+
+/// How two parses of the same text differ
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DifferenceKind {
+    /// The text is split into different words
+    Segmentation,
+    /// The words are the same, but their lemmas aren't
+    Lemma,
+}
+
+/// A piece of text that two parses disagree on, with the words of either side
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Difference {
+    pub kind: DifferenceKind,
+    /// Byte offsets of the text covered by the words of both sides
+    pub range: Range<usize>,
+    pub left: Vec<Word>,
+    pub right: Vec<Word>,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let side = |words: &[Word]| match self.kind {
+            DifferenceKind::Segmentation => words
+                .iter()
+                .map(|w| w.word.as_str())
+                .collect::<Vec<&str>>()
+                .join("|"),
+            DifferenceKind::Lemma => words
+                .iter()
+                .map(|w| format!("{} ({})", w.word, lemma(w)))
+                .collect::<Vec<String>>()
+                .join("|"),
+        };
+        let kind = match self.kind {
+            DifferenceKind::Segmentation => "segmentation",
+            DifferenceKind::Lemma => "lemma",
+        };
+
+        write!(f, "{}: {} vs {}", kind, side(&self.left), side(&self.right))
+    }
+}
+
+/// The differences of one sentence
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SentenceComparison {
+    pub text: String,
+    /// Byte offsets of the sentence in the text, like `Sentence::span`
+    pub span: Range<usize>,
+    pub differences: Vec<Difference>,
+}
+
+/// Lines up two parses of the same text by their word boundaries, returning where they
+/// split the text differently and where the same words get different lemmas.
+pub fn compare_words(left: &[Word], right: &[Word]) -> Vec<Difference> {
+    let mut differences = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < left.len() || j < right.len() {
+        let (first_left, first_right) = (i, j);
+
+        // Take words from the side that's behind until both end at the same offset
+        let mut left_end = None;
+        let mut right_end = None;
+        loop {
+            let behind_left = match (left_end, right_end) {
+                (None, _) => true,
+                (Some(_), None) => false,
+                (Some(l), Some(r)) if l == r => break,
+                (Some(l), Some(r)) => l < r,
+            };
+
+            if behind_left && i < left.len() {
+                left_end = Some(left[i].range().end);
+                i += 1;
+            } else if !behind_left && j < right.len() {
+                right_end = Some(right[j].range().end);
+                j += 1;
+            } else if i < left.len() || j < right.len() {
+                // One side ran out, the rest of the other can't line up anymore
+                i = left.len();
+                j = right.len();
+                break;
+            } else {
+                break;
+            }
+        }
+
+        let (left_words, right_words) = (&left[first_left..i], &right[first_right..j]);
+        let kind = match (left_words, right_words) {
+            ([l], [r]) if l.range() == r.range() => {
+                if lemma(l) == lemma(r) {
+                    continue;
+                }
+                DifferenceKind::Lemma
+            }
+            _ => DifferenceKind::Segmentation,
+        };
+
+        let start = [left_words.first(), right_words.first()]
+            .into_iter()
+            .flatten()
+            .map(|w| w.range().start)
+            .min()
+            .unwrap_or_default();
+        let end = [left_words.last(), right_words.last()]
+            .into_iter()
+            .flatten()
+            .map(|w| w.range().end)
+            .max()
+            .unwrap_or_default();
+
+        differences.push(Difference {
+            kind,
+            range: start..end,
+            left: left_words.to_vec(),
+            right: right_words.to_vec(),
+        });
+    }
+
+    differences
+}
+
+/// Parses `text` with two tokenizers, e.g. loaded from an IPADIC and a UniDic dictionary,
+/// and compares the words of each sentence with `compare_words`.
+/// Sentences without differences are left out.
+pub fn compare_parses(
+    left: (&(impl Tokenize + ?Sized), &ParseConfig),
+    right: (&(impl Tokenize + ?Sized), &ParseConfig),
+    text: &str,
+) -> Result<Vec<SentenceComparison>> {
+    let left = parse_text(left.0, text, left.1)?;
+    let right = parse_text(right.0, text, right.1)?;
+
+    Ok(left
+        .into_iter()
+        .zip(right)
+        .filter_map(|(left, right)| {
+            let differences = compare_words(&left.words, &right.words);
+            (!differences.is_empty()).then_some(SentenceComparison {
+                text: left.text,
+                span: left.span,
+                differences,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, Lexicon};
+    use crate::Rule;
+
+    #[test]
+    fn finds_differently_split_words() {
+        let config = ParseConfig::default();
+        let mut split = ParseConfig::default();
+        split.rules.disable(Rule::AttachAuxiliary);
+
+        let comparisons = compare_parses(
+            (&Lexicon, &config),
+            (&Lexicon, &split),
+            "猫。猫が食べました。",
+        )
+        .unwrap();
+
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].text, "猫が食べました。");
+        let differences: Vec<_> = comparisons[0]
+            .differences
+            .iter()
+            .map(|difference| (difference.to_string(), difference.range.clone()))
+            .collect();
+        assert_eq!(
+            differences,
+            [("segmentation: 食べました vs 食べ|まし|た".into(), 12..27)]
+        );
+    }
+
+    #[test]
+    fn finds_different_lemmas_of_the_same_words() {
+        let left = words("猫が嘘");
+        let mut right = left.clone();
+        right[2].lemma = Some("噓".into());
+
+        let differences = compare_words(&left, &right);
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].kind, DifferenceKind::Lemma);
+        assert_eq!(differences[0].to_string(), "lemma: 嘘 (嘘) vs 嘘 (噓)");
+    }
+
+    #[test]
+    fn lines_up_the_rest_when_one_side_runs_out() {
+        let left = words("猫が嘘");
+        let differences = compare_words(&left, &left[..1]);
+
+        assert_eq!(differences.len(), 1);
+        assert_eq!(differences[0].range, 3..9);
+        assert!(differences[0].right.is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostics::{ParseEvent, ParseObserver};
+use crate::difficulty::DifficultyModel;
+use crate::entities::EntityOptions;
+use crate::expressions::ExpressionOptions;
+use crate::fillers::Fillers;
+use crate::hook::Hook;
+use crate::kana::ReadingScript;
+use crate::normalize::NormalizeOptions;
+use crate::pipeline::Tokenize;
+use crate::postprocess::PostProcessors;
+use crate::progress::{Progress, ProgressObserver};
+use crate::readings::ReadingOverrides;
+use crate::romaji::RomajiOptions;
+use crate::rules::RuleSet;
+use crate::schema::{detect_schema, DictionarySchema, FeatureSchema};
+use crate::transcription::TranscriptionOptions;
+use crate::warnings::Strictness;
+use crate::{PartOfSpeech, PreparedToken};
+
+/// Settings for `parse_into_words_with`, for apps that expose them to their users.
+///
+/// All rules are enabled by default, which matches `parse_into_words`.
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct ParseConfig {
+    pub rules: RuleSet,
+    /// Feature layout of the dictionary, IPADIC's when not set. `Ve` and `Pipeline` detect
+    /// it from their dictionary, see [`ParseConfig::detect_schema`]
+    pub schema: Option<DictionarySchema>,
+    /// Reads the features of dictionaries with other layouts, used instead of `schema`,
+    /// see [`FeatureSchema`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub feature_schema: Option<Hook<dyn FeatureSchema>>,
+    /// Writes lemmas in the dictionary's canonical orthography, so variants like 行なう and
+    /// 行う get the same lemma, see [`Word::canonical_surface`](crate::Word::canonical_surface)
+    pub canonical_lemmas: bool,
+    /// Part of speech to assign to words based on the tags of their first token,
+    /// keyed by comma-separated tags as the dictionary writes them (e.g. `名詞,固有名詞`).
+    /// The most specific matching key wins.
+    pub pos_overrides: HashMap<String, PartOfSpeech>,
+    pub normalize: NormalizeOptions,
+    pub transcription: TranscriptionOptions,
+    pub reading_script: ReadingScript,
+    /// Readings to use instead of the dictionary's, see [`ReadingOverrides`]
+    pub reading_overrides: ReadingOverrides,
+    /// Adds romaji to words when set, see `WordExtra::romaji`
+    pub romaji: Option<RomajiOptions>,
+    pub expressions: ExpressionOptions,
+    pub entities: EntityOptions,
+    /// Whether to keep, tag or drop fillers like えーと and aizuchi like うん
+    pub fillers: Fillers,
+    pub strictness: Strictness,
+    /// Run over the words after grouping, see [`PostProcessors`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub post_processors: PostProcessors,
+    /// Notified of notable events while parsing, see [`ParseObserver`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub observer: Option<Hook<dyn ParseObserver>>,
+    /// Notified after each sentence of `parse_text` and `parse_pipelined`, see [`ProgressObserver`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub progress: Option<Hook<dyn ProgressObserver>>,
+    /// Scores how hard each word is, see [`DifficultyModel`]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub difficulty: Option<Hook<dyn DifficultyModel>>,
+}
+
+impl ParseConfig {
+    /// Reads a config from TOML, e.g.
+    ///
+    /// ```toml
+    /// strictness = "lenient"
+    /// reading_script = "hiragana"
+    /// schema = "unidic"
+    /// canonical_lemmas = true
+    /// fillers = "drop"
+    ///
+    /// [rules]
+    /// order = ["attach-suffix", "merge-numbers"]
+    /// attach-auxiliary = false
+    ///
+    /// [pos_overrides]
+    /// "名詞,固有名詞,人名" = "Noun"
+    ///
+    /// [normalize]
+    /// width = true
+    ///
+    /// [transcription]
+    /// expand_long_vowels = true
+    ///
+    /// [romaji]
+    /// system = "kunrei"
+    ///
+    /// [reading_overrides]
+    /// "今日" = "コンニチ"
+    ///
+    /// [expressions]
+    /// date_time = true
+    ///
+    /// [entities]
+    /// person_names = true
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn from_toml(config: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(config)
+    }
+
+    pub(crate) fn observe(&self, event: ParseEvent) {
+        if let Some(observer) = &self.observer {
+            observer.on_event(&event);
+        }
+    }
+
+    pub(crate) fn report_progress(&self, progress: &Progress) {
+        if let Some(observer) = &self.progress {
+            observer.on_progress(progress);
+        }
+    }
+
+    /// Sets `schema` to the one of the tokenizer's dictionary, see [`detect_schema`], unless
+    /// a schema is set already. Do this once after loading the dictionary, [`Ve`](crate::Ve)
+    /// and [`Pipeline`](crate::Pipeline) do it when they're built.
+    pub fn detect_schema(&mut self, tokenizer: &(impl Tokenize + ?Sized)) {
+        if self.schema.is_none() && self.feature_schema.is_none() {
+            self.schema = detect_schema(tokenizer);
+        }
+    }
+
+    /// The schema tokens are read with, IPADIC's unless one is set
+    pub(crate) fn read_schema(&self) -> &dyn FeatureSchema {
+        match &self.feature_schema {
+            Some(schema) => &**schema,
+            None => self.schema.unwrap_or_default().feature_schema(),
+        }
+    }
+}
+
+/// `ParseConfig::pos_overrides` with their keys split into tags, the most specific first
+pub(crate) struct PosOverrides<'a>(Vec<(Vec<&'a str>, PartOfSpeech)>);
+
+impl<'a> PosOverrides<'a> {
+    pub fn new(overrides: &'a HashMap<String, PartOfSpeech>) -> Self {
+        // Keys with more than the four POS levels can't match any token
+        let mut overrides: Vec<(Vec<&str>, PartOfSpeech)> = overrides
+            .iter()
+            .map(|(key, pos)| (key.split(',').collect::<Vec<_>>(), *pos))
+            .filter(|(key, _)| key.len() <= 4)
+            .collect();
+        overrides.sort_by_key(|(key, _)| Reverse(key.len()));
+
+        Self(overrides)
+    }
+
+    /// The part of speech for the token's tags as the dictionary has them, if one is set
+    pub fn get(&self, token: &PreparedToken) -> Option<PartOfSpeech> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let tags = token.raw_tags();
+        let tags = [&tags.pos1, &tags.pos2, &tags.pos3, &tags.pos4];
+
+        self.0
+            .iter()
+            .find(|(key, _)| key.iter().zip(tags).all(|(wanted, tag)| wanted == tag))
+            .map(|(_, pos)| *pos)
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::mpsc;
+use std::thread;
+
+use crate::{parse, prepare, ParseConfig, Progress, Result, Tokenize, Word};
+
+/// Sentences the tokenizer may get ahead of the grouping by
+const QUEUE: usize = 64;
+
+/// Parses many sentences with tokenizing and grouping on separate threads, so the dictionary
+/// lookups of one sentence overlap with the grouping rules of the ones before it. Tokens are
+/// prepared on the tokenizing thread, which keeps the two about equally busy.
+///
+/// `on_sentence` is called on the calling thread with each sentence and its words, in order.
+/// Stops at the first sentence that fails to parse.
+///
+/// The config's `progress` is told how many sentences there are in total when `sentences`
+/// knows its exact length up front, like a `Vec` does, so it gets a fraction and an ETA.
+///
+/// ```no_run
+/// use ve::{load_dictionary, parse_pipelined, ParseConfig};
+///
+/// let tokenizer = vibrato::Tokenizer::new(load_dictionary("system.dic.zst").unwrap());
+/// let corpus = std::fs::read_to_string("corpus.txt").unwrap();
+///
+/// let mut words = 0;
+/// parse_pipelined(&tokenizer, corpus.lines(), &ParseConfig::default(), |_, parsed| {
+///     words += parsed.len();
+/// })
+/// .unwrap();
+/// ```
+pub fn parse_pipelined<I, F>(
+    tokenizer: &(impl Tokenize + ?Sized),
+    sentences: I,
+    config: &ParseConfig,
+    mut on_sentence: F,
+) -> Result<()>
+where
+    I: IntoIterator,
+    I::IntoIter: Send,
+    I::Item: AsRef<str> + Send,
+    F: FnMut(I::Item, Vec<Word>),
+{
+    let sentences = sentences.into_iter();
+    let total_sentences = match sentences.size_hint() {
+        (lower, Some(upper)) if lower == upper => Some(upper),
+        _ => None,
+    };
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::sync_channel(QUEUE);
+
+        scope.spawn(move || {
+            for sentence in sentences {
+                let tokens = tokenizer
+                    .tokenize(sentence.as_ref())
+                    .and_then(|raw_tokens| prepare(raw_tokens, config, config.strictness));
+                // The receiver is gone once a sentence failed to parse
+                if sender.send((sentence, tokens)).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut progress = Progress::new(None);
+        progress.total_sentences = total_sentences;
+        for (sentence, tokens) in receiver {
+            let (tokens, _) = tokens?;
+            let (words, _) = parse(tokens, config, config.strictness)?;
+            progress.advance(sentence.as_ref().len());
+            on_sentence(sentence, words);
+            config.report_progress(&progress);
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,151 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Word;
+
+/// What a counter (助数詞) counts, e.g. `FlatObjects` for the 枚 of 3枚
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CounterClass {
+    /// 人, 名
+    People,
+    /// 匹, for small animals
+    Animals,
+    /// 頭, for large animals
+    LargeAnimals,
+    /// 羽
+    Birds,
+    /// 枚, for paper, shirts or plates
+    FlatObjects,
+    /// 本, for pens, bottles or trees
+    LongObjects,
+    /// 個 and つ
+    SmallObjects,
+    /// 台, for cars and appliances
+    Machines,
+    /// 冊
+    Books,
+    /// 軒 and 棟
+    Buildings,
+    /// 階
+    Floors,
+    /// 着, for clothes
+    Clothes,
+    /// 足, for shoes and socks, and 組 or 対
+    Pairs,
+    /// 杯, for cups and bowls
+    Servings,
+    /// 回 and 度
+    Occurrences,
+    /// 番, 位 or 号
+    Order,
+    /// 歳 and 才
+    Age,
+    /// 時, 分, 日, ヶ月, 年 and their durations like 時間
+    Time,
+    /// 円 or ドル
+    Money,
+    /// メートル, キロ or グラム
+    Measures,
+    /// Any other counter
+    Other,
+}
+
+impl CounterClass {
+    /// The class of the counter with the dictionary form `counter`
+    pub fn of(counter: &str) -> Self {
+        match counter {
+            "人" | "名" => Self::People,
+            "匹" => Self::Animals,
+            "頭" => Self::LargeAnimals,
+            "羽" => Self::Birds,
+            "枚" => Self::FlatObjects,
+            "本" => Self::LongObjects,
+            "個" | "つ" | "コ" => Self::SmallObjects,
+            "台" => Self::Machines,
+            "冊" => Self::Books,
+            "軒" | "棟" => Self::Buildings,
+            "階" => Self::Floors,
+            "着" => Self::Clothes,
+            "足" | "組" | "対" => Self::Pairs,
+            "杯" => Self::Servings,
+            "回" | "度" => Self::Occurrences,
+            "番" | "番目" | "位" | "号" | "等" => Self::Order,
+            "歳" | "才" => Self::Age,
+            "時" | "時間" | "分" | "分間" | "秒" | "秒間" | "日" | "日間" | "週" | "週間"
+            | "月" | "ヶ月" | "か月" | "カ月" | "ヵ月" | "年" | "年間" | "世紀" => {
+                Self::Time
+            }
+            "円" | "ドル" | "ユーロ" | "ポンド" | "元" | "ウォン" | "銭" => {
+                Self::Money
+            }
+            "メートル"
+            | "センチ"
+            | "ミリ"
+            | "キロ"
+            | "グラム"
+            | "トン"
+            | "リットル"
+            | "キロメートル"
+            | "キログラム"
+            | "センチメートル"
+            | "パーセント"
+            | "%"
+            | "％" => Self::Measures,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl Word {
+    /// The class of the word's counter, e.g. `People` for 3人. None if it has no counter.
+    pub fn counter(&self) -> Option<CounterClass> {
+        self.tokens.iter().find_map(|token| token.counter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    #[test]
+    fn classifies_counters() {
+        assert_eq!(CounterClass::of("枚"), CounterClass::FlatObjects);
+        assert_eq!(CounterClass::of("ヶ月"), CounterClass::Time);
+        assert_eq!(CounterClass::of("％"), CounterClass::Measures);
+        assert_eq!(CounterClass::of("猫"), CounterClass::Other);
+    }
+
+    #[test]
+    fn finds_the_counter_of_a_number() {
+        let words = words_from(
+            &[
+                ("三", "名詞,数,*,*,*,*,三,サン,サン"),
+                ("枚", "名詞,接尾,助数詞,*,*,*,枚,マイ,マイ"),
+                ("の", "助詞,連体化,*,*,*,*,の,ノ,ノ"),
+                ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+            ],
+            &ParseConfig::default(),
+        );
+
+        let counters: Vec<_> = words
+            .iter()
+            .map(|word| (word.word.as_str(), word.counter()))
+            .collect();
+        assert_eq!(
+            counters,
+            [
+                ("三枚", Some(CounterClass::FlatObjects)),
+                ("の", None),
+                ("猫", None)
+            ]
+        );
+        assert_eq!(
+            words[0].tokens[1].counter(),
+            Some(CounterClass::FlatObjects)
+        );
+    }
+}
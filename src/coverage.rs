@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::mining::is_content_word;
+use crate::{parse_text, KnownWords, ParseConfig, PartOfSpeech, Result, Sentence, Tokenize};
+
+/// How much of a text is covered by the words a learner knows, see `coverage`
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Coverage {
+    /// Content words, which are what's checked against the known words
+    pub words: usize,
+    pub known_words: usize,
+    /// Tokens of all words except symbols and numbers
+    pub tokens: usize,
+    /// Tokens of known content words and of particles, auxiliaries and other words
+    /// that aren't checked
+    pub known_tokens: usize,
+    /// Lemmas of the unknown content words, the most frequent first
+    pub unknown_lemmas: Vec<(String, usize)>,
+    pub sentences: Vec<SentenceCoverage>,
+}
+
+/// Coverage of the content words of a single sentence
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SentenceCoverage {
+    /// Byte offsets of the sentence in the text
+    pub span: Range<usize>,
+    pub words: usize,
+    pub known_words: usize,
+}
+
+fn ratio(known: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.0
+    } else {
+        known as f64 / total as f64
+    }
+}
+
+impl Coverage {
+    /// Share of content words that are known, between 0 and 1
+    pub fn word_coverage(&self) -> f64 {
+        ratio(self.known_words, self.words)
+    }
+
+    /// Share of tokens that are covered, between 0 and 1
+    pub fn token_coverage(&self) -> f64 {
+        ratio(self.known_tokens, self.tokens)
+    }
+}
+
+impl SentenceCoverage {
+    /// Share of the sentence's content words that are known, 1 if it has none
+    pub fn coverage(&self) -> f64 {
+        ratio(self.known_words, self.words)
+    }
+}
+
+/// Parses `text` and works out how much of it is covered by `known`, to tell whether
+/// it's readable for a learner. Content words are looked up by their lemma, see
+/// [`KnownWords`].
+pub fn coverage(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+    known: &KnownWords,
+) -> Result<Coverage> {
+    Ok(coverage_of(&parse_text(tokenizer, text, config)?, known))
+}
+
+/// Like `coverage`, for sentences that were already parsed
+pub fn coverage_of(sentences: &[Sentence], known: &KnownWords) -> Coverage {
+    let mut coverage = Coverage::default();
+    // Counts and first occurrence of each unknown lemma
+    let mut unknown: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for sentence in sentences {
+        let mut sentence_coverage = SentenceCoverage {
+            span: sentence.span.clone(),
+            ..Default::default()
+        };
+
+        for word in &sentence.words {
+            if matches!(
+                word.part_of_speech,
+                PartOfSpeech::Symbol | PartOfSpeech::Number
+            ) {
+                continue;
+            }
+            coverage.tokens += word.tokens.len();
+
+            if !is_content_word(word) {
+                coverage.known_tokens += word.tokens.len();
+                continue;
+            }
+
+            sentence_coverage.words += 1;
+            if known.contains(word) {
+                sentence_coverage.known_words += 1;
+                coverage.known_tokens += word.tokens.len();
+            } else {
+                let first = unknown.len();
+                unknown.entry(lemma(word)).or_insert((0, first)).0 += 1;
+            }
+        }
+
+        coverage.words += sentence_coverage.words;
+        coverage.known_words += sentence_coverage.known_words;
+        coverage.sentences.push(sentence_coverage);
+    }
+
+    let mut unknown: Vec<_> = unknown.into_iter().collect();
+    unknown.sort_by_key(|(_, (count, first))| (std::cmp::Reverse(*count), *first));
+    coverage.unknown_lemmas = unknown
+        .into_iter()
+        .map(|(lemma, (count, _))| (lemma.to_string(), count))
+        .collect();
+
+    coverage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+
+    #[test]
+    fn counts_known_content_words_and_tokens() {
+        let known = KnownWords::from_text("猫");
+        let text = "猫が嘘。嘘を食べました。";
+        let coverage = coverage(&Lexicon, text, &ParseConfig::default(), &known).unwrap();
+
+        assert_eq!((coverage.words, coverage.known_words), (4, 1));
+        assert_eq!((coverage.tokens, coverage.known_tokens), (8, 3));
+        assert_eq!(coverage.word_coverage(), 0.25);
+        assert_eq!(
+            coverage.unknown_lemmas,
+            [("嘘".into(), 2), ("食べる".into(), 1)]
+        );
+
+        let sentences: Vec<_> = coverage
+            .sentences
+            .iter()
+            .map(|sentence| (&text[sentence.span.clone()], sentence.coverage()))
+            .collect();
+        assert_eq!(sentences, [("猫が嘘。", 0.5), ("嘘を食べました。", 0.0)]);
+    }
+
+    #[test]
+    fn covers_texts_without_content_words() {
+        let coverage = coverage_of(&[], &KnownWords::new());
+
+        assert_eq!(coverage.word_coverage(), 1.0);
+        assert_eq!(coverage.token_coverage(), 1.0);
+    }
+}
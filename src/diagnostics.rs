@@ -0,0 +1,38 @@
+use crate::{Rule, Warning};
+
+/// Notable things that happen while preparing tokens and grouping them into words
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseEvent<'a> {
+    /// The main part of speech of a token isn't one of the IPADIC ones
+    UnknownPos { surface: &'a str, pos: &'a str },
+    /// A grouping rule decided what happens to a token
+    RuleFired { rule: Rule, surface: &'a str },
+    /// A token was left out of the output, like fillers with `Fillers::Drop`
+    TokenDropped { surface: &'a str, reason: &'a str },
+    /// A malformed token was worked around with `Strictness::Lenient`
+    Warning(&'a Warning),
+}
+
+/// Gets notified of [`ParseEvent`]s, e.g. to log them or collect metrics.
+///
+/// Closures taking a `&ParseEvent` implement this trait, so they can be installed directly:
+///
+/// ```
+/// use std::sync::Arc;
+/// use ve::{Hook, ParseConfig, ParseEvent};
+///
+/// let mut config = ParseConfig::default();
+/// config.observer = Some(Hook(Arc::new(|event: &ParseEvent| eprintln!("{:?}", event))));
+/// ```
+pub trait ParseObserver: Send + Sync {
+    fn on_event(&self, event: &ParseEvent);
+}
+
+impl<F> ParseObserver for F
+where
+    F: Fn(&ParseEvent) + Send + Sync,
+{
+    fn on_event(&self, event: &ParseEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,192 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::error::TokenizeError;
+
+/// Size and contents of a dictionary file, see [`inspect_dictionary`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DictionaryInfo {
+    /// Size of the (compressed) file on disk
+    pub file_size: u64,
+    /// Size of the decompressed dictionary. Vibrato decodes it into memory as a whole,
+    /// so this is roughly what a loaded dictionary takes up.
+    pub memory_size: u64,
+    pub system_entries: u64,
+    /// Entries of the user lexicon compiled into the dictionary, if it has one
+    pub user_entries: Option<u64>,
+}
+
+impl DictionaryInfo {
+    pub fn has_user_lexicon(&self) -> bool {
+        self.user_entries.is_some()
+    }
+}
+
+/// Environment variable with the path of the dictionary, see [`resolve_dictionary`]
+pub const DICTIONARY_ENV: &str = "VE_DICT_PATH";
+
+/// File name of the dictionary in the working directory and the cache directory
+pub const DEFAULT_DICTIONARY: &str = "system.dic.zst";
+
+/// Finds the dictionary to load, so apps don't depend on where they're started from.
+///
+/// An `explicit` path is used as it is. Otherwise the first file that exists of
+/// 1. the path in `VE_DICT_PATH`
+/// 2. `system.dic.zst` in the working directory
+/// 3. `system.dic.zst` in the platform's cache directory, under `ve`, e.g.
+///    `~/.cache/ve/system.dic.zst` on Linux (or in `$XDG_CACHE_HOME`)
+///
+/// Fails with [`TokenizeError::NotFound`] listing all of them if none does.
+pub fn resolve_dictionary(explicit: Option<&Path>) -> Result<PathBuf, TokenizeError> {
+    if let Some(path) = explicit {
+        return Ok(path.into());
+    }
+
+    let mut tried = Vec::new();
+    if let Some(path) = env::var_os(DICTIONARY_ENV).filter(|path| !path.is_empty()) {
+        tried.push(PathBuf::from(path));
+    }
+    tried.push(PathBuf::from(DEFAULT_DICTIONARY));
+    if let Some(cache) = cache_dir() {
+        tried.push(cache.join("ve").join(DEFAULT_DICTIONARY));
+    }
+
+    match tried.iter().find(|path| path.is_file()) {
+        Some(path) => Ok(path.clone()),
+        None => Err(TokenizeError::NotFound { tried }),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn cache_dir() -> Option<PathBuf> {
+    env::var_os("LOCALAPPDATA").map(PathBuf::from)
+}
+
+#[cfg(target_os = "macos")]
+fn cache_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Caches"))
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn cache_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CACHE_HOME")
+        .filter(|path| Path::new(path).is_absolute())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+}
+
+/// Reads the size and entry counts of a zstd-compressed vibrato dictionary,
+/// without loading it
+pub fn inspect_dictionary(path: impl AsRef<Path>) -> Result<DictionaryInfo, TokenizeError> {
+    let path = path.as_ref();
+    let io_error = |source| TokenizeError::Io {
+        path: path.into(),
+        source,
+    };
+
+    let file = File::open(path).map_err(io_error)?;
+    let file_size = file.metadata().map_err(io_error)?.len();
+    let decoder = zstd::Decoder::new(file).map_err(io_error)?;
+
+    let mut reader = CountingReader {
+        inner: decoder,
+        count: 0,
+    };
+
+    let (system_entries, user_entries) = read_entry_counts(&mut reader).map_err(io_error)?;
+    io::copy(&mut reader, &mut io::sink()).map_err(io_error)?;
+
+    Ok(DictionaryInfo {
+        file_size,
+        memory_size: reader.count,
+        system_entries,
+        user_entries,
+    })
+}
+
+/// Vibrato 0.5's file header
+const MAGIC: &[u8] = b"VibratoTokenizer 0.5\n";
+
+/// Walks the start of vibrato's bincode format (fixed-size little-endian ints),
+/// which begins with the system lexicon followed by the optional user lexicon
+fn read_entry_counts(reader: &mut impl Read) -> io::Result<(u64, Option<u64>)> {
+    let mut magic = [0; MAGIC.len()];
+    reader.read_exact(&mut magic)?;
+    if magic != MAGIC {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "not a vibrato 0.5 dictionary",
+        ));
+    }
+
+    let system_entries = read_lexicon(reader)?;
+
+    let mut has_user_lexicon = [0; 1];
+    reader.read_exact(&mut has_user_lexicon)?;
+    let user_entries = match has_user_lexicon[0] {
+        0 => None,
+        _ => Some(read_lexicon(reader)?),
+    };
+
+    Ok((system_entries, user_entries))
+}
+
+/// Skips over a lexicon and returns its number of entries
+fn read_lexicon(reader: &mut impl Read) -> io::Result<u64> {
+    // trie as bytes
+    let len = read_u64(reader)?;
+    skip(reader, len)?;
+
+    // postings as u32s
+    let len = read_u64(reader)?;
+    skip(reader, len * 4)?;
+
+    // word params, 3 16-bit ints each
+    let entries = read_u64(reader)?;
+    skip(reader, entries * 6)?;
+
+    // features as strings
+    let len = read_u64(reader)?;
+    for _ in 0..len {
+        let bytes = read_u64(reader)?;
+        skip(reader, bytes)?;
+    }
+
+    // lex type
+    skip(reader, 4)?;
+
+    Ok(entries)
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn skip(reader: &mut impl Read, len: u64) -> io::Result<()> {
+    let skipped = io::copy(&mut reader.take(len), &mut io::sink())?;
+    if skipped < len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
+}
+
+struct CountingReader<R> {
+    inner: R,
+    count: u64,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use crate::PartOfSpeech;
+
+/// Rates how hard a word is for a learner, from 0 (easiest) to 1 (hardest).
+/// Installed with `ParseConfig::difficulty`, the score ends up in `WordExtra::difficulty`.
+///
+/// Closures taking the lemma, part of speech and reading implement this trait:
+///
+/// ```
+/// use std::sync::Arc;
+/// use ve::{Hook, ParseConfig, PartOfSpeech};
+///
+/// let mut config = ParseConfig::default();
+/// config.difficulty = Some(Hook(Arc::new(|lemma: &str, _: PartOfSpeech, _: &str| {
+///     if lemma.chars().count() > 4 { 1.0 } else { 0.0 }
+/// })));
+/// ```
+pub trait DifficultyModel: Send + Sync {
+    fn score(&self, lemma: &str, part_of_speech: PartOfSpeech, reading: &str) -> f64;
+}
+
+impl<F> DifficultyModel for F
+where
+    F: Fn(&str, PartOfSpeech, &str) -> f64 + Send + Sync,
+{
+    fn score(&self, lemma: &str, part_of_speech: PartOfSpeech, reading: &str) -> f64 {
+        self(lemma, part_of_speech, reading)
+    }
+}
+
+/// Scores words by how frequent their lemma is in a frequency list: the most frequent
+/// lemma scores 0 and the score grows with the logarithm of the rank, so the difference
+/// between rank 10 and 100 counts as much as between 1000 and 10000.
+///
+/// Lemmas missing from the list score 1, numbers and symbols 0.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrequencyModel {
+    ranks: HashMap<String, usize>,
+}
+
+impl FrequencyModel {
+    /// Lemmas from the most to the least frequent
+    pub fn from_ranked<I, S>(lemmas: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut ranks = HashMap::new();
+        for (rank, lemma) in lemmas.into_iter().enumerate() {
+            // The first occurrence of a lemma is its most frequent one
+            ranks.entry(lemma.into()).or_insert(rank + 1);
+        }
+
+        Self { ranks }
+    }
+
+    /// Lemmas with how often they occurred in some corpus
+    pub fn from_counts<I, S>(counts: I) -> Self
+    where
+        I: IntoIterator<Item = (S, u64)>,
+        S: Into<String>,
+    {
+        let mut counts: Vec<(String, u64)> = counts
+            .into_iter()
+            .map(|(lemma, count)| (lemma.into(), count))
+            .collect();
+        counts.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+        Self::from_ranked(counts.into_iter().map(|(lemma, _)| lemma))
+    }
+
+    /// Reads a frequency list with one lemma per line, most frequent first. Only the first
+    /// tab-separated column is used, so lists with counts or readings in further columns work too.
+    pub fn from_list(list: &str) -> Self {
+        Self::from_ranked(
+            list.lines()
+                .filter_map(|line| line.split('\t').next())
+                .map(str::trim)
+                .filter(|lemma| !lemma.is_empty()),
+        )
+    }
+
+    /// The rank of `lemma` in the list, starting at 1
+    pub fn rank(&self, lemma: &str) -> Option<usize> {
+        self.ranks.get(lemma).copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranks.is_empty()
+    }
+}
+
+impl DifficultyModel for FrequencyModel {
+    fn score(&self, lemma: &str, part_of_speech: PartOfSpeech, _reading: &str) -> f64 {
+        if matches!(part_of_speech, PartOfSpeech::Number | PartOfSpeech::Symbol) {
+            return 0.0;
+        }
+
+        match self.rank(lemma) {
+            Some(rank) => (rank as f64).ln() / ((self.len() + 1) as f64).ln(),
+            None => 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::words_with;
+    use crate::{Hook, ParseConfig};
+
+    #[test]
+    fn ranks_lemmas_by_their_first_occurrence() {
+        let model = FrequencyModel::from_list("の\t100\n猫\n\nの\n嘘\t3\n");
+
+        assert_eq!(model.len(), 3);
+        assert_eq!(model.rank("の"), Some(1));
+        assert_eq!(model.rank("嘘"), Some(4));
+        assert_eq!(model.rank("犬"), None);
+    }
+
+    #[test]
+    fn ranks_lemmas_by_their_counts() {
+        let model = FrequencyModel::from_counts([("嘘", 2), ("猫", 10), ("が", 5)]);
+
+        assert_eq!(model.rank("猫"), Some(1));
+        assert_eq!(model.rank("嘘"), Some(3));
+    }
+
+    #[test]
+    fn scores_rare_lemmas_higher() {
+        let model = FrequencyModel::from_ranked(["が", "猫", "嘘"]);
+        let score = |lemma| model.score(lemma, PartOfSpeech::Noun, "");
+
+        assert_eq!(score("が"), 0.0);
+        assert!(score("猫") < score("嘘"));
+        assert!(score("嘘") < 1.0);
+        assert_eq!(score("犬"), 1.0);
+        assert_eq!(model.score("犬", PartOfSpeech::Symbol, ""), 0.0);
+    }
+
+    #[test]
+    fn scores_words_with_the_configured_model() {
+        let config = ParseConfig {
+            difficulty: Some(Hook(Arc::new(FrequencyModel::from_ranked(["猫"])))),
+            ..Default::default()
+        };
+
+        let scores: Vec<_> = words_with("猫嘘", &config)
+            .into_iter()
+            .map(|word| word.extra.difficulty)
+            .collect();
+        assert_eq!(scores, [Some(0.0), Some(1.0)]);
+    }
+}
@@ -0,0 +1,152 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::stats::parse_with_warnings;
+use crate::{ParseConfig, ParseStats, Progress, Result, Tokenize, Warning, Word};
+
+/// A sentence of a longer text, its words and what came up while parsing it
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sentence {
+    pub text: String,
+    /// Byte offsets of the sentence in the text, the same offsets `Word::range` uses
+    pub span: Range<usize>,
+    pub words: Vec<Word>,
+    /// Token counts, including unknown tokens, and timings of the sentence
+    pub stats: ParseStats,
+    /// What was worked around with `Strictness::Lenient`, with `Warning::sentence`
+    /// being the index of this sentence
+    pub warnings: Vec<Warning>,
+}
+
+/// Characters that end a sentence
+pub(crate) const TERMINATORS: &str = "。．！？!?";
+
+/// Characters that end a sentence together with a terminator, like …！
+pub(crate) const ELLIPSES: &str = "…‥";
+
+/// Sentences don't end inside brackets and quotes, like 「もう行く。また明日。」と言った。
+pub(crate) const BRACKETS: [(char, char); 10] = [
+    ('「', '」'),
+    ('『', '』'),
+    ('（', '）'),
+    ('(', ')'),
+    ('【', '】'),
+    ('〈', '〉'),
+    ('《', '》'),
+    ('［', '］'),
+    ('〔', '〕'),
+    ('“', '”'),
+];
+
+/// Splits `text` into sentences, after sentence-ending punctuation and at line breaks.
+/// Whitespace around sentences is left out of their spans.
+///
+/// Punctuation inside brackets and quotes doesn't end a sentence, except for quotes
+/// that stand on their own like 「はい。」「いいえ。」. Runs like ？！ or …！ end a sentence
+/// as a whole, ellipses on their own don't.
+pub fn split_sentences(text: &str) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if c == '\n' {
+            // Also closes any brackets left open, so they can't swallow the rest of the text
+            depth = 0;
+            push_trimmed(&mut spans, text, start..i);
+            start = i;
+        } else if BRACKETS.iter().any(|(open, _)| *open == c) {
+            depth += 1;
+        } else if BRACKETS.iter().any(|(_, close)| *close == c) {
+            depth = depth.saturating_sub(1);
+
+            let quote_ends = text[start..i].ends_with(|c| TERMINATORS.contains(c));
+            let stands_alone = match chars.peek() {
+                Some((_, next)) => {
+                    next.is_whitespace() || BRACKETS.iter().any(|(open, _)| open == next)
+                }
+                None => true,
+            };
+            if depth == 0 && quote_ends && stands_alone {
+                let end = i + c.len_utf8();
+                push_trimmed(&mut spans, text, start..end);
+                start = end;
+            }
+        } else if depth == 0 && (TERMINATORS.contains(c) || ELLIPSES.contains(c)) {
+            let mut end = i + c.len_utf8();
+            let mut terminated = TERMINATORS.contains(c);
+
+            while let Some(&(j, next)) = chars.peek() {
+                if !TERMINATORS.contains(next) && !ELLIPSES.contains(next) {
+                    break;
+                }
+                terminated |= TERMINATORS.contains(next);
+                end = j + next.len_utf8();
+                chars.next();
+            }
+
+            if terminated {
+                push_trimmed(&mut spans, text, start..end);
+                start = end;
+            }
+        }
+    }
+    push_trimmed(&mut spans, text, start..text.len());
+
+    spans
+}
+
+fn push_trimmed(spans: &mut Vec<Range<usize>>, text: &str, span: Range<usize>) {
+    let sentence = &text[span.clone()];
+    let trimmed = sentence.trim_start();
+    let start = span.start + sentence.len() - trimmed.len();
+    let end = start + trimmed.trim_end().len();
+
+    if start < end {
+        spans.push(start..end);
+    }
+}
+
+/// Splits `text` into sentences and parses them one by one, keeping track of where
+/// each sentence and word is in `text`.
+///
+/// The text isn't normalized, since that could shift the offsets. Apply
+/// `NormalizeOptions` beforehand if needed and keep the normalized text around.
+pub fn parse_text(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+) -> Result<Vec<Sentence>> {
+    let mut sentences = Vec::new();
+    let mut progress = Progress::new(Some(text.len()));
+
+    for (index, span) in split_sentences(text).into_iter().enumerate() {
+        let sentence = &text[span.clone()];
+        let end = span.end;
+        let (mut words, stats, mut warnings) = parse_with_warnings(tokenizer, sentence, config)?;
+
+        for word in &mut words {
+            word.shift(span.start);
+        }
+        for warning in &mut warnings {
+            warning.sentence = index;
+        }
+
+        sentences.push(Sentence {
+            text: sentence.into(),
+            span,
+            words,
+            stats,
+            warnings,
+        });
+        // Counted up to the end of the sentence, so the whitespace between sentences is too
+        progress.advance(end - progress.bytes);
+        config.report_progress(&progress);
+    }
+
+    Ok(sentences)
+}
@@ -0,0 +1,176 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::postprocess::{merge_adjacent, merge_runs};
+use crate::{PartOfSpeech, Word, POS};
+
+/// What a name refers to
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EntityKind {
+    Person,
+    Organization,
+    Place,
+}
+
+/// Which kinds of names to group into single words. They're all off by default.
+///
+/// The grouped words keep their tokens, so the parts of a name can still be shown separately.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct EntityOptions {
+    /// Surnames followed by given names (田中 + 太郎)
+    pub person_names: bool,
+    /// Organization names, including the likes of 株式会社 or 大学 around proper nouns
+    pub organizations: bool,
+    /// Consecutive place names, like the parts of an address (東京都 + 千代田区 + 永田町)
+    pub places: bool,
+}
+
+impl EntityOptions {
+    pub(crate) fn apply(&self, words: &mut Vec<Word>) {
+        if self.person_names {
+            group_person_names(words);
+        }
+        if self.organizations {
+            group_organizations(words);
+        }
+        if self.places {
+            group_places(words);
+        }
+    }
+}
+
+fn group_person_names(words: &mut Vec<Word>) {
+    let is_part = |word: &Word| {
+        word.tokens.iter().all(|t| {
+            t.pos2 == POS::KoyuuMeishi
+                && t.pos3 == POS::Jinmei
+                && [POS::Sei, POS::Mei].contains(&t.pos4)
+        })
+    };
+
+    merge_runs(
+        words,
+        is_part,
+        |_| true,
+        |word| {
+            word.part_of_speech = PartOfSpeech::ProperNoun;
+            word.extra.entity = Some(EntityKind::Person);
+        },
+    );
+}
+
+const ORGANIZATION_PREFIXES: [&str; 3] = ["株式会社", "有限会社", "合同会社"];
+
+const ORGANIZATION_SUFFIXES: [&str; 27] = [
+    "株式会社",
+    "有限会社",
+    "合同会社",
+    "ホールディングス",
+    "グループ",
+    "大学",
+    "大学院",
+    "高校",
+    "高等学校",
+    "中学校",
+    "小学校",
+    "学園",
+    "銀行",
+    "証券",
+    "病院",
+    "協会",
+    "財団",
+    "研究所",
+    "委員会",
+    "新聞",
+    "新聞社",
+    "省",
+    "庁",
+    "局",
+    "社",
+    "党",
+    "電機",
+];
+
+/// Affixes can be split into several words, like 有限 + 会社
+const MAX_AFFIX_WORDS: usize = 2;
+
+fn adjacent(words: &[Word]) -> bool {
+    words
+        .windows(2)
+        .all(|w| w[0].range().end == w[1].range().start)
+}
+
+fn spells_affix(words: &[Word], affixes: &[&str]) -> bool {
+    let affix: String = words.iter().map(|w| w.word.as_str()).collect();
+    affixes.contains(&affix.as_str())
+}
+
+/// Merges proper nouns with organization affixes around them (トヨタ + 株式会社)
+/// and tags them, as well as names the dictionary knows to be organizations
+fn group_organizations(words: &mut Vec<Word>) {
+    let mut i = 0;
+
+    while i < words.len() {
+        let Some(first) = words[i].tokens.first() else {
+            i += 1;
+            continue;
+        };
+        if first.pos2 != POS::KoyuuMeishi {
+            i += 1;
+            continue;
+        }
+
+        let mut is_organization = words[i].tokens.iter().any(|t| t.pos3 == POS::Soshiki);
+        let (mut start, mut end) = (i, i + 1);
+
+        let suffix = (1..=MAX_AFFIX_WORDS).find(|n| {
+            words
+                .get(i..=i + n)
+                .is_some_and(|run| adjacent(run) && spells_affix(&run[1..], &ORGANIZATION_SUFFIXES))
+        });
+        if let Some(n) = suffix {
+            end += n;
+            is_organization = true;
+        }
+
+        let prefix = (1..=MAX_AFFIX_WORDS.min(i)).find(|n| {
+            let run = &words[i - n..=i];
+            adjacent(run) && spells_affix(&run[..*n], &ORGANIZATION_PREFIXES)
+        });
+        if let Some(n) = prefix {
+            start -= n;
+            is_organization = true;
+        }
+
+        if is_organization {
+            merge_adjacent(words, start..end);
+
+            let word = &mut words[start];
+            word.part_of_speech = PartOfSpeech::ProperNoun;
+            word.extra.entity = Some(EntityKind::Organization);
+        }
+
+        i = start + 1;
+    }
+}
+
+fn group_places(words: &mut Vec<Word>) {
+    let is_part = |word: &Word| {
+        word.tokens
+            .iter()
+            .all(|t| [POS::KoyuuMeishi, POS::Setsubi].contains(&t.pos2) && t.pos3 == POS::Chiiki)
+    };
+
+    merge_runs(
+        words,
+        is_part,
+        |_| true,
+        |word| {
+            word.part_of_speech = PartOfSpeech::ProperNoun;
+            word.extra.entity = Some(EntityKind::Place);
+        },
+    );
+}
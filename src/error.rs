@@ -0,0 +1,139 @@
+use std::error::Error as StdError;
+use std::path::PathBuf;
+use std::{fmt, io};
+
+use vibrato::errors::VibratoError;
+
+use crate::Warning;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors of the different pipeline stages, so callers can tell problems with
+/// the dictionary (tokenizing) apart from problems with the input (preparing and grouping)
+#[derive(Debug)]
+pub enum Error {
+    Tokenize(TokenizeError),
+    Prepare(PrepareError),
+    Group(GroupError),
+}
+
+/// The dictionary couldn't be loaded or used
+#[derive(Debug)]
+pub enum TokenizeError {
+    /// The dictionary file couldn't be opened or decompressed
+    Io { path: PathBuf, source: io::Error },
+    /// No dictionary was given and none was found in the places `resolve_dictionary` looks
+    NotFound { tried: Vec<PathBuf> },
+    /// Vibrato couldn't read the dictionary or set up a tokenizer with it
+    Dictionary(VibratoError),
+    /// Lindera couldn't load the dictionary or tokenize with it
+    #[cfg(feature = "lindera")]
+    Lindera(lindera::error::LinderaError),
+    /// MeCab couldn't be started or stopped responding
+    #[cfg(feature = "mecab")]
+    Mecab(io::Error),
+}
+
+/// A token couldn't be prepared with `Strictness::Strict`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrepareError(pub Warning);
+
+/// A token couldn't be grouped into a word with `Strictness::Strict`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupError(pub Warning);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Tokenize(error) => error.fmt(f),
+            Error::Prepare(error) => error.fmt(f),
+            Error::Group(error) => error.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for TokenizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenizeError::Io { path, .. } => {
+                write!(f, "Couldn't open dictionary {}", path.display())
+            }
+            TokenizeError::NotFound { tried } => {
+                f.write_str("Couldn't find a dictionary, looked for")?;
+                for (i, path) in tried.iter().enumerate() {
+                    let separator = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", separator, path.display())?;
+                }
+                Ok(())
+            }
+            TokenizeError::Dictionary(_) => f.write_str("Couldn't load dictionary"),
+            #[cfg(feature = "lindera")]
+            TokenizeError::Lindera(_) => f.write_str("Lindera couldn't tokenize"),
+            #[cfg(feature = "mecab")]
+            TokenizeError::Mecab(_) => f.write_str("Couldn't run MeCab"),
+        }
+    }
+}
+
+impl fmt::Display for PrepareError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::Tokenize(error) => error.source(),
+            Error::Prepare(_) | Error::Group(_) => None,
+        }
+    }
+}
+
+impl StdError for TokenizeError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            TokenizeError::Io { source, .. } => Some(source),
+            TokenizeError::NotFound { .. } => None,
+            TokenizeError::Dictionary(error) => Some(error),
+            #[cfg(feature = "lindera")]
+            TokenizeError::Lindera(error) => Some(error),
+            #[cfg(feature = "mecab")]
+            TokenizeError::Mecab(error) => Some(error),
+        }
+    }
+}
+
+impl StdError for PrepareError {}
+
+impl StdError for GroupError {}
+
+impl From<TokenizeError> for Error {
+    fn from(error: TokenizeError) -> Self {
+        Error::Tokenize(error)
+    }
+}
+
+impl From<VibratoError> for TokenizeError {
+    fn from(error: VibratoError) -> Self {
+        TokenizeError::Dictionary(error)
+    }
+}
+
+impl From<PrepareError> for Error {
+    fn from(error: PrepareError) -> Self {
+        Error::Prepare(error)
+    }
+}
+
+impl From<GroupError> for Error {
+    fn from(error: GroupError) -> Self {
+        Error::Group(error)
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use crate::known::lemma;
+use crate::Sentence;
+
+/// A sentence of an indexed document
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexedSentence {
+    /// Index into `ExampleIndex::documents`
+    pub document: usize,
+    /// Byte offsets of the sentence in the document
+    pub span: Range<usize>,
+    pub text: String,
+}
+
+/// A word used in a sentence
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Example<'a> {
+    pub sentence: &'a IndexedSentence,
+    /// Byte offsets of the word in the document, like `Word::range`
+    pub range: &'a Range<usize>,
+}
+
+impl Example<'_> {
+    /// Byte offsets of the word in `sentence.text`
+    pub fn range_in_sentence(&self) -> Range<usize> {
+        let start = self.sentence.span.start;
+        self.range.start - start..self.range.end - start
+    }
+}
+
+/// Sentences of a corpus by the lemmas of their words, to show example sentences for a word.
+///
+/// Saved as a text file with one sentence or lemma per line, which `load` reads back.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExampleIndex {
+    /// Names of the indexed documents, like file names
+    pub documents: Vec<String>,
+    sentences: Vec<IndexedSentence>,
+    /// Sentence indices and word ranges of each lemma
+    lemmas: HashMap<String, Vec<(usize, Range<usize>)>>,
+}
+
+const HEADER: &str = "# ve example index 1";
+
+impl ExampleIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the sentences of a document, e.g. from `parse_text`
+    pub fn add_document(&mut self, name: &str, sentences: &[Sentence]) {
+        let document = self.documents.len();
+        self.documents.push(name.into());
+
+        for sentence in sentences {
+            let index = self.sentences.len();
+            self.sentences.push(IndexedSentence {
+                document,
+                span: sentence.span.clone(),
+                text: sentence.text.clone(),
+            });
+
+            for word in &sentence.words {
+                self.lemmas
+                    .entry(lemma(word).into())
+                    .or_default()
+                    .push((index, word.range()));
+            }
+        }
+    }
+
+    /// Where `lemma` is used, in the order the sentences were added
+    pub fn examples(&self, lemma: &str) -> Vec<Example<'_>> {
+        self.lemmas
+            .get(lemma)
+            .map(|uses| {
+                uses.iter()
+                    .map(|(sentence, range)| Example {
+                        sentence: &self.sentences[*sentence],
+                        range,
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn sentences(&self) -> &[IndexedSentence] {
+        &self.sentences
+    }
+
+    /// Number of different lemmas
+    pub fn len(&self) -> usize {
+        self.lemmas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lemmas.is_empty()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes documents as `D name`, sentences as `S document start end text` and lemmas as
+    /// `L lemma sentence:start-end ...`, with tab-separated fields
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writeln!(writer, "{}", HEADER)?;
+        for document in &self.documents {
+            writeln!(writer, "D\t{}", escape(document))?;
+        }
+        for sentence in &self.sentences {
+            writeln!(
+                writer,
+                "S\t{}\t{}\t{}\t{}",
+                sentence.document,
+                sentence.span.start,
+                sentence.span.end,
+                escape(&sentence.text)
+            )?;
+        }
+
+        let mut lemmas: Vec<_> = self.lemmas.iter().collect();
+        lemmas.sort_by_key(|(lemma, _)| *lemma);
+        for (lemma, uses) in lemmas {
+            write!(writer, "L\t{}", escape(lemma))?;
+            for (sentence, range) in uses {
+                write!(writer, "\t{}:{}-{}", sentence, range.start, range.end)?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn read_from(reader: impl BufRead) -> io::Result<Self> {
+        let mut index = Self::new();
+
+        for (number, line) in reader.lines().enumerate() {
+            let line = line?;
+            let invalid = || {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Malformed line {} in example index", number + 1),
+                )
+            };
+
+            if number == 0 {
+                if line != HEADER {
+                    return Err(invalid());
+                }
+                continue;
+            }
+
+            let mut fields = line.split('\t');
+            match fields.next() {
+                Some("D") => {
+                    let name = fields.next().ok_or_else(invalid)?;
+                    index.documents.push(unescape(name));
+                }
+                Some("S") => {
+                    let mut offset = || -> io::Result<usize> {
+                        fields
+                            .next()
+                            .and_then(|f| f.parse().ok())
+                            .ok_or_else(invalid)
+                    };
+                    let (document, start, end) = (offset()?, offset()?, offset()?);
+                    let text = fields.next().ok_or_else(invalid)?;
+
+                    if document >= index.documents.len() {
+                        return Err(invalid());
+                    }
+                    index.sentences.push(IndexedSentence {
+                        document,
+                        span: start..end,
+                        text: unescape(text),
+                    });
+                }
+                Some("L") => {
+                    let lemma = unescape(fields.next().ok_or_else(invalid)?);
+                    let uses = fields
+                        .map(|field| {
+                            let (sentence, range) = field.split_once(':')?;
+                            let (start, end) = range.split_once('-')?;
+                            let sentence: usize = sentence.parse().ok()?;
+                            (sentence < index.sentences.len())
+                                .then_some((sentence, start.parse().ok()?..end.parse().ok()?))
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .ok_or_else(invalid)?;
+                    index.lemmas.insert(lemma, uses);
+                }
+                Some("") => {}
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('t') => result.push('\t'),
+            Some('n') => result.push('\n'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+    use crate::{parse_text, ParseConfig};
+
+    fn index() -> ExampleIndex {
+        let config = ParseConfig::default();
+        let mut index = ExampleIndex::new();
+        index.add_document("a.txt", &parse_text(&Lexicon, "猫が嘘。", &config).unwrap());
+        index.add_document(
+            "b\tc.txt",
+            &parse_text(&Lexicon, "嘘。猫を食べました。", &config).unwrap(),
+        );
+        index
+    }
+
+    #[test]
+    fn finds_the_sentences_of_a_lemma() {
+        let index = index();
+        let examples: Vec<_> = index
+            .examples("猫")
+            .iter()
+            .map(|example| {
+                let sentence = &example.sentence.text;
+                (
+                    example.sentence.document,
+                    &sentence[example.range_in_sentence()],
+                    sentence.as_str(),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            examples,
+            [(0, "猫", "猫が嘘。"), (1, "猫", "猫を食べました。")]
+        );
+        assert_eq!(index.examples("食べる")[0].range, &(12..27));
+        assert!(index.examples("犬").is_empty());
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let index = index();
+        let mut saved = Vec::new();
+        index.write_to(&mut saved).unwrap();
+
+        let text = String::from_utf8(saved.clone()).unwrap();
+        assert!(text.starts_with("# ve example index 1\nD\ta.txt\nD\tb\\tc.txt\n"));
+        assert_eq!(ExampleIndex::read_from(saved.as_slice()).unwrap(), index);
+    }
+
+    #[test]
+    fn rejects_malformed_indices() {
+        for saved in [
+            "# some other file\n",
+            "# ve example index 1\nS\t0\t0\t3\t猫\n",
+            "# ve example index 1\nD\ta\nS\t0\t0\t3\t猫\nL\t猫\t1:0-3\n",
+            "# ve example index 1\nX\n",
+        ] {
+            let error = ExampleIndex::read_from(saved.as_bytes()).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+
+    #[test]
+    fn escapes_tabs_and_line_breaks() {
+        let text = "a\tb\nc\\d\r";
+
+        assert_eq!(escape(text), "a\\tb\\nc\\\\d\\r");
+        assert_eq!(unescape(&escape(text)), text);
+    }
+}
@@ -0,0 +1,205 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::postprocess::merge_adjacent;
+use crate::postprocess::merge_runs;
+use crate::{normalize_width, PartOfSpeech, PreparedToken, Word, POS};
+
+/// Kinds of multi-word expressions that get grouped into a single word
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Expression {
+    /// Dates and times like 10月27日午後3時
+    DateTime,
+    /// Amounts of money and measurements like 3000円, 5キロメートル or 20%
+    Quantity { unit: String },
+}
+
+/// Which expressions to group into single words after grouping tokens. Both are off by
+/// default, so words stay as the rules group them unless asked for.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct ExpressionOptions {
+    pub date_time: bool,
+    pub quantities: bool,
+}
+
+impl ExpressionOptions {
+    pub(crate) fn apply(&self, words: &mut Vec<Word>) {
+        if self.date_time {
+            group_date_times(words);
+        }
+        if self.quantities {
+            group_quantities(words);
+        }
+    }
+}
+
+const DATE_TIME_UNITS: [&str; 10] = [
+    "年", "月", "日", "時", "分", "秒", "時半", "半", "午前", "午後",
+];
+
+/// Parts a date or time is made of
+#[derive(PartialEq)]
+enum DateTimePart {
+    Number,
+    Unit,
+}
+
+fn date_time_part(token: &PreparedToken) -> Option<DateTimePart> {
+    if token.pos != POS::Meishi {
+        None
+    } else if token.pos2 == POS::Kazu {
+        Some(DateTimePart::Number)
+    } else if DATE_TIME_UNITS.contains(&token.literal.as_str()) || token.literal.ends_with("曜日")
+    {
+        Some(DateTimePart::Unit)
+    } else {
+        None
+    }
+}
+
+/// Merges runs of numbers and date/time units like 2023年10月27日 or 午後3時半
+fn group_date_times(words: &mut Vec<Word>) {
+    let is_part = |word: &Word| word.tokens.iter().all(|t| date_time_part(t).is_some());
+
+    // a lone 月 is the moon and a lone number is just that
+    let accept = |run: &[Word]| {
+        let parts: Vec<_> = run
+            .iter()
+            .flat_map(|w| &w.tokens)
+            .filter_map(date_time_part)
+            .collect();
+
+        parts.contains(&DateTimePart::Number) && parts.contains(&DateTimePart::Unit)
+    };
+
+    merge_runs(words, is_part, accept, |word| {
+        word.extra.expression = Some(Expression::DateTime)
+    });
+}
+
+const CURRENCIES: [&str; 9] = [
+    "円",
+    "ドル",
+    "ユーロ",
+    "ポンド",
+    "元",
+    "ウォン",
+    "銭",
+    "セント",
+    "ルピー",
+];
+
+const CURRENCY_SYMBOLS: [&str; 4] = ["$", "¥", "€", "£"];
+
+/// 度 isn't one, since it counts times, like 一度 (once), as often as it's degrees
+const MEASUREMENTS: [&str; 45] = [
+    "メートル",
+    "キロメートル",
+    "センチメートル",
+    "ミリメートル",
+    "キロ",
+    "センチ",
+    "ミリ",
+    "グラム",
+    "キログラム",
+    "ミリグラム",
+    "トン",
+    "リットル",
+    "ミリリットル",
+    "ヘクタール",
+    "パーセント",
+    "ワット",
+    "キロワット",
+    "ボルト",
+    "カロリー",
+    "キロカロリー",
+    "バイト",
+    "キロバイト",
+    "メガバイト",
+    "ギガバイト",
+    "テラバイト",
+    "%",
+    "℃",
+    "°C",
+    "m",
+    "km",
+    "cm",
+    "mm",
+    "g",
+    "kg",
+    "mg",
+    "t",
+    "l",
+    "L",
+    "ml",
+    "mL",
+    "ha",
+    "W",
+    "kW",
+    "MB",
+    "GB",
+];
+
+fn is_unit(text: &str) -> bool {
+    let text = normalize_width(text);
+    CURRENCIES.contains(&text.as_str()) || MEASUREMENTS.contains(&text.as_str())
+}
+
+/// Number of tokens at the start of the word that make up a number like 3, 2.5 or 1,000
+fn amount_len(word: &Word) -> usize {
+    word.tokens
+        .iter()
+        .take_while(|t| {
+            t.pos2 == POS::Kazu || [".", ","].contains(&normalize_width(&t.literal).as_str())
+        })
+        .count()
+}
+
+fn unit_of(tokens: &[PreparedToken]) -> Option<String> {
+    let unit: String = tokens.iter().map(|t| t.literal.as_str()).collect();
+    is_unit(&unit).then_some(unit)
+}
+
+/// Merges numbers with the currency or unit of measurement around them, like 3000円, $20 or 20%
+fn group_quantities(words: &mut Vec<Word>) {
+    let adjacent = |a: &Word, b: &Word| a.range().end == b.range().start;
+    let mut i = 0;
+
+    while i < words.len() {
+        let amount = amount_len(&words[i]);
+        if amount == 0 || words[i].tokens[0].pos2 != POS::Kazu {
+            i += 1;
+            continue;
+        }
+
+        let (mut start, mut end) = (i, i + 1);
+        let mut unit = unit_of(&words[i].tokens[amount..]);
+
+        if amount == words[i].tokens.len() {
+            let following = words.get(i + 1).filter(|w| adjacent(&words[i], w));
+            if let Some(following_unit) = following.and_then(|w| unit_of(&w.tokens)) {
+                unit = Some(following_unit);
+                end += 1;
+            } else if let Some(previous) = i.checked_sub(1).map(|p| &words[p]) {
+                let symbol = normalize_width(&previous.word);
+                if CURRENCY_SYMBOLS.contains(&symbol.as_str()) && adjacent(previous, &words[i]) {
+                    unit = Some(previous.word.clone());
+                    start -= 1;
+                }
+            }
+        }
+
+        if let Some(unit) = unit {
+            merge_adjacent(words, start..end);
+
+            let word = &mut words[start];
+            word.part_of_speech = PartOfSpeech::Number;
+            word.extra.expression = Some(Expression::Quantity { unit });
+        }
+
+        i = start + 1;
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::to_hiragana;
+use crate::{ParseConfig, ParseEvent, PartOfSpeech, Word, POS};
+
+/// Hesitations, spelled in hiragana without trailing long vowel marks
+const FILLERS: [&str; 12] = [
+    "え",
+    "えと",
+    "えっと",
+    "ええと",
+    "えーと",
+    "あの",
+    "その",
+    "ま",
+    "まあ",
+    "あ",
+    "う",
+    "うーん",
+];
+
+/// Backchannel responses (相槌), spelled like `FILLERS`
+const AIZUCHI: [&str; 11] = [
+    "うん",
+    "ええ",
+    "はい",
+    "へ",
+    "へえ",
+    "ほう",
+    "ふーん",
+    "ふうん",
+    "なるほど",
+    "ああ",
+    "おお",
+];
+
+/// Kinds of words that carry little meaning in speech, see `Fillers`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum FillerKind {
+    /// Hesitations like えーと or あのー
+    Filler,
+    /// Backchannel responses like うん or なるほど
+    Aizuchi,
+}
+
+/// What to do with fillers and aizuchi, e.g. when cleaning up transcripts
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Fillers {
+    /// Leave them in the words like any other interjection
+    #[default]
+    Keep,
+    /// Keep them, but mark them in `WordExtra::filler`
+    Tag,
+    /// Leave them out of the words, along with the commas after them
+    Drop,
+}
+
+impl Fillers {
+    pub(crate) fn apply(self, words: &mut Vec<Word>, config: &ParseConfig) {
+        match self {
+            Fillers::Keep => (),
+            Fillers::Tag => {
+                for word in words {
+                    word.extra.filler = word.filler_kind();
+                }
+            }
+            Fillers::Drop => {
+                // The commas after them go too, so えーと、行く becomes just 行く
+                let mut dropped = false;
+                words.retain(|word| {
+                    let reason = match word.filler_kind() {
+                        Some(FillerKind::Filler) => Some("filler"),
+                        Some(FillerKind::Aizuchi) => Some("aizuchi"),
+                        None if dropped && matches!(word.word.as_str(), "、" | "，" | ",") => {
+                            Some("comma after a filler")
+                        }
+                        None => None,
+                    };
+                    dropped = reason.is_some();
+
+                    if let Some(reason) = reason {
+                        for token in &word.tokens {
+                            config.observe(ParseEvent::TokenDropped {
+                                surface: &token.literal,
+                                reason,
+                            });
+                        }
+                    }
+                    !dropped
+                });
+            }
+        }
+    }
+}
+
+impl Word {
+    /// Whether the word is a filler like えーと or an aizuchi like うん.
+    /// Only interjections count, so the あの of あの人 isn't one.
+    pub fn filler_kind(&self) -> Option<FillerKind> {
+        if self.part_of_speech != PartOfSpeech::Interjection {
+            return None;
+        }
+        if self.tokens.iter().all(|token| token.pos == POS::Firaa) {
+            return Some(FillerKind::Filler);
+        }
+
+        let spelling = to_hiragana(&self.word);
+        let spelling = spelling.trim_end_matches(['ー', '〜', '～', 'っ', '…']);
+        if FILLERS.contains(&spelling) {
+            Some(FillerKind::Filler)
+        } else if AIZUCHI.contains(&spelling) {
+            Some(FillerKind::Aizuchi)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+
+    const SPEECH: [(&str, &str); 6] = [
+        ("えーと", "フィラー,*,*,*,*,*,えーと,エート,エート"),
+        ("、", "記号,読点,*,*,*,*,、,、,、"),
+        ("ウーン", "感動詞,*,*,*,*,*,うーん,ウーン,ウーン"),
+        ("うん", "感動詞,*,*,*,*,*,うん,ウン,ウン"),
+        ("あの", "連体詞,*,*,*,*,*,あの,アノ,アノ"),
+        ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+    ];
+
+    fn words_with_fillers(fillers: Fillers) -> Vec<Word> {
+        let config = ParseConfig {
+            fillers,
+            ..Default::default()
+        };
+        words_from(&SPEECH, &config)
+    }
+
+    #[test]
+    fn tells_fillers_from_aizuchi() {
+        let kinds: Vec<_> = words_with_fillers(Fillers::Keep)
+            .iter()
+            .map(Word::filler_kind)
+            .collect();
+
+        assert_eq!(
+            kinds,
+            [
+                Some(FillerKind::Filler),
+                None,
+                Some(FillerKind::Filler),
+                Some(FillerKind::Aizuchi),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn tags_fillers_when_asked_to() {
+        let tagged: Vec<_> = words_with_fillers(Fillers::Tag)
+            .into_iter()
+            .map(|word| word.extra.filler)
+            .collect();
+        assert_eq!(tagged[..2], [Some(FillerKind::Filler), None]);
+
+        let kept = words_with_fillers(Fillers::Keep);
+        assert!(kept.iter().all(|word| word.extra.filler.is_none()));
+    }
+
+    #[test]
+    fn drops_fillers_and_the_commas_after_them() {
+        let surfaces: Vec<_> = words_with_fillers(Fillers::Drop)
+            .into_iter()
+            .map(|word| word.word)
+            .collect();
+
+        assert_eq!(surfaces, ["あの", "猫"]);
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::kana::{to_hiragana, to_katakana};
+use crate::SearchToken;
+
+/// Changes or removes tokens of the search token stream, like a Lucene `TokenFilter`.
+///
+/// Closures taking a `&mut SearchToken` implement this trait:
+///
+/// ```
+/// use ve::{FilterChain, SearchToken};
+///
+/// let mut filters = FilterChain::default();
+/// filters.push(|token: &mut SearchToken| !token.term.chars().all(|c| c.is_ascii_digit()));
+/// ```
+pub trait TokenFilter: Send + Sync {
+    /// Changes `token` in place, returning false to remove it from the stream
+    fn filter(&self, token: &mut SearchToken) -> bool;
+
+    /// Tokens to stack at the position of `token` once it passed `filter`,
+    /// e.g. readings so that queries in kana match kanji. They go through
+    /// the following filters like any other token.
+    fn expand(&self, _token: &SearchToken) -> Vec<SearchToken> {
+        Vec::new()
+    }
+}
+
+impl<F> TokenFilter for F
+where
+    F: Fn(&mut SearchToken) -> bool + Send + Sync,
+{
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        self(token)
+    }
+}
+
+/// Lowercases Latin letters, so ＩＰｈｏｎｅ and iphone match after width normalization
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LowercaseFilter;
+
+impl TokenFilter for LowercaseFilter {
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        token.term = token.term.to_lowercase();
+        true
+    }
+}
+
+/// Writes kana in one script, so ひらがな and ヒラガナ match
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum KanaFilter {
+    #[default]
+    Katakana,
+    Hiragana,
+}
+
+impl TokenFilter for KanaFilter {
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        token.term = match self {
+            KanaFilter::Katakana => to_katakana(&token.term),
+            KanaFilter::Hiragana => to_hiragana(&token.term),
+        };
+        true
+    }
+}
+
+/// Replaces terms with their lemma, so 行った and 行きます both become 行く
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LemmaFilter;
+
+impl TokenFilter for LemmaFilter {
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        token.term.clone_from(&token.lemma);
+        true
+    }
+}
+
+/// Common function words, in the form they take as lemmas
+#[rustfmt::skip]
+const STOPWORDS: [&str; 62] = [
+    "の", "に", "は", "を", "た", "が", "で", "て", "と", "し", "れる", "られる", "さ", "ある",
+    "いる", "も", "する", "から", "な", "こと", "として", "い", "や", "など", "なる", "ない",
+    "この", "ため", "その", "よう", "また", "もの", "という", "まで", "へ", "か", "だ", "これ",
+    "によって", "により", "による", "ず", "より", "ば", "しかし", "について", "できる", "それ",
+    "う", "ので", "なお", "のみ", "における", "および", "いう", "さらに", "でも", "ら", "たり",
+    "ます", "ん", "です",
+];
+
+/// Removes the given terms. The default list has common particles and auxiliaries
+/// in their lemma form, so it goes after a `LemmaFilter`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StopFilter(pub HashSet<String>);
+
+impl StopFilter {
+    pub fn new<I, S>(stopwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self(stopwords.into_iter().map(Into::into).collect())
+    }
+}
+
+impl Default for StopFilter {
+    fn default() -> Self {
+        Self::new(STOPWORDS)
+    }
+}
+
+impl TokenFilter for StopFilter {
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        !self.0.contains(&token.term)
+    }
+}
+
+/// Removes terms shorter than `min` or longer than `max` characters
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LengthFilter {
+    pub min: usize,
+    pub max: usize,
+}
+
+impl TokenFilter for LengthFilter {
+    fn filter(&self, token: &mut SearchToken) -> bool {
+        let length = token.term.chars().count();
+        self.min <= length && length <= self.max
+    }
+}
+
+/// Stacks the surface, lemma and readings of a word on its token, turning the stream into
+/// a token graph: an index of 行った then also matches queries for 行く, いった or いく
+/// (with a `KanaFilter` at both ends).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExpansionFilter {
+    pub surface: bool,
+    pub lemma: bool,
+    /// The readings of the word and of its lemma
+    pub reading: bool,
+}
+
+impl Default for ExpansionFilter {
+    fn default() -> Self {
+        Self {
+            surface: true,
+            lemma: true,
+            reading: true,
+        }
+    }
+}
+
+impl TokenFilter for ExpansionFilter {
+    fn filter(&self, _token: &mut SearchToken) -> bool {
+        true
+    }
+
+    fn expand(&self, token: &SearchToken) -> Vec<SearchToken> {
+        let mut terms: Vec<&str> = Vec::new();
+        if self.surface {
+            terms.push(&token.surface);
+        }
+        if self.lemma {
+            terms.push(&token.lemma);
+        }
+        if self.reading {
+            terms.push(&token.reading);
+            terms.extend(token.lemma_reading.as_deref());
+        }
+
+        let mut expanded: Vec<SearchToken> = Vec::new();
+        for term in terms {
+            let seen = term == token.term || expanded.iter().any(|t| t.term == term);
+            if !seen && !term.is_empty() {
+                expanded.push(SearchToken {
+                    term: term.into(),
+                    position_increment: 0,
+                    ..token.clone()
+                });
+            }
+        }
+        expanded
+    }
+}
+
+/// Token filters that run one after the other over the search token stream.
+/// Removed tokens leave a gap in the positions, like with Lucene's filters.
+///
+/// The default chain replaces terms with their lemma and removes common function words.
+#[derive(Clone)]
+pub struct FilterChain(Vec<Arc<dyn TokenFilter>>);
+
+impl FilterChain {
+    /// A chain without any filters, keeping the surfaces of all words
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn push(&mut self, filter: impl TokenFilter + 'static) -> &mut Self {
+        self.0.push(Arc::new(filter));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Runs the filters over `tokens`, adding the position increments
+    /// of removed tokens to the next one that's kept
+    pub fn apply(&self, mut tokens: Vec<SearchToken>) -> Vec<SearchToken> {
+        for filter in &self.0 {
+            let mut kept = Vec::with_capacity(tokens.len());
+            let mut removed_increments = 0;
+
+            for mut token in tokens {
+                if filter.filter(&mut token) {
+                    token.position_increment += removed_increments;
+                    removed_increments = 0;
+                    let expanded = filter.expand(&token);
+                    kept.push(token);
+                    kept.extend(expanded);
+                } else {
+                    removed_increments += token.position_increment;
+                }
+            }
+
+            tokens = kept;
+        }
+
+        tokens
+    }
+}
+
+impl Default for FilterChain {
+    fn default() -> Self {
+        let mut chain = Self::new();
+        chain.push(LemmaFilter).push(StopFilter::default());
+        chain
+    }
+}
+
+impl fmt::Debug for FilterChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FilterChain({} filters)", self.0.len())
+    }
+}
+
+impl PartialEq for FilterChain {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.len() == other.0.len() && self.0.iter().zip(&other.0).all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::search_tokens;
+    use crate::testing::Lexicon;
+    use crate::ParseConfig;
+
+    fn terms(text: &str, filters: &FilterChain) -> Vec<(String, usize)> {
+        search_tokens(&Lexicon, text, &ParseConfig::default(), filters)
+            .unwrap()
+            .into_iter()
+            .map(|token| (token.term, token.position_increment))
+            .collect()
+    }
+
+    #[test]
+    fn lemmatizes_and_removes_stopwords_by_default() {
+        assert_eq!(
+            terms("猫が嘘を食べました", &FilterChain::default()),
+            [("猫".into(), 1), ("嘘".into(), 2), ("食べる".into(), 2)]
+        );
+    }
+
+    #[test]
+    fn runs_filters_in_order() {
+        let mut filters = FilterChain::new();
+        filters
+            .push(KanaFilter::Katakana)
+            .push(LengthFilter { min: 1, max: 2 })
+            .push(|token: &mut SearchToken| token.term != "嘘");
+
+        assert_eq!(filters.len(), 3);
+        assert_eq!(
+            terms("猫が食べました嘘猫", &filters),
+            [("猫".into(), 1), ("ガ".into(), 1), ("猫".into(), 3)]
+        );
+    }
+
+    #[test]
+    fn lowercases_latin_letters() {
+        let mut token = search_tokens(
+            &Lexicon,
+            "ＡＢc",
+            &ParseConfig::default(),
+            &FilterChain::new(),
+        )
+        .unwrap()
+        .remove(0);
+
+        assert!(LowercaseFilter.filter(&mut token));
+        assert_eq!(token.term, "ａｂc");
+    }
+
+    #[test]
+    fn stacks_lemmas_and_readings_on_words() {
+        let mut filters = FilterChain::default();
+        filters
+            .push(ExpansionFilter::default())
+            .push(KanaFilter::Hiragana);
+
+        assert_eq!(
+            terms("嘘を食べました", &filters),
+            [
+                ("嘘".into(), 1),
+                ("うそ".into(), 0),
+                ("食べる".into(), 2),
+                ("食べました".into(), 0),
+                ("たべました".into(), 0),
+                ("たべる".into(), 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn stacks_only_the_chosen_terms() {
+        let mut filters = FilterChain::new();
+        filters.push(ExpansionFilter {
+            surface: false,
+            lemma: true,
+            reading: false,
+        });
+
+        assert_eq!(
+            terms("猫食べました", &filters),
+            [
+                ("猫".into(), 1),
+                ("食べました".into(), 1),
+                ("食べる".into(), 0)
+            ]
+        );
+    }
+}
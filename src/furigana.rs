@@ -0,0 +1,370 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::{to_hiragana, to_katakana};
+use crate::{ReadingOverrides, Word};
+
+/// A piece of a word's surface, with the hiragana reading of its kanji.
+/// Kana and other characters don't get a reading.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FuriganaSegment {
+    pub text: String,
+    pub reading: Option<String>,
+}
+
+/// Common readings of frequent kanji, used to split the reading of kanji compounds
+/// between their kanji. Compounds with kanji missing here keep a single reading.
+#[rustfmt::skip]
+const KANJI_READINGS: [(char, &str); 165] = [
+    ('一', "イチ,イッ,ヒト"), ('二', "ニ,フタ"), ('三', "サン,ミ"), ('四', "シ,ヨン,ヨ"),
+    ('五', "ゴ,イツ"), ('六', "ロク,ロッ,ム"), ('七', "シチ,ナナ"), ('八', "ハチ,ハッ,ヤ"),
+    ('九', "キュウ,ク,ココノ"), ('十', "ジュウ,ジュッ,ジッ,トオ"), ('百', "ヒャク,ヒャッ"),
+    ('千', "セン,チ"), ('万', "マン,バン"), ('円', "エン,マル"), ('年', "ネン,トシ"),
+    ('月', "ゲツ,ガツ,ツキ"), ('日', "ニチ,ジツ,ヒ,カ,ニッ"), ('時', "ジ,トキ"),
+    ('分', "フン,ブン,プン,ワ"), ('半', "ハン"), ('週', "シュウ"), ('今', "コン,イマ"),
+    ('毎', "マイ"), ('午', "ゴ"), ('前', "ゼン,マエ"), ('後', "ゴ,コウ,アト,ウシ,ノチ"),
+    ('上', "ジョウ,ウエ,カミ,ア,ノボ"), ('下', "カ,ゲ,シタ,シモ,サ,クダ,オ"),
+    ('中', "チュウ,ナカ,ジュウ"), ('外', "ガイ,ゲ,ソト,ホカ"), ('右', "ウ,ユウ,ミギ"),
+    ('左', "サ,ヒダリ"), ('東', "トウ,ヒガシ"), ('西', "セイ,サイ,ニシ"), ('南', "ナン,ミナミ"),
+    ('北', "ホク,ホッ,キタ"), ('大', "ダイ,タイ,オオ"), ('小', "ショウ,チイ,コ,オ"),
+    ('高', "コウ,タカ"), ('長', "チョウ,ナガ"), ('新', "シン,アタラ,ニイ"), ('古', "コ,フル"),
+    ('多', "タ,オオ"), ('少', "ショウ,スク,スコ"), ('早', "ソウ,ハヤ"), ('安', "アン,ヤス"),
+    ('人', "ジン,ニン,ヒト"), ('子', "シ,ス,コ"), ('女', "ジョ,ニョ,オンナ,メ"),
+    ('男', "ダン,ナン,オトコ"), ('父', "フ,チチ"), ('母', "ボ,ハハ"), ('友', "ユウ,トモ"),
+    ('先', "セン,サキ"), ('生', "セイ,ショウ,イ,ウ,ナマ,キ"), ('学', "ガク,ガッ,マナ"),
+    ('校', "コウ"), ('会', "カイ,エ,ア"), ('社', "シャ,ヤシロ"), ('員', "イン"),
+    ('国', "コク,コッ,クニ"), ('語', "ゴ,カタ"), ('本', "ホン,ボン,ポン,モト"),
+    ('名', "メイ,ミョウ,ナ"), ('山', "サン,ザン,ヤマ"), ('川', "セン,カワ"), ('田', "デン,タ"),
+    ('村', "ソン,ムラ"), ('町', "チョウ,マチ"), ('市', "シ,イチ"), ('都', "ト,ツ,ミヤコ"),
+    ('県', "ケン"), ('京', "キョウ,ケイ"), ('駅', "エキ"), ('道', "ドウ,ミチ"),
+    ('車', "シャ,クルマ"), ('電', "デン"), ('気', "キ,ケ"), ('天', "テン,アマ"),
+    ('雨', "ウ,アメ,アマ"), ('空', "クウ,ソラ,ア,カラ"), ('花', "カ,ハナ"), ('木', "モク,ボク,キ,コ"),
+    ('水', "スイ,ミズ"), ('火', "カ,ヒ"), ('金', "キン,コン,カネ"), ('土', "ド,ト,ツチ"),
+    ('石', "セキ,シャク,イシ"), ('手', "シュ,テ"), ('足', "ソク,アシ,タ"), ('目', "モク,メ"),
+    ('口', "コウ,ク,クチ"), ('耳', "ジ,ミミ"), ('体', "タイ,テイ,カラダ"), ('心', "シン,ココロ"),
+    ('力', "リョク,リキ,チカラ"), ('物', "ブツ,モツ,モノ"), ('事', "ジ,コト"), ('者', "シャ,モノ"),
+    ('食', "ショク,ジキ,タ,ク"), ('飲', "イン,ノ"), ('見', "ケン,ミ"), ('聞', "ブン,モン,キ"),
+    ('読', "ドク,トク,ヨ"), ('書', "ショ,カ"), ('話', "ワ,ハナシ,ハナ"), ('言', "ゲン,ゴン,イ,コト"),
+    ('行', "コウ,ギョウ,アン,イ,ユ,オコナ"), ('来', "ライ,ク,キ,コ"), ('出', "シュツ,シュッ,デ,ダ"),
+    ('入', "ニュウ,イ,ハイ"), ('休', "キュウ,ヤス"), ('立', "リツ,リッ,タ"), ('作', "サク,サ,ツク"),
+    ('使', "シ,ツカ"), ('思', "シ,オモ"), ('知', "チ,シ"), ('持', "ジ,モ"), ('待', "タイ,マ"),
+    ('買', "バイ,カ"), ('売', "バイ,ウ"), ('勉', "ベン"), ('強', "キョウ,ゴウ,ツヨ"),
+    ('教', "キョウ,オシ"), ('室', "シツ,ムロ"), ('家', "カ,ケ,イエ,ヤ"), ('店', "テン,ミセ"),
+    ('所', "ショ,ジョ,トコロ,ドコロ"), ('場', "ジョウ,バ"), ('地', "チ,ジ"), ('世', "セイ,セ,ヨ"),
+    ('界', "カイ"), ('間', "カン,ケン,アイダ,マ"), ('自', "ジ,シ,ミズカ"),
+    ('方', "ホウ,カタ,ガタ"), ('明', "メイ,ミョウ,アカ,ア"), ('白', "ハク,シロ"), ('赤', "セキ,アカ"),
+    ('青', "セイ,アオ"), ('黒', "コク,クロ"), ('色', "ショク,シキ,イロ"), ('音', "オン,イン,オト,ネ"),
+    ('楽', "ガク,ラク,タノ"), ('映', "エイ,ウツ"), ('画', "ガ,カク"), ('写', "シャ,ウツ"),
+    ('真', "シン,マ"), ('問', "モン,ト"), ('題', "ダイ"),
+    ('意', "イ"), ('味', "ミ,アジ"), ('発', "ハツ,ハッ,ホツ"), ('表', "ヒョウ,オモテ,アラワ"),
+    ('政', "セイ,ショウ"), ('治', "ジ,チ,オサ,ナオ"), ('経', "ケイ,キョウ,ヘ"), ('済', "サイ,ス"),
+    ('部', "ブ"), ('品', "ヒン,シナ"), ('動', "ドウ,ウゴ"), ('働', "ドウ,ハタラ"),
+    ('私', "シ,ワタクシ,ワタシ"), ('彼', "ヒ,カレ,カノ"), ('何', "カ,ナニ,ナン"), ('誰', "スイ,ダレ"),
+];
+
+/// Readings of a kanji as it may appear inside a compound: voiced at the start (rendaku)
+/// and with a final ツ/チ/ク/キ geminated
+fn compound_readings(kanji: char, first: bool) -> Vec<String> {
+    let mut readings = Vec::new();
+
+    for (_, list) in KANJI_READINGS.iter().filter(|(k, _)| *k == kanji) {
+        for reading in list.split(',') {
+            readings.push(reading.to_string());
+
+            if !first {
+                let mut chars = reading.chars();
+                if let Some(voiced) = chars.next().and_then(voiced) {
+                    readings.push(std::iter::once(voiced).chain(chars).collect());
+                }
+            }
+
+            if let Some(stem) = reading.strip_suffix(['ツ', 'チ', 'ク', 'キ']) {
+                readings.push(format!("{}ッ", stem));
+            }
+        }
+    }
+
+    readings
+}
+
+fn voiced(kana: char) -> Option<char> {
+    const UNVOICED: &str = "カキクケコサシスセソタチツテトハヒフヘホ";
+    const VOICED: &str = "ガギグゲゴザジズゼゾダヂヅデドバビブベボ";
+
+    let index = UNVOICED.chars().position(|c| c == kana)?;
+    VOICED.chars().nth(index)
+}
+
+pub(crate) fn is_kanji(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}' | '々' | '〆' | 'ヶ')
+}
+
+/// Splits `text` into runs of kanji and runs of everything else
+fn runs(text: &str) -> Vec<(&str, bool)> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut in_kanji = None;
+
+    for (i, c) in text.char_indices() {
+        let kanji = is_kanji(c);
+        if in_kanji.is_some_and(|in_kanji| in_kanji != kanji) {
+            runs.push((&text[start..i], !kanji));
+            start = i;
+        }
+        in_kanji = Some(kanji);
+    }
+    if let Some(kanji) = in_kanji {
+        runs.push((&text[start..], kanji));
+    }
+
+    runs
+}
+
+/// Assigns a part of `reading` to each kanji run, so that the kana runs match the reading
+/// in between (the okurigana). Fails if the kana don't appear in the reading.
+fn align<'a>(runs: &[(&'a str, bool)], reading: &[char]) -> Option<Vec<(&'a str, Option<String>)>> {
+    let Some(((text, kanji), rest)) = runs.split_first() else {
+        return reading.is_empty().then(Vec::new);
+    };
+
+    if !kanji {
+        let kana: Vec<char> = to_katakana(text).chars().collect();
+        if !reading.starts_with(&kana) {
+            return None;
+        }
+        let mut aligned = align(rest, &reading[kana.len()..])?;
+        aligned.insert(0, (text, None));
+        return Some(aligned);
+    }
+
+    // Kanji are read as at least one kana, the shortest reading that works wins
+    for len in 1..=reading.len() {
+        if let Some(mut aligned) = align(rest, &reading[len..]) {
+            aligned.insert(0, (text, Some(reading[..len].iter().collect())));
+            return Some(aligned);
+        }
+    }
+
+    None
+}
+
+/// Splits the reading of a kanji compound between its kanji using `KANJI_READINGS`
+fn split_compound(kanji: &[char], reading: &str, first: bool) -> Option<Vec<String>> {
+    let Some((&head, tail)) = kanji.split_first() else {
+        return reading.is_empty().then(Vec::new);
+    };
+
+    for candidate in compound_readings(head, first) {
+        let Some(rest) = reading.strip_prefix(candidate.as_str()) else {
+            continue;
+        };
+
+        // 々 repeats the kanji before it, possibly voiced
+        if tail.first() == Some(&'々') {
+            for repeated in compound_readings(head, false) {
+                if let Some(rest) = rest.strip_prefix(repeated.as_str()) {
+                    if let Some(mut split) = split_compound(&tail[1..], rest, false) {
+                        split.insert(0, repeated);
+                        split.insert(0, candidate);
+                        return Some(split);
+                    }
+                }
+            }
+            continue;
+        }
+
+        if let Some(mut split) = split_compound(tail, rest, false) {
+            split.insert(0, candidate);
+            return Some(split);
+        }
+    }
+
+    None
+}
+
+fn push_segment(segments: &mut Vec<FuriganaSegment>, text: &str, reading: Option<String>) {
+    match (segments.last_mut(), &reading) {
+        (Some(last), None) if last.reading.is_none() => last.text.push_str(text),
+        _ => segments.push(FuriganaSegment {
+            text: text.into(),
+            reading: reading.map(|r| to_hiragana(&r)),
+        }),
+    }
+}
+
+impl Word {
+    /// Splits the word into segments for furigana, so that only kanji get a reading,
+    /// e.g. 食べ物 → 食(た) べ 物(もの). Kanji compounds are split per kanji where
+    /// the readings of their kanji are known (勉強 → 勉(べん) 強(きょう)),
+    /// otherwise they keep a single reading. So do words with irregular readings like 大人,
+    /// see [`ReadingOverrides`].
+    pub fn furigana_segments(&self) -> Vec<FuriganaSegment> {
+        self.furigana_segments_with(&ReadingOverrides::default())
+    }
+
+    /// Like `furigana_segments`, with the given irregular readings
+    /// on top of the built-in ones, e.g. `ParseConfig::reading_overrides`
+    pub fn furigana_segments_with(&self, overrides: &ReadingOverrides) -> Vec<FuriganaSegment> {
+        let mut segments = Vec::new();
+
+        for token in &self.tokens {
+            let reading = to_katakana(&token.reading);
+            let runs = runs(&token.literal);
+
+            let has_reading = !reading.is_empty() && reading != "*";
+
+            // The reading was already overridden while preparing the token, if at all
+            if let Some(read_as) = overrides.irregular(&token.literal) {
+                let reading = if has_reading { reading } else { read_as.into() };
+                push_segment(&mut segments, &token.literal, Some(reading));
+                continue;
+            }
+
+            let aligned = if runs.iter().any(|(_, kanji)| *kanji) && has_reading {
+                let reading: Vec<char> = reading.chars().collect();
+                align(&runs, &reading)
+            } else {
+                None
+            };
+
+            let Some(aligned) = aligned else {
+                let reading =
+                    (has_reading && runs.iter().any(|(_, kanji)| *kanji)).then_some(reading);
+                push_segment(&mut segments, &token.literal, reading);
+                continue;
+            };
+
+            for (text, reading) in aligned {
+                let Some(reading) = reading else {
+                    push_segment(&mut segments, text, None);
+                    continue;
+                };
+
+                let kanji: Vec<char> = text.chars().collect();
+                let split = split_compound(&kanji, &reading, true)
+                    .filter(|_| kanji.len() > 1 && overrides.irregular(text).is_none());
+                match split {
+                    Some(split) => {
+                        for (kanji, reading) in kanji.iter().zip(split) {
+                            push_segment(&mut segments, &kanji.to_string(), Some(reading));
+                        }
+                    }
+                    None => push_segment(&mut segments, text, Some(reading)),
+                }
+            }
+        }
+
+        segments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    fn furigana(text: &str) -> Vec<(String, Option<String>)> {
+        words(text)
+            .iter()
+            .flat_map(Word::furigana_segments)
+            .map(|segment| (segment.text, segment.reading))
+            .collect()
+    }
+
+    fn split(kanji: &str, reading: &str) -> Option<Vec<String>> {
+        let kanji: Vec<char> = kanji.chars().collect();
+        split_compound(&kanji, reading, true)
+    }
+
+    #[test]
+    fn aligns_okurigana_with_the_reading() {
+        let reading: Vec<char> = "タベモノ".chars().collect();
+
+        assert_eq!(
+            align(&runs("食べ物"), &reading),
+            Some(vec![
+                ("食", Some("タ".into())),
+                ("べ", None),
+                ("物", Some("モノ".into()))
+            ])
+        );
+        assert_eq!(align(&runs("食べ物"), &['タ', 'ベ']), None);
+    }
+
+    #[test]
+    fn splits_compounds_between_their_kanji() {
+        assert_eq!(
+            split("勉強", "ベンキョウ"),
+            Some(vec!["ベン".into(), "キョウ".into()])
+        );
+        assert_eq!(
+            split("作家", "サッカ"),
+            Some(vec!["サッ".into(), "カ".into()])
+        );
+        assert_eq!(split("猫舌", "ネコジタ"), None);
+    }
+
+    #[test]
+    fn voices_kanji_inside_compounds() {
+        assert_eq!(
+            split("花火", "ハナビ"),
+            Some(vec!["ハナ".into(), "ビ".into()])
+        );
+        assert_eq!(split("火花", "ビハナ"), None);
+    }
+
+    #[test]
+    fn reads_the_iteration_mark_as_the_kanji_before_it() {
+        assert_eq!(
+            split("人々", "ヒトビト"),
+            Some(vec!["ヒト".into(), "ビト".into()])
+        );
+        assert_eq!(
+            split("時々", "トキドキ"),
+            Some(vec!["トキ".into(), "ドキ".into()])
+        );
+    }
+
+    #[test]
+    fn gives_readings_to_kanji_only() {
+        assert_eq!(
+            furigana("食べ物を勉強"),
+            [
+                ("食".into(), Some("た".into())),
+                ("べ".into(), None),
+                ("物".into(), Some("もの".into())),
+                ("を".into(), None),
+                ("勉".into(), Some("べん".into())),
+                ("強".into(), Some("きょう".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_irregular_readings_whole() {
+        assert_eq!(
+            furigana("今日大人"),
+            [
+                ("今日".into(), Some("きょう".into())),
+                ("大人".into(), Some("おとな".into()))
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_overridden_readings_whole() {
+        let mut overrides = ReadingOverrides::default();
+        overrides.insert("東京", "トウキョウ");
+
+        let segments = words("東京")[0].furigana_segments_with(&overrides);
+        assert_eq!(
+            segments,
+            [FuriganaSegment {
+                text: "東京".into(),
+                reading: Some("とうきょう".into())
+            }]
+        );
+        assert_eq!(furigana("東京").len(), 2);
+    }
+}
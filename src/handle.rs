@@ -0,0 +1,247 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use vibrato::Tokenizer;
+
+use crate::{
+    load_dictionary, parse_with_stats, resolve_dictionary, ParseConfig, Result, Tokenize,
+    TokenizeError, Word,
+};
+
+/// A tokenizer and parse config bundled together, for apps that parse many sentences
+/// with the same setup. Keeps counters of what it parsed, see [`Ve::metrics`].
+///
+/// It can be shared between threads, e.g. behind an `Arc`. The tokenizer is vibrato's
+/// unless another backend is passed to [`Ve::new`].
+pub struct Ve<T = Tokenizer> {
+    tokenizer: T,
+    config: ParseConfig,
+    cache: Option<Cache>,
+    counters: Counters,
+}
+
+/// Cumulative counters of a [`Ve`] handle
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Metrics {
+    /// Calls to `parse`, each counted as one sentence
+    pub sentences: u64,
+    pub tokens: u64,
+    /// Tokens that came from vibrato's unknown-word handling
+    pub unknown_tokens: u64,
+    /// Sentences answered from the cache, which don't count towards tokens
+    pub cache_hits: u64,
+}
+
+/// Longest unknown words the tokenizer groups by default, the same as the CLI's
+const DEFAULT_MAX_GROUPING_LEN: usize = 24;
+
+/// Loads a dictionary and sets up a [`Ve`] handle with it, see [`Ve::builder`]
+#[derive(Debug)]
+pub struct VeBuilder {
+    dictionary: Option<PathBuf>,
+    config: ParseConfig,
+    ignore_space: bool,
+    max_grouping_len: Option<usize>,
+    cache: Option<usize>,
+}
+
+#[derive(Default)]
+struct Counters {
+    sentences: AtomicU64,
+    tokens: AtomicU64,
+    unknown_tokens: AtomicU64,
+    cache_hits: AtomicU64,
+}
+
+struct Cache {
+    capacity: usize,
+    sentences: Mutex<CachedSentences>,
+}
+
+#[derive(Default)]
+struct CachedSentences {
+    words: HashMap<String, Vec<Word>>,
+    /// Cached sentences from the oldest to the newest
+    order: VecDeque<String>,
+}
+
+impl Ve {
+    /// Sets up a handle from a dictionary file, so the whole pipeline can be run without
+    /// loading the dictionary or building a tokenizer by hand.
+    ///
+    /// ```no_run
+    /// let ve = ve::Ve::builder()
+    ///     .dictionary("ipadic-mecab-2_7_0/system.dic.zst")
+    ///     .build()
+    ///     .unwrap();
+    /// let words = ve.parse("今日はいい天気ですね").unwrap();
+    /// ```
+    pub fn builder() -> VeBuilder {
+        VeBuilder::default()
+    }
+}
+
+impl<T: Tokenize> Ve<T> {
+    /// Detects the dictionary's schema unless `config` sets one, see [`ParseConfig::detect_schema`]
+    pub fn new(tokenizer: T, mut config: ParseConfig) -> Self {
+        config.detect_schema(&tokenizer);
+
+        Self {
+            tokenizer,
+            config,
+            cache: None,
+            counters: Counters::default(),
+        }
+    }
+
+    /// Caches the words of up to `capacity` sentences, so repeated ones aren't parsed again.
+    /// Once the cache is full, the sentence that was cached first makes room for the next one.
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(Cache {
+            capacity,
+            sentences: Mutex::default(),
+        });
+        self
+    }
+
+    pub fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
+    }
+
+    /// Tokenizes `sentence` and groups the tokens into words
+    pub fn parse(&self, sentence: &str) -> Result<Vec<Word>> {
+        self.counters.sentences.fetch_add(1, Ordering::Relaxed);
+
+        if let Some(words) = self.cache.as_ref().and_then(|c| c.get(sentence)) {
+            self.counters.cache_hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(words);
+        }
+
+        let (words, stats) = parse_with_stats(&self.tokenizer, sentence, &self.config)?;
+
+        let counters = &self.counters;
+        counters
+            .tokens
+            .fetch_add(stats.tokens as u64, Ordering::Relaxed);
+        counters
+            .unknown_tokens
+            .fetch_add(stats.oov_tokens as u64, Ordering::Relaxed);
+
+        if let Some(cache) = &self.cache {
+            cache.insert(sentence, &words);
+        }
+
+        Ok(words)
+    }
+
+    /// A snapshot of the counters since the handle was created
+    pub fn metrics(&self) -> Metrics {
+        let counters = &self.counters;
+
+        Metrics {
+            sentences: counters.sentences.load(Ordering::Relaxed),
+            tokens: counters.tokens.load(Ordering::Relaxed),
+            unknown_tokens: counters.unknown_tokens.load(Ordering::Relaxed),
+            cache_hits: counters.cache_hits.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl VeBuilder {
+    /// A zstd-compressed vibrato dictionary. If not set, it's looked for with
+    /// [`resolve_dictionary`](crate::resolve_dictionary), falling back to the bundled one
+    /// with the `bundled-dict` feature.
+    pub fn dictionary(mut self, path: impl Into<PathBuf>) -> Self {
+        self.dictionary = Some(path.into());
+        self
+    }
+
+    pub fn config(mut self, config: ParseConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Whether the tokenizer skips spaces instead of making tokens of them, on by default
+    pub fn ignore_space(mut self, ignore_space: bool) -> Self {
+        self.ignore_space = ignore_space;
+        self
+    }
+
+    /// Longest unknown words the tokenizer groups, in characters, 24 by default.
+    /// 0 means no limit.
+    pub fn max_grouping_len(mut self, max_grouping_len: usize) -> Self {
+        self.max_grouping_len = Some(max_grouping_len);
+        self
+    }
+
+    /// See [`Ve::with_cache`]
+    pub fn cache(mut self, capacity: usize) -> Self {
+        self.cache = Some(capacity);
+        self
+    }
+
+    pub fn build(self) -> std::result::Result<Ve, TokenizeError> {
+        let dictionary = match resolve_dictionary(self.dictionary.as_deref()) {
+            Ok(path) => load_dictionary(path)?,
+            #[cfg(feature = "bundled-dict")]
+            Err(TokenizeError::NotFound { .. }) => crate::bundled_dictionary()?,
+            Err(error) => return Err(error),
+        };
+
+        let mut tokenizer = Tokenizer::new(dictionary).ignore_space(self.ignore_space)?;
+        if let Some(max_grouping_len) = self.max_grouping_len {
+            tokenizer = tokenizer.max_grouping_len(max_grouping_len);
+        }
+
+        let ve = Ve::new(tokenizer, self.config);
+        Ok(match self.cache {
+            Some(capacity) => ve.with_cache(capacity),
+            None => ve,
+        })
+    }
+}
+
+impl Default for VeBuilder {
+    /// Skips spaces and groups unknown words of up to 24 characters, like the CLI does
+    fn default() -> Self {
+        Self {
+            dictionary: None,
+            config: ParseConfig::default(),
+            ignore_space: true,
+            max_grouping_len: Some(DEFAULT_MAX_GROUPING_LEN),
+            cache: None,
+        }
+    }
+}
+
+impl Cache {
+    fn get(&self, sentence: &str) -> Option<Vec<Word>> {
+        self.sentences.lock().unwrap().words.get(sentence).cloned()
+    }
+
+    fn insert(&self, sentence: &str, words: &[Word]) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut sentences = self.sentences.lock().unwrap();
+        if sentences.words.contains_key(sentence) {
+            return;
+        }
+        if sentences.words.len() >= self.capacity {
+            if let Some(oldest) = sentences.order.pop_front() {
+                sentences.words.remove(&oldest);
+            }
+        }
+        sentences.words.insert(sentence.into(), words.to_vec());
+        sentences.order.push_back(sentence.into());
+    }
+}
@@ -0,0 +1,168 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::Word;
+
+/// A piece of a text around words that matched a query
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Snippet {
+    /// Byte offsets of the snippet in the text
+    pub range: Range<usize>,
+    /// Byte offsets of the matching words in the text, inside `range`
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Settings for `highlight`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct HighlightOptions {
+    /// Words to show before and after a match. Snippets whose context overlaps are joined.
+    pub context_words: usize,
+    /// How many snippets to return at most, 0 for all of them
+    pub max_snippets: usize,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            context_words: 5,
+            max_snippets: 3,
+        }
+    }
+}
+
+/// Finds the words of `text` whose lemma or surface is one of `query`,
+/// and cuts snippets around them at word boundaries.
+///
+/// `words` need to have been parsed from `text` with their ranges intact, e.g. by
+/// `parse_text`, so the query should be lemmatized the same way, like with `search_tokens`.
+pub fn highlight(
+    text: &str,
+    words: &[Word],
+    query: &[&str],
+    options: &HighlightOptions,
+) -> Vec<Snippet> {
+    let matches = |word: &Word| query.contains(&lemma(word)) || query.contains(&word.word.as_str());
+    let mut snippets: Vec<(Range<usize>, Vec<Range<usize>>)> = Vec::new();
+
+    for (index, word) in words.iter().enumerate().filter(|(_, word)| matches(word)) {
+        let first = index.saturating_sub(options.context_words);
+        let last = (index + options.context_words).min(words.len() - 1);
+
+        match snippets.last_mut() {
+            // Context of the previous snippet reaches this one
+            Some((window, highlights)) if first <= window.end + 1 => {
+                window.end = last;
+                highlights.push(word.range());
+            }
+            _ => {
+                if options.max_snippets > 0 && snippets.len() == options.max_snippets {
+                    break;
+                }
+                snippets.push((first..last, vec![word.range()]));
+            }
+        }
+    }
+
+    snippets
+        .into_iter()
+        .map(|(window, highlights)| Snippet {
+            range: words[window.start].range().start..words[window.end].range().end,
+            highlights,
+        })
+        .filter(|snippet| snippet.range.end <= text.len())
+        .collect()
+}
+
+impl Snippet {
+    /// The snippet as HTML with the matches in `<em>`, and … where it's cut off from `text`
+    pub fn to_html(&self, text: &str) -> String {
+        let mut html = String::new();
+        if self.range.start > 0 {
+            html.push('…');
+        }
+
+        let mut position = self.range.start;
+        for highlight in &self.highlights {
+            push_escaped(&mut html, &text[position..highlight.start]);
+            html.push_str("<em>");
+            push_escaped(&mut html, &text[highlight.clone()]);
+            html.push_str("</em>");
+            position = highlight.end;
+        }
+        push_escaped(&mut html, &text[position..self.range.end]);
+
+        if self.range.end < text.trim_end().len() {
+            html.push('…');
+        }
+        html
+    }
+}
+
+fn push_escaped(html: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            _ => html.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    const TEXT: &str = "猫が嘘を食べました。猫が嘘。";
+
+    fn snippets(query: &[&str], context_words: usize, max_snippets: usize) -> Vec<String> {
+        let options = HighlightOptions {
+            context_words,
+            max_snippets,
+        };
+
+        highlight(TEXT, &words(TEXT), query, &options)
+            .iter()
+            .map(|snippet| snippet.to_html(TEXT))
+            .collect()
+    }
+
+    #[test]
+    fn highlights_words_by_their_lemma() {
+        assert_eq!(snippets(&["食べる"], 1, 0), ["…を<em>食べました</em>。…"]);
+    }
+
+    #[test]
+    fn joins_snippets_whose_context_overlaps() {
+        assert_eq!(
+            snippets(&["嘘"], 2, 0),
+            ["猫が<em>嘘</em>を食べました…", "…猫が<em>嘘</em>。"]
+        );
+        assert_eq!(
+            snippets(&["嘘"], 3, 0),
+            ["猫が<em>嘘</em>を食べました。猫が<em>嘘</em>。"]
+        );
+        assert_eq!(snippets(&["嘘"], 2, 1), ["猫が<em>嘘</em>を食べました…"]);
+    }
+
+    #[test]
+    fn escapes_html() {
+        let snippet = Snippet {
+            range: 0..7,
+            highlights: vec![1..2, 3..4],
+        };
+
+        assert_eq!(
+            snippet.to_html("a<b&\"c\""),
+            "a<em>&lt;</em>b<em>&amp;</em>&quot;c&quot;"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+use std::any::type_name;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// A trait object installed in a [`ParseConfig`](crate::ParseConfig), like its observer,
+/// e.g. `Hook(Arc::new(|event: &ParseEvent| ...))`. Its `Debug` output is the name of the
+/// trait, since there's nothing else to show about a closure.
+pub struct Hook<T: ?Sized>(pub Arc<T>);
+
+impl<T: ?Sized> Clone for Hook<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Deref for Hook<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Hook<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = type_name::<T>();
+        let name = name.rsplit("::").next().unwrap_or(name);
+
+        write!(f, "Hook({})", name)
+    }
+}
@@ -0,0 +1,341 @@
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_text, ParseConfig, Result, Tokenize, Word};
+
+/// How words are marked up in `annotate_html`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum HtmlAnnotation {
+    /// `<ruby>` furigana over the kanji of each word
+    #[default]
+    Ruby,
+    /// A `<span class="ve-word">` around each word, with its part of speech,
+    /// lemma and reading as `data-` attributes
+    Spans,
+    /// Spans with ruby inside them
+    Both,
+}
+
+/// Elements whose text is left as it is
+const SKIPPED_ELEMENTS: [&str; 8] = [
+    "script", "style", "title", "textarea", "ruby", "rt", "rp", "code",
+];
+
+/// Parses the text nodes of an HTML or EPUB XHTML document and returns the document
+/// with the words marked up as `annotation` says. Tags, comments and attributes are kept
+/// as they are, and so is the text of elements like `<script>`, `<code>` or existing `<ruby>`.
+///
+/// Each text node is parsed on its own, so a word split by inline markup like
+/// 食<b>べる</b> is parsed as two. Entities in text are decoded for parsing and
+/// the output is escaped again, so it's valid XHTML if the input was.
+pub fn annotate_html(
+    tokenizer: &(impl Tokenize + ?Sized),
+    html: &str,
+    config: &ParseConfig,
+    annotation: HtmlAnnotation,
+) -> Result<String> {
+    let mut output = String::with_capacity(html.len() * 2);
+    // Elements like <script> whose text is skipped, with their nesting depth
+    let mut skipped: Option<(&str, usize)> = None;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(markup) = rest.strip_prefix('<') else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..end];
+            rest = &rest[end..];
+
+            if skipped.is_some() || text.trim().is_empty() {
+                output.push_str(text);
+            } else {
+                let (text, verbatim) = decode_entities(text);
+                annotate_text(tokenizer, &text, &verbatim, config, annotation, &mut output)?;
+            }
+            continue;
+        };
+
+        let end = if markup.starts_with("!--") {
+            markup.find("-->").map(|end| end + "-->".len())
+        } else if markup.starts_with("![CDATA[") {
+            markup.find("]]>").map(|end| end + "]]>".len())
+        } else {
+            tag_end(markup)
+        };
+        let end = end.map_or(rest.len(), |end| end + '<'.len_utf8());
+        let tag = &rest[..end];
+        rest = &rest[end..];
+        output.push_str(tag);
+
+        let Some((name, closing, self_closing)) = tag_name(tag) else {
+            continue;
+        };
+        match &mut skipped {
+            Some((skipped_name, depth)) if skipped_name.eq_ignore_ascii_case(name) => {
+                if closing {
+                    *depth -= 1;
+                    if *depth == 0 {
+                        skipped = None;
+                    }
+                } else if !self_closing {
+                    *depth += 1;
+                }
+            }
+            Some(_) => {}
+            None => {
+                let skip = SKIPPED_ELEMENTS
+                    .iter()
+                    .find(|skip| skip.eq_ignore_ascii_case(name));
+                if let (Some(skip), false, false) = (skip, closing, self_closing) {
+                    skipped = Some((*skip, 1));
+                }
+            }
+        }
+    }
+
+    Ok(output)
+}
+
+/// Where a tag ends, skipping over `>` in quoted attribute values
+fn tag_end(markup: &str) -> Option<usize> {
+    let mut quote = None;
+
+    for (i, c) in markup.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// The name of an element's tag, whether it's a closing tag and whether it closes itself.
+/// None for comments, doctypes and processing instructions.
+fn tag_name(tag: &str) -> Option<(&str, bool, bool)> {
+    let inner = tag.strip_prefix('<')?.trim_end_matches('>');
+    let (inner, closing) = match inner.strip_prefix('/') {
+        Some(inner) => (inner, true),
+        None => (inner, false),
+    };
+
+    let end = inner
+        .find(|c: char| c.is_whitespace() || c == '/')
+        .unwrap_or(inner.len());
+    let name = &inner[..end];
+
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some((name, closing, inner.ends_with('/')))
+}
+
+fn annotate_text(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    verbatim: &[Range<usize>],
+    config: &ParseConfig,
+    annotation: HtmlAnnotation,
+    output: &mut String,
+) -> Result<()> {
+    let mut position = 0;
+
+    for sentence in parse_text(tokenizer, text, config)? {
+        for word in &sentence.words {
+            let range = word.range();
+            push_text(output, text, position..range.start, verbatim);
+            position = range.end;
+
+            // Words that don't match the text, e.g. because of spaces inside them, stay as text,
+            // and so do the ones in entities that weren't decoded
+            let in_entity = verbatim
+                .iter()
+                .any(|entity| entity.start < range.end && range.start < entity.end);
+            if text[range.clone()] != word.word || in_entity {
+                push_text(output, text, range, verbatim);
+                continue;
+            }
+
+            match annotation {
+                HtmlAnnotation::Ruby => push_ruby(output, word, config),
+                HtmlAnnotation::Spans | HtmlAnnotation::Both => {
+                    output.push_str("<span class=\"ve-word\" data-pos=\"");
+                    output.push_str(&format!("{:?}", word.part_of_speech));
+                    output.push_str("\" data-lemma=\"");
+                    push_escaped(output, word.lemma.as_deref().unwrap_or(&word.word));
+                    output.push_str("\" data-reading=\"");
+                    push_escaped(output, &word.extra.reading);
+                    output.push_str("\">");
+
+                    if annotation == HtmlAnnotation::Both {
+                        push_ruby(output, word, config);
+                    } else {
+                        push_escaped(output, &word.word);
+                    }
+                    output.push_str("</span>");
+                }
+            }
+        }
+    }
+    push_text(output, text, position..text.len(), verbatim);
+
+    Ok(())
+}
+
+/// Escapes `text[range]`, except for the parts of it in `verbatim`
+fn push_text(output: &mut String, text: &str, range: Range<usize>, verbatim: &[Range<usize>]) {
+    let mut position = range.start;
+    for entity in verbatim {
+        if entity.end <= position || entity.start >= range.end {
+            continue;
+        }
+        let start = entity.start.max(position);
+        let end = entity.end.min(range.end);
+        push_escaped(output, &text[position..start]);
+        output.push_str(&text[start..end]);
+        position = end;
+    }
+    push_escaped(output, &text[position..range.end]);
+}
+
+fn push_ruby(output: &mut String, word: &Word, config: &ParseConfig) {
+    for segment in word.furigana_segments_with(&config.reading_overrides) {
+        match segment.reading {
+            Some(reading) => {
+                output.push_str("<ruby>");
+                push_escaped(output, &segment.text);
+                output.push_str("<rp>(</rp><rt>");
+                push_escaped(output, &reading);
+                output.push_str("</rt><rp>)</rp></ruby>");
+            }
+            None => push_escaped(output, &segment.text),
+        }
+    }
+}
+
+fn push_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Decodes the XML entities, `&nbsp;` and numeric character references.
+/// Other named entities like `&hellip;` are kept as they are, and returned with where they
+/// are in the decoded text so they're written back verbatim instead of escaped again.
+fn decode_entities(text: &str) -> (String, Vec<Range<usize>>) {
+    let mut decoded = String::with_capacity(text.len());
+    let mut verbatim = Vec::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest[1..]
+            .find(';')
+            .filter(|&end| end <= 10)
+            .map(|end| &rest[1..end + 1]);
+        let c = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some('\u{a0}'),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix(['x', 'X']) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                char::from_u32(code)
+            }
+        });
+
+        match (entity, c) {
+            (Some(entity), Some(c)) => {
+                decoded.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            (Some(entity), None) if is_entity_name(entity) => {
+                let start = decoded.len();
+                decoded.push_str(&rest[..entity.len() + 2]);
+                verbatim.push(start..decoded.len());
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+
+    (decoded, verbatim)
+}
+
+fn is_entity_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+
+    fn annotate(html: &str, annotation: HtmlAnnotation) -> String {
+        annotate_html(&Lexicon, html, &ParseConfig::default(), annotation).unwrap()
+    }
+
+    #[test]
+    fn adds_ruby_to_text() {
+        assert_eq!(
+            annotate("<p class=\"a\">猫</p>", HtmlAnnotation::Ruby),
+            "<p class=\"a\"><ruby>猫<rp>(</rp><rt>ねこ</rt><rp>)</rp></ruby></p>"
+        );
+    }
+
+    #[test]
+    fn escapes_decoded_entities_again() {
+        assert_eq!(
+            annotate("<p>&lt;猫&gt; &amp; &#x732B;</p>", HtmlAnnotation::Ruby),
+            "<p>&lt;<ruby>猫<rp>(</rp><rt>ねこ</rt><rp>)</rp></ruby>&gt; &amp; \
+             <ruby>猫<rp>(</rp><rt>ねこ</rt><rp>)</rp></ruby></p>"
+        );
+    }
+
+    #[test]
+    fn keeps_other_named_entities_as_they_are() {
+        for annotation in [HtmlAnnotation::Ruby, HtmlAnnotation::Spans] {
+            let html = annotate("<p>嘘&hellip;&copy;</p>", annotation);
+            assert!(html.ends_with("&hellip;&copy;</p>"), "{}", html);
+            assert!(!html.contains("&amp;"), "{}", html);
+        }
+    }
+
+    #[test]
+    fn leaves_skipped_elements_alone() {
+        let html = "<code>猫 &amp; &hellip;</code><script>if (a < b) {}</script>";
+        assert_eq!(annotate(html, HtmlAnnotation::Spans), html);
+    }
+
+    #[test]
+    fn marks_undecoded_named_entities_verbatim() {
+        let (decoded, verbatim) = decode_entities("&lt;&hellip;&#x732B;&bogus &amp;");
+
+        assert_eq!(decoded, "<&hellip;猫&bogus &");
+        assert_eq!(verbatim.len(), 1);
+        assert_eq!(&decoded[verbatim[0].clone()], "&hellip;");
+    }
+}
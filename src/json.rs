@@ -0,0 +1,86 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::Word;
+
+/// The words as a JSON array, in the same shape `Word`'s serde derive gives them
+pub fn to_json(words: &[Word]) -> String {
+    serde_json::to_string(words).expect("words serialize to JSON")
+}
+
+/// Writes parsed sentences as JSON Lines, one `{"text": ..., "words": [...]}` object per
+/// line, so a corpus can be streamed out without collecting it first.
+///
+/// ```no_run
+/// use ve::JsonlWriter;
+///
+/// let ve = ve::Ve::builder().build().unwrap();
+/// let mut writer = JsonlWriter::new(std::io::stdout().lock());
+/// for line in ["今日はいい天気ですね", "明日は雨です"] {
+///     writer.write_sentence(line, &ve.parse(line).unwrap()).unwrap();
+/// }
+/// ```
+pub struct JsonlWriter<W: Write> {
+    writer: W,
+}
+
+#[derive(Serialize)]
+struct Line<'a> {
+    text: &'a str,
+    words: &'a [Word],
+}
+
+impl<W: Write> JsonlWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Writes a sentence and its words as one line
+    pub fn write_sentence(&mut self, text: &str, words: &[Word]) -> io::Result<()> {
+        serde_json::to_writer(&mut self.writer, &Line { text, words })?;
+        self.writer.write_all(b"\n")
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn writes_words_as_an_array() {
+        let json: serde_json::Value = serde_json::from_str(&to_json(&words("猫が"))).unwrap();
+
+        assert_eq!(json.as_array().unwrap().len(), 2);
+        assert_eq!(json[0]["word"], "猫");
+        assert_eq!(json[1]["word"], "が");
+    }
+
+    #[test]
+    fn writes_one_line_per_sentence() {
+        let mut writer = JsonlWriter::new(Vec::new());
+        writer.write_sentence("猫が", &words("猫が")).unwrap();
+        writer.write_sentence("", &[]).unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert!(output.ends_with('\n'));
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["text"], "猫が");
+        assert_eq!(lines[0]["words"][0]["word"], "猫");
+        assert_eq!(lines[1], serde_json::json!({ "text": "", "words": [] }));
+    }
+}
@@ -0,0 +1,78 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+const A_ROW: &str = "アカガサザタダナハバパマヤラワァャヮ";
+const I_ROW: &str = "イキギシジチヂニヒビピミリヰィ";
+const U_ROW: &str = "ウクグスズツヅヌフブプムユルゥュヴ";
+const E_ROW: &str = "エケゲセゼテデネヘベペメレヱェ";
+const O_ROW: &str = "オコゴソゾトドノホボポモヨロヲォョ";
+
+/// The vowel a katakana syllable ends in, like ア for カ or ャ
+pub(crate) fn vowel_of(kana: char) -> Option<char> {
+    [
+        (A_ROW, 'ア'),
+        (I_ROW, 'イ'),
+        (U_ROW, 'ウ'),
+        (E_ROW, 'エ'),
+        (O_ROW, 'オ'),
+    ]
+    .into_iter()
+    .find(|(row, _)| row.contains(kana))
+    .map(|(_, vowel)| vowel)
+}
+
+/// Replaces the long vowel mark ー with the vowel it extends, like トーキョー → トオキョオ.
+/// Marks that don't follow a vowel (e.g. after ン) are kept.
+pub(crate) fn expand_long_vowels(katakana: &str) -> String {
+    let mut result = String::with_capacity(katakana.len());
+    let mut vowel = None;
+
+    for c in katakana.chars() {
+        if c == 'ー' {
+            result.push(vowel.unwrap_or(c));
+        } else {
+            vowel = vowel_of(c);
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Whether the character is hiragana or katakana, including ー
+pub(crate) fn is_kana(c: char) -> bool {
+    matches!(c, 'ぁ'..='ゖ' | 'ゝ' | 'ゞ' | 'ァ'..='ヺ' | 'ー' | 'ヽ' | 'ヾ')
+}
+
+/// Converts hiragana to katakana, leaving everything else as is
+pub(crate) fn to_katakana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ぁ'..='ゖ' | 'ゝ' | 'ゞ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Converts katakana to hiragana, leaving everything else (including ー) as is
+pub(crate) fn to_hiragana(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'ァ'..='ヶ' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+/// Script `WordExtra::reading` is written in
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ReadingScript {
+    /// As the dictionary has it
+    #[default]
+    Katakana,
+    Hiragana,
+    /// Katakana, with the hiragana in `WordExtra::hiragana_reading`
+    Both,
+}
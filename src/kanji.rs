@@ -0,0 +1,129 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::furigana::is_kanji;
+use crate::Word;
+
+/// When a kanji is taught in Japanese schools
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum KanjiGrade {
+    /// Grade 1 to 6 of elementary school, for the 1026 kyōiku kanji
+    Grade(u8),
+    /// Any other kanji, taught in secondary school if it's one of the jōyō kanji
+    /// and otherwise not at all
+    Secondary,
+}
+
+/// The kyōiku kanji of each elementary school grade, as revised in 2020
+const GRADES: [&str; 6] = [
+    concat!(
+        "一右雨円王音下火花貝学気九休玉金空月犬見五口校左三山子四糸字耳七車手十出女小上森",
+        "人水正生青夕石赤千川先早草足村大男竹中虫町天田土二日入年白八百文木本名目立力林六",
+    ),
+    concat!(
+        "引羽雲園遠何科夏家歌画回会海絵外角楽活間丸岩顔汽記帰弓牛魚京強教近兄形計元言原戸",
+        "古午後語工公広交光考行高黄合谷国黒今才細作算止市矢姉思紙寺自時室社弱首秋週春書少",
+        "場色食心新親図数西声星晴切雪船線前組走多太体台地池知茶昼長鳥朝直通弟店点電刀冬当",
+        "東答頭同道読内南肉馬売買麦半番父風分聞米歩母方北毎妹万明鳴毛門夜野友用曜来里理話",
+    ),
+    concat!(
+        "悪安暗医委意育員院飲運泳駅央横屋温化荷界開階寒感漢館岸起期客究急級宮球去橋業曲局",
+        "銀区苦具君係軽血決研県庫湖向幸港号根祭皿仕死使始指歯詩次事持式実写者主守取酒受州",
+        "拾終習集住重宿所暑助昭消商章勝乗植申身神真深進世整昔全相送想息速族他打対待代第題",
+        "炭短談着注柱丁帳調追定庭笛鉄転都度投豆島湯登等動童農波配倍箱畑発反坂板皮悲美鼻筆",
+        "氷表秒病品負部服福物平返勉放味命面問役薬由油有遊予羊洋葉陽様落流旅両緑礼列練路和",
+    ),
+    concat!(
+        "愛案以衣位茨印英栄媛塩岡億加果貨課芽賀改械害街各覚潟完官管関観願岐希季旗器機議求",
+        "泣給挙漁共協鏡競極熊訓軍郡群径景芸欠結建健験固功好香候康佐差菜最埼材崎昨札刷察参",
+        "産散残氏司試児治滋辞鹿失借種周祝順初松笑唱焼照城縄臣信井成省清静席積折節説浅戦選",
+        "然争倉巣束側続卒孫帯隊達単置仲沖兆低底的典伝徒努灯働特徳栃奈梨熱念敗梅博阪飯飛必",
+        "票標不夫付府阜富副兵別辺変便包法望牧末満未民無約勇要養浴利陸良料量輪類令冷例連老",
+        "労録",
+    ),
+    concat!(
+        "圧囲移因永営衛易益液演応往桜可仮価河過快解格確額刊幹慣眼紀基寄規喜技義逆久旧救居",
+        "許境均禁句型経潔件険検限現減故個護効厚耕航鉱構興講告混査再災妻採際在財罪殺雑酸賛",
+        "士支史志枝師資飼示似識質舎謝授修述術準序招証象賞条状常情織職制性政勢精製税責績接",
+        "設絶祖素総造像増則測属率損貸態団断築貯張停提程適統堂銅導得毒独任燃能破犯判版比肥",
+        "非費備評貧布婦武復複仏粉編弁保墓報豊防貿暴脈務夢迷綿輸余容略留領歴",
+    ),
+    concat!(
+        "胃異遺域宇映延沿恩我灰拡革閣割株干巻看簡危机揮貴疑吸供胸郷勤筋系敬警劇激穴券絹権",
+        "憲源厳己呼誤后孝皇紅降鋼刻穀骨困砂座済裁策冊蚕至私姿視詞誌磁射捨尺若樹収宗就衆従",
+        "縦縮熟純処署諸除承将傷障蒸針仁垂推寸盛聖誠舌宣専泉洗染銭善奏窓創装層操蔵臓存尊退",
+        "宅担探誕段暖値宙忠著庁頂腸潮賃痛敵展討党糖届難乳認納脳派拝背肺俳班晩否批秘俵腹奮",
+        "並陛閉片補暮宝訪亡忘棒枚幕密盟模訳郵優預幼欲翌乱卵覧裏律臨朗論",
+    ),
+];
+
+/// The grade `kanji` is taught in, None if it's not a kanji
+pub fn kanji_grade(kanji: char) -> Option<KanjiGrade> {
+    if !is_kanji(kanji) {
+        return None;
+    }
+
+    let grade = GRADES.iter().position(|grade| grade.contains(kanji));
+    Some(match grade {
+        Some(grade) => KanjiGrade::Grade(grade as u8 + 1),
+        None => KanjiGrade::Secondary,
+    })
+}
+
+impl Word {
+    /// The highest grade of the kanji in the word, e.g. `Grade(4)` for 大阪 or
+    /// `Secondary` for 憂鬱. None if the word has no kanji.
+    ///
+    /// Marks like 々 in 人々 or ヶ in 三ヶ月 don't count on their own.
+    pub fn kanji_grade(&self) -> Option<KanjiGrade> {
+        self.word
+            .chars()
+            .filter(|c| !matches!(c, '々' | '〆' | 'ヶ'))
+            .filter_map(kanji_grade)
+            .max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn lists_each_of_the_kyouiku_kanji_once() {
+        let kanji: Vec<char> = GRADES.iter().flat_map(|grade| grade.chars()).collect();
+        let mut unique = kanji.clone();
+        unique.sort_unstable();
+        unique.dedup();
+
+        assert_eq!(kanji.len(), 1026);
+        assert_eq!(unique.len(), kanji.len());
+    }
+
+    #[test]
+    fn grades_kanji() {
+        assert_eq!(kanji_grade('一'), Some(KanjiGrade::Grade(1)));
+        assert_eq!(kanji_grade('阪'), Some(KanjiGrade::Grade(4)));
+        assert_eq!(kanji_grade('憂'), Some(KanjiGrade::Secondary));
+        assert_eq!(kanji_grade('ね'), None);
+    }
+
+    #[test]
+    fn grades_words_by_their_hardest_kanji() {
+        let grades: Vec<_> = words("東京食べました嘘が")
+            .iter()
+            .map(Word::kanji_grade)
+            .collect();
+
+        assert_eq!(
+            grades,
+            [
+                Some(KanjiGrade::Grade(2)),
+                Some(KanjiGrade::Grade(2)),
+                Some(KanjiGrade::Secondary),
+                None
+            ]
+        );
+    }
+}
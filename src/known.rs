@@ -0,0 +1,249 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::normalize::normalize_width;
+use crate::{PartOfSpeech, Word};
+
+/// Lemmas a learner knows, optionally only with a certain part of speech.
+///
+/// Words are looked up by their lemma, so knowing 行く covers 行った and 行きます.
+/// Lemmas are stored width-normalized and trimmed, so ｶﾀｶﾅ and カタカナ are the same.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct KnownWords {
+    /// The parts of speech each lemma is known with, an empty set meaning any
+    lemmas: HashMap<String, HashSet<PartOfSpeech>>,
+}
+
+fn normalize(lemma: &str) -> String {
+    normalize_width(lemma.trim())
+}
+
+impl KnownWords {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a list with one lemma per line. Blank lines and lines starting with # are skipped.
+    pub fn from_text(text: &str) -> Self {
+        let mut known = Self::new();
+        for line in text.lines().map(str::trim) {
+            if !line.is_empty() && !line.starts_with('#') {
+                known.insert(line);
+            }
+        }
+        known
+    }
+
+    /// Reads CSV lines of a lemma and optionally a part of speech like `Verb`, e.g. `行く,Verb`.
+    /// A header line, blank lines and lines starting with # are skipped,
+    /// and so is a part of speech that isn't known.
+    pub fn from_csv(csv: &str) -> Self {
+        let mut known = Self::new();
+        for line in csv.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') || line.eq_ignore_ascii_case("lemma,pos") {
+                continue;
+            }
+
+            let mut fields = line.split(',').map(|field| field.trim().trim_matches('"'));
+            let lemma = fields.next().unwrap_or_default();
+            match fields.next().and_then(parse_part_of_speech) {
+                Some(pos) => known.insert_with_pos(lemma, pos),
+                None => known.insert(lemma),
+            };
+        }
+        known
+    }
+
+    /// One lemma per line, for `from_text`. Parts of speech are left out.
+    pub fn to_text(&self) -> String {
+        let mut lemmas: Vec<&String> = self.lemmas.keys().collect();
+        lemmas.sort();
+        lemmas
+            .into_iter()
+            .map(|lemma| format!("{}\n", lemma))
+            .collect()
+    }
+
+    /// CSV with a `lemma,pos` header, for `from_csv`
+    pub fn to_csv(&self) -> String {
+        let mut lines: Vec<String> = self
+            .lemmas
+            .iter()
+            .flat_map(|(lemma, parts_of_speech)| {
+                if parts_of_speech.is_empty() {
+                    vec![format!("{},", lemma)]
+                } else {
+                    parts_of_speech
+                        .iter()
+                        .map(|pos| format!("{},{:?}", lemma, pos))
+                        .collect()
+                }
+            })
+            .collect();
+        lines.sort();
+
+        let mut csv = String::from("lemma,pos\n");
+        for line in lines {
+            csv.push_str(&line);
+            csv.push('\n');
+        }
+        csv
+    }
+
+    /// Knows `lemma` with any part of speech
+    pub fn insert(&mut self, lemma: &str) -> &mut Self {
+        let lemma = normalize(lemma);
+        if !lemma.is_empty() {
+            self.lemmas.entry(lemma).or_default().clear();
+        }
+        self
+    }
+
+    /// Knows `lemma` with `pos`, unless it's already known with any part of speech
+    pub fn insert_with_pos(&mut self, lemma: &str, pos: PartOfSpeech) -> &mut Self {
+        let lemma = normalize(lemma);
+        if lemma.is_empty() {
+            return self;
+        }
+
+        match self.lemmas.get_mut(&lemma) {
+            Some(parts_of_speech) if parts_of_speech.is_empty() => {}
+            Some(parts_of_speech) => {
+                parts_of_speech.insert(pos);
+            }
+            None => {
+                self.lemmas.insert(lemma, HashSet::from([pos]));
+            }
+        }
+        self
+    }
+
+    /// Knows the lemma of `word` with its part of speech
+    pub fn insert_word(&mut self, word: &Word) -> &mut Self {
+        self.insert_with_pos(lemma(word), word.part_of_speech)
+    }
+
+    pub fn remove(&mut self, lemma: &str) -> &mut Self {
+        self.lemmas.remove(&normalize(lemma));
+        self
+    }
+
+    /// Whether `lemma` is known with any part of speech
+    pub fn contains_lemma(&self, lemma: &str) -> bool {
+        self.lemmas.contains_key(&normalize(lemma))
+    }
+
+    /// Whether the lemma of `word` is known, with the word's part of speech
+    /// if the lemma was added with one
+    pub fn contains(&self, word: &Word) -> bool {
+        match self.lemmas.get(&normalize(lemma(word))) {
+            Some(parts_of_speech) => {
+                parts_of_speech.is_empty() || parts_of_speech.contains(&word.part_of_speech)
+            }
+            None => false,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.lemmas.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lemmas.is_empty()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for KnownWords {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut known = Self::new();
+        known.extend(iter);
+        known
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for KnownWords {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for lemma in iter {
+            self.insert(lemma.as_ref());
+        }
+    }
+}
+
+pub(crate) fn lemma(word: &Word) -> &str {
+    word.lemma.as_deref().unwrap_or(&word.word)
+}
+
+/// Parses the names `PartOfSpeech` is printed with, ignoring case
+fn parse_part_of_speech(name: &str) -> Option<PartOfSpeech> {
+    use PartOfSpeech::*;
+
+    [
+        Noun,
+        ProperNoun,
+        Pronoun,
+        Adjective,
+        Adverb,
+        Determiner,
+        Preposition,
+        Postposition,
+        Verb,
+        Suffix,
+        Prefix,
+        Conjunction,
+        Interjection,
+        Number,
+        Unknown,
+        Symbol,
+        Other,
+        Foreign,
+    ]
+    .into_iter()
+    .find(|pos| format!("{:?}", pos).eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn knows_words_by_their_lemma() {
+        let known: KnownWords = ["食べる", "ｶﾀｶﾅ "].into_iter().collect();
+        let words = words("食べました猫");
+
+        assert!(known.contains(&words[0]));
+        assert!(!known.contains(&words[1]));
+        assert!(known.contains_lemma("カタカナ"));
+    }
+
+    #[test]
+    fn knows_lemmas_with_a_part_of_speech() {
+        let mut known = KnownWords::new();
+        known.insert_with_pos("猫", PartOfSpeech::Verb);
+        assert!(!known.contains(&words("猫")[0]));
+
+        known.insert_word(&words("猫")[0]);
+        assert!(known.contains(&words("猫")[0]));
+
+        known.insert("猫").insert_with_pos("猫", PartOfSpeech::Verb);
+        assert_eq!(known.to_csv(), "lemma,pos\n猫,\n");
+    }
+
+    #[test]
+    fn reads_lists_and_csv() {
+        let known = KnownWords::from_text("# known\n猫\n\n 嘘 \n");
+        assert_eq!(known.to_text(), "嘘\n猫\n");
+
+        let known = KnownWords::from_csv("lemma,pos\n食べる,verb\n\"猫\",Noun\n嘘,Nonsense\n");
+        assert_eq!(known.to_csv(), "lemma,pos\n嘘,\n猫,Noun\n食べる,Verb\n");
+        assert_eq!(KnownWords::from_csv(&known.to_csv()), known);
+    }
+
+    #[test]
+    fn forgets_removed_lemmas() {
+        let mut known = KnownWords::from_text("猫\n嘘\n");
+        known.remove("猫");
+
+        assert_eq!(known.len(), 1);
+        assert!(!known.contains_lemma("猫"));
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Word;
+
+/// A token in the shape of Kuromoji's, for tools written against its JSON output.
+/// Serialized with Kuromoji's camelCase names, e.g. `baseForm`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct KuromojiToken {
+    pub surface: String,
+    pub base_form: String,
+    pub reading: String,
+    pub pronunciation: String,
+    /// The four part-of-speech levels, like 名詞, 固有名詞, 人名, 姓
+    pub part_of_speech: Vec<String>,
+    pub conjugation_type: String,
+    pub conjugation_form: String,
+    /// Offset in UTF-16 code units, like Java strings count them
+    pub position: usize,
+    /// Whether the token is in the dictionary
+    pub known: bool,
+}
+
+/// The tokens of the words of `text` as Kuromoji would give them. Kuromoji doesn't group
+/// tokens into words, so there's one for each token rather than each word.
+pub fn kuromoji_tokens(text: &str, words: &[Word]) -> Vec<KuromojiToken> {
+    let utf16_offset = |byte: usize| text[..byte].encode_utf16().count();
+
+    words
+        .iter()
+        .flat_map(|word| &word.tokens)
+        .map(|token| {
+            let tags = token.raw_tags();
+            KuromojiToken {
+                surface: token.literal.clone(),
+                base_form: token.lemma.clone(),
+                reading: token.reading.clone(),
+                pronunciation: token.hatsuon.clone(),
+                part_of_speech: vec![
+                    tags.pos1.clone(),
+                    tags.pos2.clone(),
+                    tags.pos3.clone(),
+                    tags.pos4.clone(),
+                ],
+                conjugation_type: tags.inflection_type.clone(),
+                conjugation_form: tags.inflection_form.clone(),
+                position: utf16_offset(token.range.start),
+                known: !token.is_oov,
+            }
+        })
+        .collect()
+}
+
+/// The tokens of [`kuromoji_tokens`] as a JSON array
+#[cfg(feature = "json")]
+pub fn kuromoji_json(text: &str, words: &[Word]) -> String {
+    serde_json::to_string(&kuromoji_tokens(text, words)).expect("tokens serialize to JSON")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn has_a_token_for_each_token_of_the_words() {
+        let text = "猫が食べました";
+        let tokens = kuromoji_tokens(text, &words(text));
+
+        let surfaces: Vec<&str> = tokens.iter().map(|t| t.surface.as_str()).collect();
+        assert_eq!(surfaces, ["猫", "が", "食べ", "まし", "た"]);
+        assert_eq!(
+            tokens[2],
+            KuromojiToken {
+                surface: "食べ".into(),
+                base_form: "食べる".into(),
+                reading: "タベ".into(),
+                pronunciation: "タベ".into(),
+                part_of_speech: vec!["動詞".into(), "自立".into(), "*".into(), "*".into()],
+                conjugation_type: "一段".into(),
+                conjugation_form: "連用形".into(),
+                position: 2,
+                known: true,
+            }
+        );
+    }
+
+    #[test]
+    fn counts_positions_in_utf16_code_units() {
+        let text = "𠮷猫";
+        let tokens = kuromoji_tokens(text, &words(text));
+
+        assert_eq!(tokens[0].position, 0);
+        assert!(!tokens[0].known);
+        assert_eq!(tokens[1].position, 2);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn writes_kuromoji_field_names() {
+        let json: serde_json::Value =
+            serde_json::from_str(&kuromoji_json("猫", &words("猫"))).unwrap();
+
+        assert_eq!(json[0]["baseForm"], "猫");
+        assert_eq!(json[0]["partOfSpeech"][0], "名詞");
+        assert_eq!(json[0]["position"], 0);
+    }
+}
@@ -0,0 +1,138 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_text, FuriganaSegment, ParseConfig, Result, Tokenize};
+
+/// The LaTeX package whose `\ruby` command `annotate_latex` writes for
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LatexRuby {
+    /// pxrubrica: `\ruby{食}{た}`, with kanji compounds as jukugo ruby `\jruby{勉|強}{べん|きょう}`
+    #[default]
+    Pxrubrica,
+    /// okumacro: `\ruby{食}{た}`, with each kanji of a compound getting its own `\ruby`
+    Okumacro,
+}
+
+/// Writes `text` as LaTeX with furigana over its kanji, escaping LaTeX's special characters.
+/// Line breaks are kept, the result goes into a document that loads the package of `style`.
+pub fn annotate_latex(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+    style: LatexRuby,
+) -> Result<String> {
+    let mut output = String::with_capacity(text.len() * 2);
+    let mut position = 0;
+
+    for sentence in parse_text(tokenizer, text, config)? {
+        for word in &sentence.words {
+            let range = word.range();
+            push_escaped(&mut output, &text[position..range.start]);
+            position = range.end;
+
+            if text[range.clone()] != word.word {
+                push_escaped(&mut output, &text[range]);
+                continue;
+            }
+
+            let segments = word.furigana_segments_with(&config.reading_overrides);
+            // Runs of segments with readings, like 勉 強 in 勉強する
+            for run in segments.chunk_by(|a, b| a.reading.is_some() == b.reading.is_some()) {
+                match (run, style) {
+                    ([FuriganaSegment { reading: None, .. }, ..], _) => {
+                        for segment in run {
+                            push_escaped(&mut output, &segment.text);
+                        }
+                    }
+                    ([_, _, ..], LatexRuby::Pxrubrica) => {
+                        push_command(&mut output, "jruby", run);
+                    }
+                    _ => {
+                        for segment in run {
+                            push_command(&mut output, "ruby", std::slice::from_ref(segment));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    push_escaped(&mut output, &text[position..]);
+
+    Ok(output)
+}
+
+/// Writes `\command{a|b}{reading a|reading b}`
+fn push_command(output: &mut String, command: &str, segments: &[FuriganaSegment]) {
+    output.push('\\');
+    output.push_str(command);
+
+    for part in [
+        |segment: &FuriganaSegment| segment.text.clone(),
+        |segment: &FuriganaSegment| segment.reading.clone().unwrap_or_default(),
+    ] {
+        output.push('{');
+        for (i, segment) in segments.iter().enumerate() {
+            if i > 0 {
+                output.push('|');
+            }
+            push_escaped(output, &part(segment));
+        }
+        output.push('}');
+    }
+}
+
+fn push_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '#' | '$' | '%' | '&' | '_' | '{' | '}' => {
+                output.push('\\');
+                output.push(c);
+            }
+            '\\' => output.push_str("\\textbackslash{}"),
+            '~' => output.push_str("\\textasciitilde{}"),
+            '^' => output.push_str("\\textasciicircum{}"),
+            '|' => output.push_str("\\textbar{}"),
+            _ => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+
+    fn annotate(text: &str, style: LatexRuby) -> String {
+        annotate_latex(&Lexicon, text, &ParseConfig::default(), style).unwrap()
+    }
+
+    #[test]
+    fn writes_compounds_as_jukugo_ruby_for_pxrubrica() {
+        assert_eq!(
+            annotate("勉強", LatexRuby::Pxrubrica),
+            "\\jruby{勉|強}{べん|きょう}"
+        );
+        assert_eq!(
+            annotate("食べました", LatexRuby::Pxrubrica),
+            "\\ruby{食}{た}べました"
+        );
+    }
+
+    #[test]
+    fn writes_each_kanji_on_its_own_for_okumacro() {
+        assert_eq!(
+            annotate("勉強", LatexRuby::Okumacro),
+            "\\ruby{勉}{べん}\\ruby{強}{きょう}"
+        );
+    }
+
+    #[test]
+    fn escapes_special_characters() {
+        assert_eq!(
+            annotate("猫 100% & {~}\n", LatexRuby::Pxrubrica),
+            "\\ruby{猫}{ねこ} 100\\% \\& \\{\\textasciitilde{}\\}\n"
+        );
+    }
+}
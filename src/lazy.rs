@@ -0,0 +1,57 @@
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::schema::{split_features, RawTags};
+
+/// The raw tags of a `PreparedToken`, only read from the token's feature string once they're
+/// accessed since most callers never look at them
+#[derive(Clone)]
+pub(crate) struct LazyRawTags {
+    features: Option<Arc<str>>,
+    value: OnceLock<RawTags>,
+}
+
+impl LazyRawTags {
+    pub fn new(features: &str) -> Self {
+        Self {
+            features: Some(features.into()),
+            value: OnceLock::new(),
+        }
+    }
+
+    pub fn get(&self) -> &RawTags {
+        self.value.get_or_init(|| {
+            let features = self
+                .features
+                .as_ref()
+                .expect("lazy raw tags have either features or a value");
+            RawTags::from_features(&split_features(features))
+        })
+    }
+}
+
+impl fmt::Debug for LazyRawTags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for LazyRawTags {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for LazyRawTags {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RawTags::deserialize(deserializer).map(|tags| Self {
+            features: None,
+            value: OnceLock::from(tags),
+        })
+    }
+}
@@ -1,13 +1,186 @@
-use anyhow::{bail, Result};
+use std::ops::Range;
+
+use vibrato::dictionary::LexType;
 
 #[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
 
+mod adjectives;
+mod analyze;
+mod aozora;
+#[cfg(feature = "binary")]
+mod binary;
+mod cloze;
+mod compare;
+mod config;
+mod corpus;
+mod counters;
+mod coverage;
+mod diagnostics;
+mod difficulty;
+mod dictionary;
+mod document;
+mod entities;
+mod error;
+mod examples;
+mod expressions;
+mod fillers;
+mod filters;
+mod furigana;
+mod kana;
+mod kanji;
+mod lazy;
+mod known;
+mod kuromoji;
+mod handle;
+mod highlight;
+mod hook;
+#[cfg(feature = "html")]
+mod html;
+#[cfg(feature = "json")]
+mod json;
+mod latex;
+#[cfg(feature = "lindera")]
+mod lindera;
+mod markdown;
+#[cfg(feature = "mecab")]
+mod mecab;
+mod mecab_format;
+mod mining;
+mod negation;
+mod nominalizers;
+mod normalize;
+mod origin;
+mod phonemes;
+mod phrase;
+mod pipeline;
+mod postprocess;
+mod progress;
+mod readings;
+mod report;
+mod reconstruction;
+mod romaji;
+mod ruby;
+mod rules;
+mod schema;
+mod search;
+mod spans;
+mod srs;
+mod stats;
+mod subtitles;
+mod table;
+mod tense;
+#[cfg(test)]
+mod testing;
+mod tokenize;
+mod transcription;
+mod transitivity;
+mod voice;
+mod warnings;
+
+pub use adjectives::AdjectiveType;
+pub use analyze::{analyze_json, analyze_tokens, AnalyzeToken};
+pub use aozora::{apply_ruby, read_aozora, verify_readings, AozoraText, ReadingMismatch, Ruby};
+#[cfg(feature = "binary")]
+pub use binary::{from_binary, read_binary, to_binary, write_binary};
+pub use cloze::{make_cloze, Cloze, ClozeOptions, ClozeTarget};
+pub use compare::{compare_parses, compare_words, Difference, DifferenceKind, SentenceComparison};
+pub use config::ParseConfig;
+pub use corpus::parse_pipelined;
+pub use counters::CounterClass;
+pub use coverage::{coverage, coverage_of, Coverage, SentenceCoverage};
+pub use diagnostics::{ParseEvent, ParseObserver};
+pub use difficulty::{DifficultyModel, FrequencyModel};
+pub use dictionary::{
+    inspect_dictionary, resolve_dictionary, DictionaryInfo, DEFAULT_DICTIONARY, DICTIONARY_ENV,
+};
+pub use document::{parse_text, split_sentences, Sentence};
+pub use entities::{EntityKind, EntityOptions};
+pub use examples::{Example, ExampleIndex, IndexedSentence};
+pub use error::{Error, GroupError, PrepareError, Result, TokenizeError};
+pub use expressions::{Expression, ExpressionOptions};
+pub use fillers::{FillerKind, Fillers};
+pub use filters::{
+    ExpansionFilter, FilterChain, KanaFilter, LemmaFilter, LengthFilter, LowercaseFilter, StopFilter,
+    TokenFilter,
+};
+pub use furigana::FuriganaSegment;
+#[cfg(feature = "html")]
+pub use html::{annotate_html, HtmlAnnotation};
+pub use handle::{Metrics, Ve, VeBuilder};
+pub use highlight::{highlight, HighlightOptions, Snippet};
+pub use hook::Hook;
+#[cfg(feature = "json")]
+pub use json::{to_json, JsonlWriter};
+pub use kana::ReadingScript;
+pub use kanji::{kanji_grade, KanjiGrade};
+pub use known::KnownWords;
+#[cfg(feature = "json")]
+pub use kuromoji::kuromoji_json;
+pub use kuromoji::{kuromoji_tokens, KuromojiToken};
+pub use latex::{annotate_latex, LatexRuby};
+#[cfg(feature = "lindera")]
+pub use lindera::LinderaTokenizer;
+pub use markdown::{annotate_markdown, MarkdownRuby};
+#[cfg(feature = "mecab")]
+pub use mecab::MecabTokenizer;
+pub use mecab_format::{to_mecab, words_to_mecab};
+pub use mining::{mine_sentences, MinedSentence};
+pub use nominalizers::ParticleRole;
+pub use phonemes::{to_phonemes, AccentPhrase, PhoneSet};
+pub use phrase::{contains_near, contains_phrase, find_near, find_phrase, PhraseMatch};
+pub use pipeline::{Group, Pipeline, PipelineBuilder, Prepare, Tokenize};
+pub use postprocess::{merge_adjacent, PostProcessors};
+pub use progress::{Progress, ProgressObserver};
+pub use readings::ReadingOverrides;
+pub use reconstruction::{verify_reconstruction, Divergence};
+pub use report::{corpus_report, CorpusReport, ReportCollector};
+pub use romaji::{romanize, romanize_sentence, LongVowels, RomajiOptions, RomajiSystem};
+pub use ruby::{anki_furigana, ruby_html, RubyOptions};
+pub use rules::{Rule, RuleSet, TokenDecision};
+pub use schema::{
+    detect_schema, DictionarySchema, FeatureSchema, Inflection, IpadicSchema, NameKind, NounKind,
+    NounStem, ParticleKind, RawTags, SchemaToken, UnidicFields, UnidicSchema, VerbClass,
+    WordClass, WordOrigin,
+};
+pub use search::{
+    search_tokens, AnalysisMode, AnalysisProfile, SearchAnalyzer, SearchToken,
+};
+pub use spans::spans_html;
+pub use srs::{cards_to_tsv, make_cards, Card, CardOptions};
+pub use stats::{parse_with_stats, ParseStats};
+pub use subtitles::{annotate_cues, read_ass, read_srt, read_subtitles, AnnotatedCue, Cue};
+pub use table::{TableWriter, WordColumn};
+pub use tense::{Aspect, Tense};
+#[cfg(feature = "bundled-dict")]
+pub use tokenize::bundled_dictionary;
+pub use tokenize::{load_dictionary, tokenize};
+pub use transcription::TranscriptionOptions;
+pub use transitivity::{verb_pair, Transitivity};
+pub use voice::Voice;
+pub use warnings::{Strictness, Warning, WarningReason};
+
+use config::PosOverrides;
+use kana::{is_kana, to_hiragana, to_katakana};
+use lazy::LazyRawTags;
+use normalize::{is_acronym, is_katakana, spell_out};
+use readings::irregular_reading;
+use romaji::romanize_word;
+use rules::{Decision, RuleContext};
+use schema::TagCache;
+use transcription::{is_greeting, pronounce_particle};
+use warnings::Reporter;
+pub use normalize::{join_soft_line_breaks, normalize_width, NormalizeOptions};
+
 /// Simple struct that abstracts away vibrato's own Tokens
 /// that for some reason reference the worker they were tokenized from
 pub struct VibratoToken {
     pub surface: String,
     pub feature: String,
+    /// Whether vibrato's unknown-word handling made up this token, i.e. it's not in the dictionary
+    pub is_oov: bool,
+    /// Byte offsets of the token in the tokenized text
+    pub range: Range<usize>,
 }
 
 impl From<vibrato::token::Token<'_, '_>> for VibratoToken {
@@ -15,6 +188,8 @@ impl From<vibrato::token::Token<'_, '_>> for VibratoToken {
         Self {
             surface: value.surface().into(),
             feature: value.feature().into(),
+            is_oov: value.lex_type() == LexType::Unknown,
+            range: value.range_byte(),
         }
     }
 }
@@ -26,17 +201,139 @@ pub struct PreparedToken {
     pos: POS,
     pos2: POS,
     pos3: POS,
-    #[allow(dead_code)] // we're not using pos4 at all
     pos4: POS,
     inflection_type: POS,
     inflection_form: POS,
     lemma: String,
     reading: String,
     hatsuon: String,
+    unidic: Option<UnidicFields>,
+    counter: Option<CounterClass>,
+    verb_class: Option<VerbClass>,
+    raw_tags: LazyRawTags,
+    is_oov: bool,
+    range: Range<usize>,
+}
+
+impl PreparedToken {
+    /// Whether the token isn't in the dictionary, see `VibratoToken::is_oov`
+    pub fn is_oov(&self) -> bool {
+        self.is_oov
+    }
+
+    /// Byte offsets of the token in the tokenized text
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// UniDic's word origin, base forms and accent, when parsed with `DictionarySchema::Unidic`
+    pub fn unidic(&self) -> Option<&UnidicFields> {
+        self.unidic.as_ref()
+    }
+
+    /// What the token counts if it's a counter (助数詞), like the 枚 of 3枚
+    pub fn counter(&self) -> Option<CounterClass> {
+        self.counter
+    }
+
+    /// The conjugation class of a verb, like `Godan('カ')` for 書い
+    pub fn verb_class(&self) -> Option<VerbClass> {
+        self.verb_class
+    }
+
+    /// The POS and inflection tags as the dictionary has them, see [`RawTags`]
+    pub fn raw_tags(&self) -> &RawTags {
+        self.raw_tags.get()
+    }
+
+    /// The token in the dictionary's canonical orthography, e.g. 行っ for 行なっ or ウイルス
+    /// for ウィルス. Only UniDic has one, with other dictionaries this is the surface.
+    pub fn canonical_surface(&self) -> String {
+        let (Some(lemma), Some(orth_base)) = (self.canonical_lemma(), self.orth_base()) else {
+            return self.literal.clone();
+        };
+
+        // Swap the stem for the lexeme's, keeping the inflected ending
+        let prefix = self
+            .literal
+            .chars()
+            .zip(orth_base.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum::<usize>();
+        match lemma.strip_suffix(&orth_base[prefix..]) {
+            Some(stem) => format!("{}{}", stem, &self.literal[prefix..]),
+            None => self.literal.clone(),
+        }
+    }
+
+    /// UniDic's lexeme spelling, unless the token is written in kana only and the lexeme
+    /// isn't, so する doesn't become 為る or こと 事
+    fn canonical_lemma(&self) -> Option<&str> {
+        let lemma = self.unidic.as_ref()?.lemma.as_deref()?;
+        let kana_only = |text: &str| text.chars().all(is_kana);
+
+        (kana_only(lemma) || !kana_only(self.orth_base()?)).then_some(lemma)
+    }
+
+    fn orth_base(&self) -> Option<&str> {
+        self.unidic.as_ref()?.orth_base.as_deref()
+    }
+
+    /// Reading of the token's dictionary form, derived from its reading by swapping
+    /// the inflected kana ending for the lemma's, e.g. 難しかっ (ムズカシカッ) → ムズカシイ.
+    /// None if the token has no reading or its ending isn't kana.
+    pub fn lemma_reading(&self) -> Option<String> {
+        if self.reading.is_empty() || self.reading == "*" {
+            return None;
+        }
+        if self.lemma == self.literal || self.lemma.is_empty() || self.lemma == "*" {
+            return Some(self.reading.clone());
+        }
+
+        let mut prefix = self
+            .literal
+            .chars()
+            .zip(self.lemma.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum::<usize>();
+        // 来る is the only verb whose kanji is read differently when inflected (来ない, 来ます),
+        // unlike the 来 of compounds such as 出来る
+        let kuru = matches!(self.lemma.as_str(), "来る" | "来ます")
+            || self.reading.starts_with(['コ', 'キ', 'ク']);
+        if kuru && self.literal[..prefix].ends_with('来') {
+            prefix -= '来'.len_utf8();
+        }
+
+        let literal_ending = &self.literal[prefix..];
+        let lemma_ending = &self.lemma[prefix..];
+        let readable = |c: char| is_kana(c) || c == '来';
+        if !literal_ending.chars().all(readable) || !lemma_ending.chars().all(readable) {
+            return None;
+        }
+
+        // Every character of the ending, including 来, is read as a single kana
+        let stem_len = self
+            .reading
+            .chars()
+            .count()
+            .checked_sub(literal_ending.chars().count())?;
+        let mut reading: String = self.reading.chars().take(stem_len).collect();
+        reading.push_str(&to_katakana(&lemma_ending.replace('来', "ク")));
+
+        Some(reading)
+    }
+
+    /// Whether this is sentence-ending punctuation like 。
+    fn ends_sentence(&self) -> bool {
+        self.pos == POS::Kigou && self.pos2 == POS::Kuten
+    }
 }
 
 #[derive(PartialEq, Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[allow(clippy::upper_case_acronyms)]
 enum POS {
     Meishi,
     KoyuuMeishi,
@@ -78,61 +375,73 @@ enum POS {
     TokushuNu,
     Fuhenkagata,
     Jinmei,
+    Sei,
+    Mei,
+    Soshiki,
+    Chiiki,
     MeireiI,
     Kakarijoshi,
+    Kuten,
 
     Unset,
     Unknown,
 }
 
+/// The tags `POS` has a variant for, looked up once per feature column of every token
+static POS_TAGS: phf::Map<&'static str, POS> = phf::phf_map! {
+    "名詞" => POS::Meishi,
+    "固有名詞" => POS::KoyuuMeishi,
+    "代名詞" => POS::DaiMeishi,
+    "助動詞" => POS::JoDoushi,
+    "数" => POS::Kazu,
+    "助詞" => POS::Joshi,
+    "接頭詞" => POS::Settoushi,
+    "動詞" => POS::Doushi,
+    "記号" => POS::Kigou,
+    "フィラー" => POS::Firaa,
+    "その他" => POS::Sonota,
+    "感動詞" => POS::Kandoushi,
+    "連体詞" => POS::Rentaishi,
+    "接続詞" => POS::Setsuzokushi,
+    "副詞" => POS::Fukushi,
+    "接続助詞" => POS::Setsuzokujoshi,
+    "形容詞" => POS::Keiyoushi,
+    "非自立" => POS::Hijiritsu,
+    "副詞可能" => POS::Fukushikanou,
+    "サ変接続" => POS::Sahensetsuzoku,
+    "形容動詞語幹" => POS::Keiyoudoushigokan,
+    "ナイ形容詞語幹" => POS::Naikeiyoushigokan,
+    "助動詞語幹" => POS::Jodoushigokan,
+    "副詞化" => POS::Fukushika,
+    "体言接続" => POS::Taigensetsuzoku,
+    "連体化" => POS::Rentaika,
+    "特殊" => POS::Tokushu,
+    "接尾" => POS::Setsubi,
+    "接続詞的" => POS::Setsuzokushiteki,
+    "動詞非自立的" => POS::Doushihijiritsuteki,
+    "サ変・スル" => POS::SahenSuru,
+    "特殊・タ" => POS::TokushuTa,
+    "特殊・ナイ" => POS::TokushuNai,
+    "特殊・タイ" => POS::TokushuTai,
+    "特殊・デス" => POS::TokushuDesu,
+    "特殊・ダ" => POS::TokushuDa,
+    "特殊・マス" => POS::TokushuMasu,
+    "特殊・ヌ" => POS::TokushuNu,
+    "不変化型" => POS::Fuhenkagata,
+    "人名" => POS::Jinmei,
+    "姓" => POS::Sei,
+    "名" => POS::Mei,
+    "組織" => POS::Soshiki,
+    "地域" => POS::Chiiki,
+    "命令ｉ" => POS::MeireiI,
+    "係助詞" => POS::Kakarijoshi,
+    "句点" => POS::Kuten,
+    "*" => POS::Unset,
+};
+
 impl From<&str> for POS {
     fn from(value: &str) -> Self {
-        match value {
-            "名詞" => Self::Meishi,
-            "固有名詞" => Self::KoyuuMeishi,
-            "代名詞" => Self::DaiMeishi,
-            "助動詞" => Self::JoDoushi,
-            "数" => Self::Kazu,
-            "助詞" => Self::Joshi,
-            "接頭詞" => Self::Settoushi,
-            "動詞" => Self::Doushi,
-            "記号" => Self::Kigou,
-            "フィラー" => Self::Firaa,
-            "その他" => Self::Sonota,
-            "感動詞" => Self::Kandoushi,
-            "連体詞" => Self::Rentaishi,
-            "接続詞" => Self::Setsuzokushi,
-            "副詞" => Self::Fukushi,
-            "接続助詞" => Self::Setsuzokujoshi,
-            "形容詞" => Self::Keiyoushi,
-            "非自立" => Self::Hijiritsu,
-            "副詞可能" => Self::Fukushikanou,
-            "サ変接続" => Self::Sahensetsuzoku,
-            "形容動詞語幹" => Self::Keiyoudoushigokan,
-            "ナイ形容詞語幹" => Self::Naikeiyoushigokan,
-            "助動詞語幹" => Self::Jodoushigokan,
-            "副詞化" => Self::Fukushika,
-            "体言接続" => Self::Taigensetsuzoku,
-            "連体化" => Self::Rentaika,
-            "特殊" => Self::Tokushu,
-            "接尾" => Self::Setsubi,
-            "接続詞的" => Self::Setsuzokushiteki,
-            "動詞非自立的" => Self::Doushihijiritsuteki,
-            "サ変・スル" => Self::SahenSuru,
-            "特殊・タ" => Self::TokushuTa,
-            "特殊・ナイ" => Self::TokushuNai,
-            "特殊・タイ" => Self::TokushuTai,
-            "特殊・デス" => Self::TokushuDesu,
-            "特殊・ダ" => Self::TokushuDa,
-            "特殊・マス" => Self::TokushuMasu,
-            "特殊・ヌ" => Self::TokushuNu,
-            "不変化型" => Self::Fuhenkagata,
-            "人名" => Self::Jinmei,
-            "命令ｉ" => Self::MeireiI,
-            "係助詞" => Self::Kakarijoshi,
-            "*" => Self::Unset,
-            _ => Self::Unknown,
-        }
+        POS_TAGS.get(value).cloned().unwrap_or(Self::Unknown)
     }
 }
 
@@ -144,7 +453,7 @@ const BA: &str = "ば";
 const NN: &str = "ん";
 const SA: &str = "さ";
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Word {
     pub word: String,
@@ -152,17 +461,96 @@ pub struct Word {
     pub part_of_speech: PartOfSpeech,
     pub tokens: Vec<PreparedToken>,
     pub extra: WordExtra,
+    /// Whether any of the tokens isn't in the dictionary
+    pub is_oov: bool,
+}
+
+impl Word {
+    /// Byte offsets of the word in the tokenized text
+    pub fn range(&self) -> Range<usize> {
+        match (self.tokens.first(), self.tokens.last()) {
+            (Some(first), Some(last)) => first.range.start..last.range.end,
+            _ => 0..0,
+        }
+    }
+
+    /// The word in the dictionary's canonical orthography, see
+    /// [`PreparedToken::canonical_surface`]
+    pub fn canonical_surface(&self) -> String {
+        self.tokens.iter().map(PreparedToken::canonical_surface).collect()
+    }
+
+    /// The conjugation class of the word's verb, e.g. `Suru` for 勉強しました.
+    /// None if the word has no verb.
+    pub fn verb_class(&self) -> Option<VerbClass> {
+        self.tokens.iter().find_map(PreparedToken::verb_class)
+    }
+
+    /// The reading in hiragana, whichever script `ParseConfig::reading_script` put
+    /// `extra.reading` in. Long vowel marks are kept, so ラーメン is らーめん.
+    pub fn hiragana_reading(&self) -> String {
+        match &self.extra.hiragana_reading {
+            Some(reading) => reading.clone(),
+            None => to_hiragana(&self.extra.reading),
+        }
+    }
+
+    /// The dictionary's own tags of the word's first token, for the distinctions
+    /// `part_of_speech` doesn't make
+    pub fn raw_tags(&self) -> Option<&RawTags> {
+        self.tokens.first().map(PreparedToken::raw_tags)
+    }
+
+    /// Moves the offsets of the word's tokens, e.g. from a sentence into the text it's from
+    pub(crate) fn shift(&mut self, offset: usize) {
+        for token in &mut self.tokens {
+            token.range = token.range.start + offset..token.range.end + offset;
+        }
+    }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct WordExtra {
+    /// In katakana unless `ParseConfig::reading_script` says otherwise
     pub reading: String,
+    /// The reading in hiragana, with `ReadingScript::Both`
+    pub hiragana_reading: Option<String>,
+    /// Reading of the lemma, e.g. ムズカシイ for 難しかった
+    pub lemma_reading: Option<String>,
     pub transcription: String,
     pub grammar: Option<Grammar>,
+    /// Set on words that were grouped into an expression, see `ParseConfig::expressions`
+    pub expression: Option<Expression>,
+    /// Set on names of things, see `ParseConfig::entities`
+    pub entity: Option<EntityKind>,
+    /// Set on fillers and aizuchi with `Fillers::Tag`, see `ParseConfig::fillers`
+    pub filler: Option<FillerKind>,
+    /// Whether a ない, ぬ or ません negates the word, also when it's a separate word like the
+    /// ない of 静かではない
+    pub negated: bool,
+    /// Set on verbs and adjectives, from the auxiliaries grouped into them
+    pub tense: Option<Tense>,
+    /// Set on verbs with a ている, てある, てしまう or ておく, see [`Aspect`]
+    pub aspect: Option<Aspect>,
+    /// Set on verbs, from the れる, られる, せる or させる grouped into them
+    pub voice: Option<Voice>,
+    /// Set on nominalizers like the の of 食べるのが好き, see [`ParticleRole`]
+    pub particle_role: Option<ParticleRole>,
+    /// Romanized transcription, if enabled with `ParseConfig::romaji`
+    pub romaji: Option<String>,
+    /// Reading given by the text itself, which takes precedence over the dictionary's,
+    /// see [`apply_ruby`]
+    pub ruby: Option<String>,
+    /// How hard the word is, if a model is installed with `ParseConfig::difficulty`
+    pub difficulty: Option<f64>,
+    /// The UniDic fields of the word's first token, see [`PreparedToken::unidic`]
+    pub unidic: Option<UnidicFields>,
 }
 
-#[derive(PartialEq, Debug)]
+/// With the `serde` feature, serialized as the variant's name, e.g. `"ProperNoun"`.
+/// Config files name them the same way, so these names are kept as they are.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum PartOfSpeech {
     Noun,
@@ -182,40 +570,134 @@ pub enum PartOfSpeech {
     Unknown,
     Symbol,
     Other,
+    /// Latin-alphabet words like iPhone15Pro
+    Foreign,
 }
 
-#[derive(Debug)]
+/// With the `serde` feature, serialized as the variant's name like [`PartOfSpeech`]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Grammar {
+    /// Serialized spelled right, as `"Auxiliary"`
+    #[cfg_attr(feature = "serde", serde(rename = "Auxiliary", alias = "Auxillary"))]
     Auxillary,
     Nominal,
 }
 
 pub fn prepare_tokens(raw_tokens: Vec<VibratoToken>) -> Result<Vec<PreparedToken>> {
-    raw_tokens.into_iter().map(|raw_token| {
-        let features: Vec<&str> = raw_token.feature.split(',').collect();
+    prepare_tokens_with(raw_tokens, &ParseConfig::default())
+}
+
+pub fn prepare_tokens_with(raw_tokens: Vec<VibratoToken>, config: &ParseConfig) -> Result<Vec<PreparedToken>> {
+    prepare(raw_tokens, config, config.strictness).map(|(tokens, _)| tokens)
+}
+
+/// Like `prepare_tokens_with`, but works around malformed tokens instead of failing
+/// and returns what it worked around as warnings. `Strictness::Strict` is treated as `Lenient` here,
+/// with `Permissive` nothing is reported.
+pub fn prepare_tokens_lenient(raw_tokens: Vec<VibratoToken>, config: &ParseConfig) -> (Vec<PreparedToken>, Vec<Warning>) {
+    let strictness = config.strictness.max(Strictness::Lenient);
+
+    prepare(raw_tokens, config, strictness).expect("lenient preparation doesn't fail")
+}
+
+pub(crate) fn prepare(
+    raw_tokens: Vec<VibratoToken>,
+    config: &ParseConfig,
+    strictness: Strictness,
+) -> Result<(Vec<PreparedToken>, Vec<Warning>)> {
+    let mut reporter = Reporter::new(config, strictness);
+    let mut prepared = Vec::with_capacity(raw_tokens.len());
+    let mut sentence = 0;
+    let schema = config.read_schema();
+    let mut previous: Option<(&str, Vec<&str>)> = None;
+    let mut tag_cache = TagCache::default();
+
+    for raw_token in &raw_tokens {
+        let mut features = schema::split_features(&raw_token.feature);
 
-        let [pos, pos2, pos3, pos4, inflection_type, inflection_form] = features[..6] else {
-            bail!("Couldn't read all features from token. Make sure you're using an IPADIC dictionary")
+        if features.len() < 6 {
+            let reason = WarningReason::MissingFeatures { found: features.len() };
+            reporter.report(&raw_token.surface, sentence, reason).map_err(PrepareError)?;
+            features.resize(6, "*");
+        }
+
+        let pos = features[0];
+        let schema_token = SchemaToken {
+            surface: &raw_token.surface,
+            features: &features,
+            previous: previous.as_ref().map(|(surface, features)| (*surface, &features[..])),
         };
+        let mut lemma = schema.lemma(&schema_token);
+        let mut reading = schema.reading(&schema_token);
+        let mut hatsuon = schema.pronunciation(&schema_token);
+
+        // Unknown words don't come with a lemma or reading. They're their own dictionary form though,
+        // katakana ones are read as they're written and acronyms letter by letter
+        let spelled_out;
+        if raw_token.is_oov {
+            if lemma.is_empty() || lemma == "*" {
+                lemma = &raw_token.surface;
+            }
+
+            let read_as = if is_katakana(&raw_token.surface) {
+                Some(raw_token.surface.as_str())
+            } else if is_acronym(&raw_token.surface) {
+                spelled_out = spell_out(&raw_token.surface);
+                Some(spelled_out.as_str())
+            } else {
+                None
+            };
+
+            if let Some(read_as) = read_as {
+                for feature in [&mut reading, &mut hatsuon] {
+                    if feature.is_empty() || *feature == "*" {
+                        *feature = read_as;
+                    }
+                }
+            }
+        }
 
-        let lemma: &str = features.get(6).unwrap_or(&"");
-        let reading: &str = features.get(7).unwrap_or(&"");
-        let hatsuon: &str = features.get(8).unwrap_or(&"");
+        // Overridden readings replace the dictionary's, irregular ones only fill in a missing one.
+        // The pronunciation stays the dictionary's either way
+        if let Some(read_as) = config.reading_overrides.get(&raw_token.surface) {
+            reading = read_as;
+        } else if reading.is_empty() || reading == "*" {
+            if let Some(read_as) = irregular_reading(&raw_token.surface) {
+                reading = read_as;
+            }
+        }
+
+        let tags = tag_cache.tags(schema, &raw_token.feature, &schema_token);
+        let [parsed_pos, parsed_pos2, parsed_pos3, parsed_pos4, parsed_inf_type, parsed_inf_form] = tags;
 
-        let parsed_pos = POS::from(pos);
-        let parsed_pos2 = POS::from(pos2);
-        let parsed_pos3 = POS::from(pos3);
-        let parsed_pos4 = POS::from(pos4);
-        let parsed_inf_type = POS::from(inflection_type);
-        let parsed_inf_form = POS::from(inflection_form);
+        // Only the main POS is checked, since we're just acting upon the other values we know
+        // and otherwise leave tokens as is. Most of them aren't in our POS enum to begin with.
+        if parsed_pos == POS::Unknown {
+            config.observe(ParseEvent::UnknownPos { surface: &raw_token.surface, pos });
+            let reason = WarningReason::UnknownPos { pos: pos.into() };
+            reporter.report(&raw_token.surface, sentence, reason).map_err(PrepareError)?;
+        } else if parsed_pos == POS::Unset {
+            reporter
+                .report(&raw_token.surface, sentence, WarningReason::UnsetPos)
+                .map_err(PrepareError)?;
+        }
 
-        // We could check all others for unknown/invalid values too, 
-        // but since we're just acting upon values we know and otherwise leave tokens as is, it doesnt matter.
-        if parsed_pos == POS::Unset { bail!("The main POS of token '{}' couldn't be identified", raw_token.surface);}
+        let hatsuon = match parsed_pos {
+            POS::Joshi => pronounce_particle(&raw_token.surface, hatsuon, reading),
+            POS::Kandoushi if is_greeting(&raw_token.surface) => {
+                pronounce_particle(&raw_token.surface, hatsuon, reading)
+            }
+            _ => hatsuon.into(),
+        };
 
-        Ok(PreparedToken {
-            literal: raw_token.surface,
+        let verb_class = match parsed_pos {
+            POS::Doushi => schema.verb_class(&schema_token),
+            _ => None,
+        };
+
+        let token = PreparedToken {
+            literal: raw_token.surface.clone(),
             pos: parsed_pos,
             pos2: parsed_pos2,
             pos3: parsed_pos3,
@@ -223,212 +705,245 @@ pub fn prepare_tokens(raw_tokens: Vec<VibratoToken>) -> Result<Vec<PreparedToken
             inflection_type: parsed_inf_type,
             inflection_form: parsed_inf_form,
             lemma: lemma.into(),
-            reading:  reading.into(),
-            hatsuon: hatsuon.into(),
-        })
+            reading: reading.into(),
+            hatsuon,
+            unidic: schema.unidic_fields(&schema_token),
+            counter: schema.is_counter(&schema_token).then(|| CounterClass::of(lemma)),
+            verb_class,
+            raw_tags: LazyRawTags::new(&raw_token.feature),
+            is_oov: raw_token.is_oov,
+            range: raw_token.range.clone(),
+        };
+
+        if token.ends_sentence() {
+            sentence += 1;
+        }
 
-    }).collect()
+        prepared.push(token);
+        previous = Some((&raw_token.surface, features));
+    }
+
+    Ok((prepared, reporter.warnings))
 }
 
+/// The value of a feature column, None if the dictionary leaves it empty or `*`
 fn sanitize_asterisk(value: &str) -> Option<String> {
     if value.is_empty() || value == "*" {
-        Some(value.into())
-    } else {
         None
+    } else {
+        Some(value.into())
+    }
+}
+
+/// Adds a token's dictionary form reading to a word's `lemma_reading`,
+/// which becomes None if the token's can't be derived
+fn extend_lemma_reading(lemma_reading: &mut Option<String>, token: &PreparedToken) {
+    match (lemma_reading.as_mut(), token.lemma_reading()) {
+        (Some(reading), Some(other)) => reading.push_str(&other),
+        _ => *lemma_reading = None,
     }
 }
 
 pub fn parse_into_words(tokens: Vec<PreparedToken>) -> Result<Vec<Word>> {
-    let mut words: Vec<Word> = Vec::new();
-    let mut iter = tokens.iter().peekable();
-    let mut previous: Option<PreparedToken> = None;
+    parse_into_words_with(tokens, &ParseConfig::default())
+}
 
-    while let Some(token) = iter.next() {
-        let mut pos: Option<PartOfSpeech> = None;
-        let mut grammar: Option<Grammar> = None;
-        let mut eat_next = false;
-        let mut eat_lemma = false;
-        let mut attach_to_previous = false;
-        let mut also_attach_to_lemma = false;
-        let mut update_pos = false;
-
-        match token.pos {
-            POS::Meishi => {
-                pos = Some(PartOfSpeech::Noun);
-                match token.pos2 {
-                    POS::KoyuuMeishi => {
-                        pos = Some(PartOfSpeech::ProperNoun);
-                    }
-                    POS::DaiMeishi => {
-                        pos = Some(PartOfSpeech::Pronoun);
-                    }
-                    POS::Fukushikanou
-                    | POS::Sahensetsuzoku
-                    | POS::Keiyoudoushigokan
-                    | POS::Naikeiyoushigokan => {
-                        if let Some(following) = iter.peek() {
-                            if following.inflection_type == POS::SahenSuru {
-                                pos = Some(PartOfSpeech::Verb);
-                                eat_next = true;
-                            } else if following.inflection_type == POS::TokushuDa {
-                                pos = Some(PartOfSpeech::Adjective);
-                                if following.inflection_form == POS::Taigensetsuzoku {
-                                    eat_next = true;
-                                    eat_lemma = false;
-                                }
-                            } else if following.inflection_type == POS::TokushuNai {
-                                pos = Some(PartOfSpeech::Adjective);
-                                eat_next = true;
-                            } else if following.pos == POS::Joshi && following.literal == NI {
-                                pos = Some(PartOfSpeech::Adverb);
-                                eat_next = false;
-                            }
-                        }
-                    }
-                    POS::Hijiritsu | POS::Tokushu => {
-                        if let Some(following) = iter.peek() {
-                            match token.pos3 {
-                                POS::Fukushikanou => {
-                                    if following.pos == POS::Joshi && following.literal == NI {
-                                        pos = Some(PartOfSpeech::Adverb);
-                                        eat_next = true;
-                                    }
-                                }
-                                POS::Jodoushigokan => {
-                                    if following.inflection_type == POS::TokushuDa {
-                                        pos = Some(PartOfSpeech::Verb);
-                                        grammar = Some(Grammar::Auxillary);
-
-                                        if following.inflection_form == POS::Taigensetsuzoku {
-                                            eat_next = true;
-                                        }
-                                    } else if following.pos == POS::Joshi
-                                        && following.pos2 == POS::Fukushika
-                                    {
-                                        pos = Some(PartOfSpeech::Adverb);
-                                        eat_next = true;
-                                    }
-                                }
-                                POS::Keiyoudoushigokan => {
-                                    pos = Some(PartOfSpeech::Adjective);
-                                    if (following.inflection_type == POS::TokushuDa
-                                        && following.inflection_form == POS::Taigensetsuzoku)
-                                        || following.pos2 == POS::Rentaika
-                                    {
-                                        eat_next = true;
-                                    }
-                                }
-                                _ => (),
-                            }
-                        }
-                    }
-                    POS::Kazu => {
-                        pos = Some(PartOfSpeech::Number);
-                        if words.len() > 0
-                            && words
-                                .last()
-                                .is_some_and(|w| w.part_of_speech == PartOfSpeech::Number)
-                        {
-                            attach_to_previous = true;
-                            also_attach_to_lemma = true;
-                        }
-                    }
-                    POS::Setsubi => {
-                        if token.pos3 == POS::Jinmei {
-                            pos = Some(PartOfSpeech::Suffix);
-                        } else {
-                            if token.pos3 == POS::Tokushu && token.lemma == SA {
-                                update_pos = true;
-                                pos = Some(PartOfSpeech::Noun);
-                            } else {
-                                also_attach_to_lemma = true;
-                            }
-                            attach_to_previous = true;
-                        }
-                    }
-                    POS::Setsuzokushiteki => {
-                        pos = Some(PartOfSpeech::Conjunction);
-                    }
-                    POS::Doushihijiritsuteki => {
-                        pos = Some(PartOfSpeech::Verb);
-                        grammar = Some(Grammar::Nominal)
-                    }
-                    _ => (),
-                }
-            }
-            POS::Settoushi => {
-                pos = Some(PartOfSpeech::Prefix);
-            }
-            POS::JoDoushi => {
-                pos = Some(PartOfSpeech::Postposition);
-
-                if (previous.is_none() || (previous.is_some_and(|p| p.pos2 != POS::Kakarijoshi)))
-                    && [
-                        POS::TokushuTa,
-                        POS::TokushuNai,
-                        POS::TokushuTai,
-                        POS::TokushuMasu,
-                        POS::TokushuNu,
-                    ]
-                    .contains(&token.inflection_type)
-                {
-                    attach_to_previous = true;
-                } else if token.inflection_type == POS::Fuhenkagata && token.lemma == NN {
-                    attach_to_previous = true;
-                } else if [POS::TokushuDa, POS::TokushuDesu].contains(&token.inflection_type)
-                    && token.literal != NA
-                {
-                    pos = Some(PartOfSpeech::Verb)
-                }
-            }
-            POS::Doushi => {
-                pos = Some(PartOfSpeech::Verb);
-                if token.pos2 == POS::Setsubi {
-                    attach_to_previous = true;
-                } else if token.pos2 == POS::Hijiritsu && token.inflection_form != POS::MeireiI {
-                    attach_to_previous = true;
-                }
+/// Part of speech a token gets on its own, before any grouping rules are applied
+fn classify(token: &PreparedToken) -> Decision {
+    let mut grammar = None;
+
+    let pos = match token.pos {
+        POS::Meishi => match token.pos2 {
+            POS::KoyuuMeishi => Some(PartOfSpeech::ProperNoun),
+            POS::DaiMeishi => Some(PartOfSpeech::Pronoun),
+            POS::Kazu => Some(PartOfSpeech::Number),
+            POS::Setsubi if token.pos3 == POS::Jinmei => Some(PartOfSpeech::Suffix),
+            POS::Setsuzokushiteki => Some(PartOfSpeech::Conjunction),
+            POS::Doushihijiritsuteki => {
+                grammar = Some(Grammar::Nominal);
+                Some(PartOfSpeech::Verb)
             }
-            POS::Keiyoushi => {
-                pos = Some(PartOfSpeech::Adjective);
+            _ => Some(PartOfSpeech::Noun),
+        },
+        POS::Settoushi => Some(PartOfSpeech::Prefix),
+        POS::JoDoushi => {
+            if [POS::TokushuDa, POS::TokushuDesu].contains(&token.inflection_type)
+                && token.literal != NA
+            {
+                Some(PartOfSpeech::Verb)
+            } else {
+                Some(PartOfSpeech::Postposition)
             }
-            POS::Joshi => {
-                pos = Some(PartOfSpeech::Postposition);
-                if token.pos2 == POS::Setsuzokujoshi
-                    && [TE, DE, BA].contains(&token.literal.as_str())
+        }
+        POS::Doushi => Some(PartOfSpeech::Verb),
+        POS::Keiyoushi => Some(PartOfSpeech::Adjective),
+        POS::Joshi => Some(PartOfSpeech::Postposition),
+        POS::Rentaishi => Some(PartOfSpeech::Determiner),
+        POS::Setsuzokushi => Some(PartOfSpeech::Conjunction),
+        POS::Fukushi => Some(PartOfSpeech::Adverb),
+        POS::Kigou => Some(PartOfSpeech::Symbol),
+        POS::Firaa | POS::Kandoushi => Some(PartOfSpeech::Interjection),
+        POS::Sonota => Some(PartOfSpeech::Other),
+        _ => None,
+    };
+
+    Decision {
+        pos,
+        grammar,
+        ..Default::default()
+    }
+}
+
+pub fn parse_into_words_with(tokens: Vec<PreparedToken>, config: &ParseConfig) -> Result<Vec<Word>> {
+    parse(tokens, config, config.strictness).map(|(words, _)| words)
+}
+
+/// Like `parse_into_words_with`, but works around tokens it can't make sense of instead of failing
+/// and returns what it worked around as warnings. `Strictness::Strict` is treated as `Lenient` here,
+/// with `Permissive` nothing is reported.
+pub fn parse_into_words_lenient(tokens: Vec<PreparedToken>, config: &ParseConfig) -> (Vec<Word>, Vec<Warning>) {
+    let strictness = config.strictness.max(Strictness::Lenient);
+
+    parse(tokens, config, strictness).expect("lenient parsing doesn't fail")
+}
+
+/// What the grouping rules decided for each token, without the passes that run once the
+/// words are grouped. There's a decision for every token, including the ones eaten by the
+/// token before them.
+pub fn parse_into_decisions(tokens: Vec<PreparedToken>, config: &ParseConfig) -> Result<Vec<TokenDecision>> {
+    let mut reporter = Reporter::new(config, config.strictness);
+    let mut decisions = Vec::with_capacity(tokens.len());
+
+    group(&tokens, config, &mut reporter, Some(&mut decisions))?;
+
+    Ok(decisions)
+}
+
+pub(crate) fn parse(
+    tokens: Vec<PreparedToken>,
+    config: &ParseConfig,
+    strictness: Strictness,
+) -> Result<(Vec<Word>, Vec<Warning>)> {
+    let mut reporter = Reporter::new(config, strictness);
+    let mut words = group(&tokens, config, &mut reporter, None)?;
+
+    for word in &mut words {
+        if config.canonical_lemmas {
+            if let (Some(lemma), Some(first)) = (&mut word.lemma, word.tokens.first()) {
+                if let (Some(canonical), Some(rest)) =
+                    (first.canonical_lemma(), lemma.strip_prefix(first.lemma.as_str()))
                 {
-                    attach_to_previous = true;
+                    *lemma = format!("{}{}", canonical, rest);
                 }
             }
-            POS::Rentaishi => {
-                pos = Some(PartOfSpeech::Determiner);
-            }
-            POS::Setsuzokushi => {
-                pos = Some(PartOfSpeech::Conjunction);
-            }
-            POS::Fukushi => {
-                pos = Some(PartOfSpeech::Adverb);
+        }
+
+        if let Some(options) = &config.romaji {
+            word.extra.romaji = Some(romanize_word(word, options));
+        }
+
+        word.extra.transcription = config.transcription.apply(&word.extra.transcription);
+
+        match config.reading_script {
+            ReadingScript::Katakana => (),
+            ReadingScript::Hiragana => {
+                word.extra.reading = to_hiragana(&word.extra.reading);
+                word.extra.lemma_reading = word.extra.lemma_reading.as_deref().map(to_hiragana);
             }
-            POS::Kigou => {
-                pos = Some(PartOfSpeech::Symbol);
+            ReadingScript::Both => {
+                word.extra.hiragana_reading = Some(to_hiragana(&word.extra.reading));
             }
-            POS::Firaa | POS::Kandoushi => {
-                pos = Some(PartOfSpeech::Interjection);
+        }
+    }
+
+    config.expressions.apply(&mut words);
+    config.entities.apply(&mut words);
+    negation::mark_negation(&mut words);
+    tense::mark_tense(&mut words);
+    voice::mark_voice(&mut words);
+    nominalizers::mark_nominalizers(&mut words);
+    config.fillers.apply(&mut words, config);
+
+    if let Some(model) = &config.difficulty {
+        for word in &mut words {
+            let lemma = word.lemma.as_deref().unwrap_or(&word.word);
+            let score = model.score(lemma, word.part_of_speech, &word.extra.reading);
+            word.extra.difficulty = Some(score);
+        }
+    }
+
+    config.post_processors.run(&mut words);
+
+    Ok((words, reporter.warnings))
+}
+
+/// Groups the tokens into words by the rules, recording what was decided for each token
+/// in `decisions` if given
+fn group(
+    tokens: &[PreparedToken],
+    config: &ParseConfig,
+    reporter: &mut Reporter,
+    mut decisions: Option<&mut Vec<TokenDecision>>,
+) -> Result<Vec<Word>> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut sentence = 0;
+    let mut iter = tokens.iter().peekable();
+    let mut previous: Option<&PreparedToken> = None;
+    let pos_overrides = PosOverrides::new(&config.pos_overrides);
+
+    while let Some(token) = iter.next() {
+        let ctx = RuleContext {
+            token,
+            previous,
+            following: iter.peek().copied(),
+            last_word: words.last(),
+        };
+
+        let mut decision = classify(token);
+        let mut fired = None;
+        for rule in config.rules.iter() {
+            if rule.apply(&ctx, &mut decision) {
+                config.observe(ParseEvent::RuleFired { rule, surface: &token.literal });
+                fired = Some(rule);
+                break;
             }
-            POS::Sonota => pos = Some(PartOfSpeech::Other),
-            _ => (),
         }
 
+        let Decision {
+            pos,
+            grammar,
+            eat_next,
+            eat_lemma,
+            attach_to_previous,
+            also_attach_to_lemma,
+            update_pos,
+        } = decision;
+
         // let's make sure we found *some* part of speech here
-        if pos.is_none() {
-            bail!(
-                "Part of speech couldn't be recognized for token {}",
-                token.literal
-            );
+        let pos = match pos {
+            Some(pos) => pos,
+            None => {
+                reporter
+                    .report(&token.literal, sentence, WarningReason::UnrecognizedPos)
+                    .map_err(GroupError)?;
+                PartOfSpeech::Unknown
+            }
+        };
+        let pos = pos_overrides.get(token).unwrap_or(pos);
+
+        if let Some(decisions) = decisions.as_deref_mut() {
+            decisions.push(TokenDecision {
+                surface: token.literal.clone(),
+                part_of_speech: pos,
+                rule: fired,
+                eat_next,
+                attach_to_previous: attach_to_previous && !words.is_empty(),
+                update_pos,
+                eaten: false,
+            });
         }
-        let pos = pos.unwrap();
 
-        if attach_to_previous && words.len() > 0 {
+        if attach_to_previous && !words.is_empty() {
             let last = words.last_mut().unwrap();
 
             let token = token.clone();
@@ -436,11 +951,13 @@ pub fn parse_into_words(tokens: Vec<PreparedToken>) -> Result<Vec<Word>> {
             last.word.push_str(&token.literal);
             last.extra.reading.push_str(&token.reading);
             last.extra.transcription.push_str(&token.hatsuon);
+            last.is_oov |= token.is_oov;
 
             if also_attach_to_lemma {
                 if let Some(ref mut lemma) = last.lemma {
                     lemma.push_str(&token.lemma)
                 }
+                extend_lemma_reading(&mut last.extra.lemma_reading, &token);
             }
 
             if update_pos {
@@ -451,6 +968,7 @@ pub fn parse_into_words(tokens: Vec<PreparedToken>) -> Result<Vec<Word>> {
         } else {
             let token = token.clone();
             let token2 = token.clone();
+            let lemma_reading = token.lemma_reading();
 
             let mut word = Word {
                 word: token.literal,
@@ -458,33 +976,109 @@ pub fn parse_into_words(tokens: Vec<PreparedToken>) -> Result<Vec<Word>> {
                 part_of_speech: pos,
                 tokens: vec![token2],
                 extra: WordExtra {
+                    lemma_reading,
                     reading: token.reading,
+                    hiragana_reading: None,
                     transcription: token.hatsuon,
                     grammar,
+                    expression: None,
+                    entity: None,
+                    filler: None,
+                    negated: false,
+                    tense: None,
+                    aspect: None,
+                    voice: None,
+                    particle_role: None,
+                    romaji: None,
+                    ruby: None,
+                    difficulty: None,
+                    unidic: token.unidic,
                 },
+                is_oov: token.is_oov,
             };
 
-            if eat_next {
-                let Some(following) = iter.next() else {
-                    bail!("eat_next was set despite there being no following token")
-                };
-
+            if let Some(following) = iter.next_if(|_| eat_next) {
+                if let Some(decisions) = decisions.as_deref_mut() {
+                    decisions.push(TokenDecision {
+                        surface: following.literal.clone(),
+                        part_of_speech: pos,
+                        rule: None,
+                        eat_next: false,
+                        attach_to_previous: false,
+                        update_pos: false,
+                        eaten: true,
+                    });
+                }
                 let following = following.clone();
                 word.word.push_str(&following.literal);
                 word.extra.reading.push_str(&following.reading);
                 word.extra.transcription.push_str(&following.hatsuon);
+                word.is_oov |= following.is_oov;
                 if eat_lemma {
                     if let Some(ref mut lemma) = word.lemma {
                         lemma.push_str(&following.lemma)
                     }
+                    extend_lemma_reading(&mut word.extra.lemma_reading, &following);
                 }
                 word.tokens.push(following);
+            } else if eat_next {
+                reporter
+                    .report(&word.word, sentence, WarningReason::MissingFollowingToken)
+                    .map_err(GroupError)?;
             }
 
             words.push(word);
         }
-        previous = Some(token.clone());
+        if token.ends_sentence() {
+            sentence += 1;
+        }
+        previous = Some(token);
     }
 
     Ok(words)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, words_from};
+
+    const OKONAU: (&str, &str) = (
+        "行なっ",
+        "動詞,一般,*,*,五段-ワア行,連用形-促音便,オコナウ,行う,行なっ,オコナッ,行なう,オコナウ,和,*,*,*,*",
+    );
+    const SURU: (&str, &str) = (
+        "する",
+        "動詞,非自立可能,*,*,サ行変格,終止形-一般,スル,為る,する,スル,する,スル,和,*,*,*,*",
+    );
+
+    fn unidic(canonical_lemmas: bool) -> ParseConfig {
+        ParseConfig {
+            schema: Some(DictionarySchema::Unidic),
+            canonical_lemmas,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn writes_words_in_the_canonical_orthography() {
+        let words = words_from(&[OKONAU, SURU], &unidic(false));
+
+        assert_eq!(words[0].canonical_surface(), "行っ");
+        assert_eq!(words[0].lemma.as_deref(), Some("行なう"));
+        assert_eq!(words[1].canonical_surface(), "する");
+    }
+
+    #[test]
+    fn canonical_lemmas_are_opt_in() {
+        let words = words_from(&[OKONAU, SURU], &unidic(true));
+
+        assert_eq!(words[0].lemma.as_deref(), Some("行う"));
+        assert_eq!(words[1].lemma.as_deref(), Some("する"));
+    }
+
+    #[test]
+    fn keeps_the_surface_without_unidic() {
+        assert_eq!(words("食べました")[0].canonical_surface(), "食べました");
+    }
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use lindera::dictionary::{load_dictionary, Dictionary};
+use lindera::mode::Mode;
+use lindera::segmenter::Segmenter;
+
+use crate::error::TokenizeError;
+use crate::{Result, Tokenize, VibratoToken};
+
+/// Tokenizes with Lindera instead of vibrato, for apps that can't ship a vibrato dictionary.
+/// Lindera's IPADIC has the same features as vibrato's, so its tokens prepare the same way.
+///
+/// Use it like vibrato's tokenizer, e.g. with [`Ve::new`](crate::Ve::new) or a
+/// [`Pipeline`](crate::Pipeline).
+pub struct LinderaTokenizer {
+    segmenter: Segmenter,
+}
+
+impl LinderaTokenizer {
+    /// Loads a compiled Lindera IPADIC from the directory at `path`
+    pub fn new(path: &str) -> std::result::Result<Self, TokenizeError> {
+        let dictionary = load_dictionary(path).map_err(TokenizeError::Lindera)?;
+
+        Ok(Self::with_dictionary(dictionary))
+    }
+
+    /// Uses an already loaded Lindera dictionary
+    pub fn with_dictionary(dictionary: Dictionary) -> Self {
+        Self {
+            segmenter: Segmenter::new(Mode::Normal, dictionary, None),
+        }
+    }
+}
+
+impl Tokenize for LinderaTokenizer {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>> {
+        let tokens = self
+            .segmenter
+            .segment(Cow::Borrowed(text))
+            .map_err(TokenizeError::Lindera)?;
+
+        Ok(tokens
+            .into_iter()
+            .map(|mut token| VibratoToken {
+                surface: token.surface.to_string(),
+                feature: join_features(token.details_iter()),
+                is_oov: token.word_id.is_unknown(),
+                range: token.byte_start..token.byte_end,
+            })
+            .collect())
+    }
+}
+
+/// Joins the columns like a MeCab feature string, quoting the ones with commas in them
+fn join_features<'a>(columns: impl Iterator<Item = &'a str>) -> String {
+    let columns: Vec<Cow<str>> = columns
+        .map(|column| match column.contains(',') {
+            true => Cow::Owned(format!("\"{}\"", column)),
+            false => Cow::Borrowed(column),
+        })
+        .collect();
+
+    columns.join(",")
+}
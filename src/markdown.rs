@@ -0,0 +1,276 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_text, ParseConfig, Result, Tokenize};
+
+/// How `annotate_markdown` writes furigana
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum MarkdownRuby {
+    /// The ruby extension syntax `[漢字]{かんじ}` of markdown-it, Pandoc filters and others
+    #[default]
+    Braces,
+    /// Inline HTML `<ruby>漢字<rt>かんじ</rt></ruby>`, which any Markdown renderer passes through
+    Html,
+}
+
+/// Adds furigana to the kanji of a Markdown text, leaving front matter, code blocks,
+/// inline code, links, images and inline HTML as they are.
+pub fn annotate_markdown(
+    tokenizer: &(impl Tokenize + ?Sized),
+    markdown: &str,
+    config: &ParseConfig,
+    style: MarkdownRuby,
+) -> Result<String> {
+    let mut output = String::with_capacity(markdown.len() * 2);
+    // The fence of the code block we're in, like ``` or ~~~~
+    let mut fence: Option<&str> = None;
+    let mut front_matter = markdown
+        .lines()
+        .next()
+        .is_some_and(|line| line.trim_end() == "---");
+    let mut previous_blank = true;
+    let mut in_indented_code = false;
+
+    for (i, line) in markdown.split_inclusive('\n').enumerate() {
+        let trimmed = line.trim();
+        let blank = trimmed.is_empty();
+
+        let verbatim = if front_matter {
+            front_matter = i == 0 || trimmed != "---";
+            true
+        } else if let Some(open) = fence {
+            if trimmed.starts_with(open)
+                && trimmed
+                    .trim_start_matches(open.chars().next().unwrap_or('`'))
+                    .is_empty()
+            {
+                fence = None;
+            }
+            true
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            let c = trimmed.chars().next().unwrap_or('`');
+            let length = trimmed.len() - trimmed.trim_start_matches(c).len();
+            fence = Some(&trimmed[..length]);
+            true
+        } else {
+            let indented = line.starts_with("    ") || line.starts_with('\t');
+            in_indented_code = !blank && indented && (previous_blank || in_indented_code);
+            // Link reference definitions like [1]: https://example.com
+            let definition = trimmed.starts_with('[') && trimmed.contains("]:");
+            in_indented_code || definition
+        };
+        previous_blank = blank;
+
+        if verbatim || blank {
+            output.push_str(line);
+        } else {
+            annotate_line(tokenizer, line, config, style, &mut output)?;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Annotates the text of a line, skipping over code spans, links and HTML
+fn annotate_line(
+    tokenizer: &(impl Tokenize + ?Sized),
+    line: &str,
+    config: &ParseConfig,
+    style: MarkdownRuby,
+    output: &mut String,
+) -> Result<()> {
+    let mut text_start = 0;
+    let mut position = 0;
+
+    while let Some(c) = line[position..].chars().next() {
+        let skipped = match c {
+            '`' => code_span_end(&line[position..]),
+            '[' => link_end(&line[position..]),
+            '!' if line[position..].starts_with("![") => {
+                link_end(&line[position + 1..]).map(|end| end + 1)
+            }
+            '<' if line[position + 1..]
+                .starts_with(|c: char| c.is_ascii_alphabetic() || c == '/' || c == '!') =>
+            {
+                line[position..].find('>').map(|end| end + 1)
+            }
+            _ => None,
+        };
+
+        match skipped {
+            Some(length) => {
+                annotate_text(
+                    tokenizer,
+                    &line[text_start..position],
+                    config,
+                    style,
+                    output,
+                )?;
+                output.push_str(&line[position..position + length]);
+                position += length;
+                text_start = position;
+            }
+            None => position += c.len_utf8(),
+        }
+    }
+
+    annotate_text(tokenizer, &line[text_start..], config, style, output)
+}
+
+/// The length of the code span at the start of `text`, closed by as many backticks
+/// as it was opened with
+fn code_span_end(text: &str) -> Option<usize> {
+    let ticks = text.len() - text.trim_start_matches('`').len();
+    let fence = &text[..ticks];
+
+    let mut search = ticks;
+    while let Some(found) = text[search..].find(fence) {
+        let start = search + found;
+        let end = start + ticks;
+        // A longer run of backticks doesn't close the span
+        if !text[end..].starts_with('`') {
+            return Some(end);
+        }
+        search = end + text[end..].len() - text[end..].trim_start_matches('`').len();
+    }
+
+    Some(ticks)
+}
+
+/// The length of the link at the start of `text`, like [text](url) or [text][ref]
+fn link_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut label_end = None;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    label_end = Some(i + 1);
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let label_end = label_end?;
+    let (open, close) = match text[label_end..].chars().next()? {
+        '(' => ('(', ')'),
+        '[' => ('[', ']'),
+        _ => return None,
+    };
+
+    let rest = &text[label_end..];
+    let mut depth = 0;
+    for (i, c) in rest.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(label_end + i + 1);
+            }
+        }
+    }
+
+    None
+}
+
+fn annotate_text(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+    style: MarkdownRuby,
+    output: &mut String,
+) -> Result<()> {
+    if text.trim().is_empty() {
+        output.push_str(text);
+        return Ok(());
+    }
+
+    let mut position = 0;
+
+    for sentence in parse_text(tokenizer, text, config)? {
+        for word in &sentence.words {
+            let range = word.range();
+            output.push_str(&text[position..range.start]);
+            position = range.end;
+
+            if text[range.clone()] != word.word {
+                output.push_str(&text[range]);
+                continue;
+            }
+
+            for segment in word.furigana_segments_with(&config.reading_overrides) {
+                match (segment.reading, style) {
+                    (Some(reading), MarkdownRuby::Braces) => {
+                        output.push_str(&format!("[{}]{{{}}}", segment.text, reading));
+                    }
+                    (Some(reading), MarkdownRuby::Html) => {
+                        output.push_str(&format!(
+                            "<ruby>{}<rt>{}</rt></ruby>",
+                            segment.text, reading
+                        ));
+                    }
+                    (None, _) => output.push_str(&segment.text),
+                }
+            }
+        }
+    }
+    output.push_str(&text[position..]);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+
+    fn annotate(markdown: &str, style: MarkdownRuby) -> String {
+        annotate_markdown(&Lexicon, markdown, &ParseConfig::default(), style).unwrap()
+    }
+
+    #[test]
+    fn adds_furigana_in_either_style() {
+        assert_eq!(
+            annotate("# 猫が食べました\n", MarkdownRuby::Braces),
+            "# [猫]{ねこ}が[食]{た}べました\n"
+        );
+        assert_eq!(
+            annotate("**嘘**", MarkdownRuby::Html),
+            "**<ruby>嘘<rt>うそ</rt></ruby>**"
+        );
+    }
+
+    #[test]
+    fn leaves_code_and_front_matter_alone() {
+        let markdown = "---\ntitle: 猫\n---\n```\n猫\n```\n\n    猫\n\n`猫` 猫\n";
+
+        assert_eq!(
+            annotate(markdown, MarkdownRuby::Braces),
+            "---\ntitle: 猫\n---\n```\n猫\n```\n\n    猫\n\n`猫` [猫]{ねこ}\n"
+        );
+    }
+
+    #[test]
+    fn leaves_links_and_html_alone() {
+        let markdown = "[猫](猫.md) ![嘘][1] <span title=\"猫\">猫</span>\n[1]: 嘘.png\n";
+
+        assert_eq!(
+            annotate(markdown, MarkdownRuby::Braces),
+            "[猫](猫.md) ![嘘][1] <span title=\"猫\">[猫]{ねこ}</span>\n[1]: 嘘.png\n"
+        );
+    }
+
+    #[test]
+    fn closes_code_spans_with_as_many_backticks() {
+        assert_eq!(code_span_end("``a`b`` c"), Some(7));
+        assert_eq!(code_span_end("`a``` b` c"), Some(8));
+    }
+}
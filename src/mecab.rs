@@ -0,0 +1,137 @@
+use std::ffi::OsStr;
+use std::io::{self, BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Mutex;
+
+use crate::error::TokenizeError;
+use crate::{Result, Tokenize, VibratoToken};
+
+/// Surface, features and whether the node is an unknown word (`%s` is 1 for those)
+const NODE_FORMAT: &str = "--node-format=%m\\t%H\\t%s\\n";
+const UNK_FORMAT: &str = "--unk-format=%m\\t%H\\t%s\\n";
+const EOS_FORMAT: &str = "--eos-format=EOS\\n";
+/// MeCab cuts off lines longer than its input buffer, 8 KiB by default
+const INPUT_BUFFER: &str = "--input-buffer-size=1048576";
+
+/// Tokenizes with a MeCab installed on the system, for apps that don't want to build a
+/// vibrato dictionary. With IPADIC its features are the same as vibrato's.
+///
+/// Runs `mecab` as a child process that's kept around for all calls, so it's only
+/// started once. Use it like vibrato's tokenizer, e.g. with [`Ve::new`](crate::Ve::new) or a
+/// [`Pipeline`](crate::Pipeline).
+pub struct MecabTokenizer {
+    process: Mutex<Process>,
+}
+
+struct Process {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl MecabTokenizer {
+    /// Starts `mecab` from the `PATH` with its default dictionary
+    pub fn new() -> std::result::Result<Self, TokenizeError> {
+        Self::with_args("mecab", &[] as &[&str])
+    }
+
+    /// Starts `program` with extra arguments, e.g. `["-d", "/usr/lib/mecab/dic/ipadic"]`
+    /// for another dictionary
+    pub fn with_args<S: AsRef<OsStr>>(
+        program: impl AsRef<OsStr>,
+        args: &[S],
+    ) -> std::result::Result<Self, TokenizeError> {
+        let mut child = Command::new(program)
+            .args(args)
+            .args([NODE_FORMAT, UNK_FORMAT, EOS_FORMAT, INPUT_BUFFER])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(TokenizeError::Mecab)?;
+
+        let process = Process {
+            stdin: child.stdin.take().expect("stdin is piped"),
+            stdout: BufReader::new(child.stdout.take().expect("stdout is piped")),
+            child,
+        };
+
+        Ok(Self {
+            process: Mutex::new(process),
+        })
+    }
+}
+
+impl Tokenize for MecabTokenizer {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>> {
+        let mut process = self.process.lock().unwrap();
+        let mut tokens = Vec::new();
+        let mut line_start = 0;
+
+        // MeCab parses line by line, ending each with an EOS
+        for line in text.split('\n') {
+            process
+                .tokenize_line(line, line_start, &mut tokens)
+                .map_err(TokenizeError::Mecab)?;
+            line_start += line.len() + 1;
+        }
+
+        Ok(tokens)
+    }
+}
+
+impl Process {
+    fn tokenize_line(
+        &mut self,
+        line: &str,
+        offset: usize,
+        tokens: &mut Vec<VibratoToken>,
+    ) -> io::Result<()> {
+        writeln!(self.stdin, "{}", line)?;
+        self.stdin.flush()?;
+
+        // MeCab skips whitespace, so each surface is searched for after the previous one
+        let mut position = 0;
+        let mut output = String::new();
+        loop {
+            output.clear();
+            if self.stdout.read_line(&mut output)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "mecab exited"));
+            }
+
+            let node = output.trim_end_matches(['\r', '\n']);
+            if node == "EOS" {
+                return Ok(());
+            }
+
+            let mut columns = node.splitn(3, '\t');
+            let (Some(surface), Some(feature), status) =
+                (columns.next(), columns.next(), columns.next())
+            else {
+                continue;
+            };
+
+            let range = match line[position..].find(surface) {
+                Some(found) => {
+                    let start = position + found;
+                    position = start + surface.len();
+                    start..position
+                }
+                None => position..position,
+            };
+
+            tokens.push(VibratoToken {
+                surface: surface.into(),
+                feature: feature.into(),
+                is_oov: status == Some("1"),
+                range: offset + range.start..offset + range.end,
+            });
+        }
+    }
+}
+
+impl Drop for Process {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::{RawTags, VibratoToken, Word};
+
+/// Tokens the way the `mecab` command prints them, a `surface\tfeature` line each and
+/// an `EOS` line at the end, for scripts written against MeCab's output
+pub fn to_mecab(tokens: &[VibratoToken]) -> String {
+    let mut output = String::new();
+    for token in tokens {
+        let _ = writeln!(output, "{}\t{}", token.surface, token.feature);
+    }
+    output.push_str("EOS\n");
+    output
+}
+
+/// Words the way the `mecab` command prints tokens, so words grouped by ve can be fed to
+/// scripts written against MeCab's output. The features have IPADIC's nine columns: the tags
+/// of the word's first token, then the lemma, reading and pronunciation of the whole word.
+///
+/// For 食べられなかった that's
+/// `動詞,自立,*,*,一段,未然形,食べる,タベラレナカッタ,タベラレナカッタ`.
+pub fn words_to_mecab(words: &[Word]) -> String {
+    let mut output = String::new();
+    for word in words {
+        let tags = word.raw_tags().cloned().unwrap_or_else(|| RawTags {
+            pos1: "*".into(),
+            pos2: "*".into(),
+            pos3: "*".into(),
+            pos4: "*".into(),
+            inflection_type: "*".into(),
+            inflection_form: "*".into(),
+        });
+
+        let _ = writeln!(
+            output,
+            "{}\t{},{},{},{},{},{},{},{},{}",
+            word.word,
+            tags.pos1,
+            tags.pos2,
+            tags.pos3,
+            tags.pos4,
+            tags.inflection_type,
+            tags.inflection_form,
+            column(word.lemma.as_deref().unwrap_or_default()),
+            column(&word.extra.reading),
+            column(&word.extra.transcription),
+        );
+    }
+    output.push_str("EOS\n");
+    output
+}
+
+/// `*` for empty columns like MeCab has, and quoted if there's a comma in it
+fn column(value: &str) -> Cow<'_, str> {
+    match value {
+        "" => "*".into(),
+        value if value.contains(',') => format!("\"{}\"", value).into(),
+        value => value.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, Lexicon};
+    use crate::Tokenize;
+
+    #[test]
+    fn writes_tokens_like_mecab() {
+        let tokens = Lexicon.tokenize("猫が").unwrap();
+
+        assert_eq!(
+            to_mecab(&tokens),
+            "猫\t名詞,一般,*,*,*,*,猫,ネコ,ネコ\n\
+             が\t助詞,格助詞,一般,*,*,*,が,ガ,ガ\nEOS\n"
+        );
+        assert_eq!(to_mecab(&[]), "EOS\n");
+    }
+
+    #[test]
+    fn writes_grouped_words_with_the_tags_of_their_first_token() {
+        assert_eq!(
+            words_to_mecab(&words("食べました")),
+            "食べました\t動詞,自立,*,*,一段,連用形,食べる,タベマシタ,タベマシタ\nEOS\n"
+        );
+    }
+
+    #[test]
+    fn writes_empty_columns_as_asterisks() {
+        assert_eq!(column(""), "*");
+        assert_eq!(column("1,0"), "\"1,0\"");
+        assert_eq!(column("猫"), "猫");
+    }
+}
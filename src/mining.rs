@@ -0,0 +1,86 @@
+use crate::known::lemma;
+use crate::{KnownWords, PartOfSpeech, Sentence, Word};
+
+/// A sentence with exactly one unknown content word
+#[derive(Clone, Copy, Debug)]
+pub struct MinedSentence<'a> {
+    pub sentence: &'a Sentence,
+    /// Index of the unknown word in `sentence.words`
+    pub unknown: usize,
+}
+
+impl<'a> MinedSentence<'a> {
+    pub fn unknown_word(&self) -> &'a Word {
+        &self.sentence.words[self.unknown]
+    }
+}
+
+/// Words that carry meaning and count towards what a learner needs to know.
+/// Particles, auxiliaries, names, numbers and symbols don't.
+pub(crate) fn is_content_word(word: &Word) -> bool {
+    matches!(
+        word.part_of_speech,
+        PartOfSpeech::Noun | PartOfSpeech::Verb | PartOfSpeech::Adjective | PartOfSpeech::Adverb
+    )
+}
+
+/// Finds the sentences with exactly one content word that isn't in `known`
+/// (the i+1 sentences), so they can be learned from in one step. A word that occurs
+/// more than once in a sentence counts once.
+pub fn mine_sentences<'a>(sentences: &'a [Sentence], known: &KnownWords) -> Vec<MinedSentence<'a>> {
+    sentences
+        .iter()
+        .filter_map(|sentence| {
+            let mut unknown = sentence
+                .words
+                .iter()
+                .enumerate()
+                .filter(|(_, word)| is_content_word(word) && !known.contains(word));
+
+            let (index, word) = unknown.next()?;
+            if unknown.any(|(_, other)| lemma(other) != lemma(word)) {
+                return None;
+            }
+
+            Some(MinedSentence {
+                sentence,
+                unknown: index,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+    use crate::{parse_text, ParseConfig};
+
+    fn mined(text: &str, known: &[&str]) -> Vec<String> {
+        let sentences = parse_text(&Lexicon, text, &ParseConfig::default()).unwrap();
+        let known: KnownWords = known.iter().collect();
+
+        mine_sentences(&sentences, &known)
+            .iter()
+            .map(|mined| format!("{} {}", mined.sentence.text, mined.unknown_word().word))
+            .collect()
+    }
+
+    #[test]
+    fn finds_sentences_with_one_unknown_content_word() {
+        assert_eq!(
+            mined("猫が嘘。猫を食べました。嘘を食べました。", &["猫"]),
+            ["猫が嘘。 嘘", "猫を食べました。 食べました"]
+        );
+    }
+
+    #[test]
+    fn counts_repeated_words_once() {
+        assert_eq!(mined("嘘が嘘。", &[]), ["嘘が嘘。 嘘"]);
+    }
+
+    #[test]
+    fn skips_sentences_without_unknown_words() {
+        assert!(mined("猫が。が。", &["猫"]).is_empty());
+    }
+}
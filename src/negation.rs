@@ -0,0 +1,186 @@
+use crate::{PartOfSpeech, PreparedToken, Word, NN, POS};
+
+/// Particles that can stand between a predicate and the ない negating it, like in 高くはない
+const TOPIC_PARTICLES: [&str; 3] = ["は", "も", "さえ"];
+
+/// Sets `WordExtra::negated` on the predicates that ない, ぬ or ません negate.
+///
+/// Within a word like 食べなかった or 行かず that's the word itself. A separate ない or
+/// ありません negates the predicate before it, so 静か in 静かではない, 学生 in 学生じゃない
+/// and 高く in 高くありません. An even number of negations cancels out, as in おいしくなくない.
+pub(crate) fn mark_negation(words: &mut [Word]) {
+    for i in 0..words.len() {
+        let count = words[i]
+            .tokens
+            .iter()
+            .filter(|token| is_negative(token))
+            .count();
+        if count == 0 {
+            continue;
+        }
+
+        // A negator can negate another one, like the ない of 高くなくない, which passes
+        // it on to the predicate that one negates
+        let mut target = i;
+        while is_negator(&words[target]) {
+            match negated_predicate(words, target) {
+                Some(predicate) => target = predicate,
+                None => break,
+            }
+        }
+        words[target].extra.negated ^= count % 2 == 1;
+    }
+}
+
+/// The negative auxiliaries ない and ぬ (ず, ん) as well as the adjective ない
+fn is_negative(token: &PreparedToken) -> bool {
+    match token.pos {
+        POS::JoDoushi => {
+            matches!(token.inflection_type, POS::TokushuNai | POS::TokushuNu)
+                || (token.inflection_type == POS::Fuhenkagata && token.lemma == NN)
+        }
+        POS::Keiyoushi => matches!(token.lemma.as_str(), "ない" | "無い"),
+        _ => false,
+    }
+}
+
+/// Words that only negate what comes before them: ない, ありません or じゃない
+fn is_negator(word: &Word) -> bool {
+    let Some(first) = word.tokens.first() else {
+        return false;
+    };
+    match first.pos {
+        POS::Keiyoushi | POS::JoDoushi if is_negative(first) => true,
+        POS::Doushi => matches!(first.lemma.as_str(), "ある" | "有る"),
+        POS::Joshi | POS::JoDoushi => is_copula(&first.literal),
+        _ => false,
+    }
+}
+
+fn is_copula(surface: &str) -> bool {
+    matches!(surface, "で" | "じゃ" | "じゃあ" | "では")
+}
+
+/// The predicate the negator at `index` applies to, skipping particles like は and the
+/// で of ではない. Nouns need the で, adjectives need to be in the く form.
+fn negated_predicate(words: &[Word], index: usize) -> Option<usize> {
+    let mut copula = words[index]
+        .tokens
+        .first()
+        .is_some_and(|first| first.pos != POS::Keiyoushi && is_copula(&first.literal));
+
+    let mut j = index;
+    while j > 0 {
+        j -= 1;
+        let word = &words[j];
+
+        if word.part_of_speech == PartOfSpeech::Postposition
+            && TOPIC_PARTICLES.contains(&word.word.as_str())
+        {
+            continue;
+        }
+        if !copula && is_copula(&word.word) {
+            copula = true;
+            continue;
+        }
+
+        return match word.part_of_speech {
+            PartOfSpeech::Adjective if !copula && word.word.ends_with('く') => Some(j),
+            PartOfSpeech::Noun
+            | PartOfSpeech::ProperNoun
+            | PartOfSpeech::Pronoun
+            | PartOfSpeech::Adjective
+                if copula =>
+            {
+                Some(j)
+            }
+            _ => None,
+        };
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    const NAI: (&str, &str) = (
+        "ない",
+        "形容詞,自立,*,*,形容詞・アウオ段,基本形,ない,ナイ,ナイ",
+    );
+
+    const TAKAKU: (&str, &str) = (
+        "高く",
+        "形容詞,自立,*,*,形容詞・アウオ段,連用テ接続,高い,タカク,タカク",
+    );
+
+    fn negated(tokens: &[(&str, &str)]) -> Vec<(String, bool)> {
+        words_from(tokens, &ParseConfig::default())
+            .into_iter()
+            .map(|word| (word.word, word.extra.negated))
+            .collect()
+    }
+
+    #[test]
+    fn marks_negated_words() {
+        assert_eq!(
+            negated(&[
+                ("食べ", "動詞,自立,*,*,一段,未然形,食べる,タベ,タベ"),
+                (
+                    "なかっ",
+                    "助動詞,*,*,*,特殊・ナイ,連用タ接続,ない,ナカッ,ナカッ"
+                ),
+                ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+            ]),
+            [("食べなかった".into(), true)]
+        );
+    }
+
+    #[test]
+    fn marks_the_predicate_a_separate_negation_applies_to() {
+        assert_eq!(
+            negated(&[
+                ("静か", "名詞,形容動詞語幹,*,*,*,*,静か,シズカ,シズカ"),
+                ("で", "助詞,格助詞,一般,*,*,*,で,デ,デ"),
+                ("は", "助詞,係助詞,*,*,*,*,は,ハ,ワ"),
+                NAI,
+            ]),
+            [
+                ("静か".into(), true),
+                ("で".into(), false),
+                ("は".into(), false),
+                ("ない".into(), false)
+            ]
+        );
+        assert_eq!(
+            negated(&[
+                TAKAKU,
+                ("あり", "動詞,自立,*,*,五段・ラ行,連用形,ある,アリ,アリ"),
+                ("ませ", "助動詞,*,*,*,特殊・マス,未然形,ます,マセ,マセ"),
+                ("ん", "助動詞,*,*,*,不変化型,基本形,ん,ン,ン"),
+            ]),
+            [("高く".into(), true), ("ありません".into(), false)]
+        );
+    }
+
+    #[test]
+    fn cancels_out_double_negations() {
+        assert_eq!(
+            negated(&[
+                TAKAKU,
+                (
+                    "なく",
+                    "形容詞,自立,*,*,形容詞・アウオ段,連用テ接続,ない,ナク,ナク"
+                ),
+                NAI,
+            ]),
+            [
+                ("高く".into(), false),
+                ("なく".into(), false),
+                ("ない".into(), false)
+            ]
+        );
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PartOfSpeech, PreparedToken, Word, POS};
+
+/// What a particle or particle-like noun does in its clause
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ParticleRole {
+    /// The の or こと that turns the predicate before it into a noun phrase,
+    /// like in 食べるのが好き or 泳ぐことができる
+    Nominalizer,
+}
+
+/// The dependent nouns の and こと, though not the の of explanatory のだ
+pub(crate) fn is_nominalizer(token: &PreparedToken, following: Option<&PreparedToken>) -> bool {
+    let explanatory = following.is_some_and(|following| {
+        token.literal == "の"
+            && [POS::TokushuDa, POS::TokushuDesu].contains(&following.inflection_type)
+    });
+
+    token.pos == POS::Meishi
+        && token.pos2 == POS::Hijiritsu
+        && matches!(token.literal.as_str(), "の" | "こと" | "事")
+        && !explanatory
+}
+
+pub(crate) fn is_predicate(word: &Word) -> bool {
+    matches!(
+        word.part_of_speech,
+        PartOfSpeech::Verb | PartOfSpeech::Adjective
+    )
+}
+
+/// Sets `WordExtra::particle_role` on nominalizers after a verb or adjective, and on the
+/// words `Rule::AttachNominalizer` grouped them into
+pub(crate) fn mark_nominalizers(words: &mut [Word]) {
+    for i in 0..words.len() {
+        let following = words.get(i + 1).and_then(|word| word.tokens.first());
+        let tokens = &words[i].tokens;
+        let Some(last) = tokens.last() else {
+            continue;
+        };
+
+        let nominalizes = is_nominalizer(last, following)
+            && if let [.., predicate, _] = &tokens[..] {
+                [POS::Doushi, POS::Keiyoushi, POS::JoDoushi].contains(&predicate.pos)
+            } else {
+                i > 0 && is_predicate(&words[i - 1])
+            };
+        if nominalizes {
+            words[i].extra.particle_role = Some(ParticleRole::Nominalizer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rule;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    const TABERU: (&str, &str) = ("食べる", "動詞,自立,*,*,一段,基本形,食べる,タベル,タベル");
+    const NO: (&str, &str) = ("の", "名詞,非自立,一般,*,*,*,の,ノ,ノ");
+    const GA: (&str, &str) = ("が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ");
+
+    fn roles(tokens: &[(&str, &str)], config: &ParseConfig) -> Vec<(String, Option<ParticleRole>)> {
+        words_from(tokens, config)
+            .into_iter()
+            .map(|word| (word.word, word.extra.particle_role))
+            .collect()
+    }
+
+    #[test]
+    fn marks_nominalizers_after_predicates() {
+        assert_eq!(
+            roles(&[TABERU, NO, GA], &ParseConfig::default()),
+            [
+                ("食べる".into(), None),
+                ("の".into(), Some(ParticleRole::Nominalizer)),
+                ("が".into(), None)
+            ]
+        );
+        assert_eq!(
+            roles(
+                &[TABERU, ("こと", "名詞,非自立,一般,*,*,*,こと,コト,コト")],
+                &ParseConfig::default()
+            )[1],
+            ("こと".into(), Some(ParticleRole::Nominalizer))
+        );
+    }
+
+    #[test]
+    fn leaves_explanatory_no_and_other_nouns_alone() {
+        assert_eq!(
+            roles(
+                &[TABERU, NO, ("だ", "助動詞,*,*,*,特殊・ダ,基本形,だ,ダ,ダ")],
+                &ParseConfig::default()
+            )
+            .iter()
+            .filter_map(|(_, role)| *role)
+            .count(),
+            0
+        );
+        assert_eq!(
+            roles(
+                &[("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"), NO, GA],
+                &ParseConfig::default()
+            )
+            .iter()
+            .filter_map(|(_, role)| *role)
+            .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn marks_nominalizers_grouped_into_the_predicate() {
+        let mut config = ParseConfig::default();
+        config.rules.enable(Rule::AttachNominalizer);
+
+        assert_eq!(
+            roles(&[TABERU, NO, GA], &config),
+            [
+                ("食べるの".into(), Some(ParticleRole::Nominalizer)),
+                ("が".into(), None)
+            ]
+        );
+    }
+}
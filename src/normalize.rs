@@ -0,0 +1,228 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::document::{BRACKETS, ELLIPSES, TERMINATORS};
+
+const HALFWIDTH_KATAKANA: &str = "｡｢｣､･ｦｧｨｩｪｫｬｭｮｯｰｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜﾝ";
+const FULLWIDTH_KATAKANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// Normalization applied to the input text before it's handed to the tokenizer
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct NormalizeOptions {
+    /// Fold full-width ASCII into half-width and half-width katakana into full-width,
+    /// the forms IPADIC has entries for
+    pub width: bool,
+    /// Trim surrounding whitespace off each line
+    pub trim: bool,
+    /// Fold full-width acronyms like ＮＨＫ into half-width, leaving other full-width text alone
+    pub acronyms: bool,
+    /// Join lines that were wrapped in the middle of a sentence, see [`join_soft_line_breaks`]
+    pub join_lines: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        Self {
+            width: false,
+            trim: true,
+            acronyms: false,
+            join_lines: false,
+        }
+    }
+}
+
+impl NormalizeOptions {
+    pub fn apply(&self, text: &str) -> String {
+        let joined;
+        let text = if self.join_lines {
+            joined = join_soft_line_breaks(text);
+            &joined
+        } else {
+            text
+        };
+        let text = if self.trim { text.trim() } else { text };
+
+        if self.width {
+            normalize_width(text)
+        } else if self.acronyms {
+            normalize_acronyms(text)
+        } else {
+            text.into()
+        }
+    }
+}
+
+pub fn normalize_width(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '！'..='～' => result.push(char::from_u32(c as u32 - 0xFEE0).unwrap_or(c)),
+            '　' => result.push(' '),
+            // voiced and semi-voiced marks combine with the preceding kana
+            'ﾞ' | 'ﾟ' => match result.chars().last().and_then(|last| combine_mark(last, c)) {
+                Some(combined) => {
+                    result.pop();
+                    result.push(combined);
+                }
+                None => result.push(if c == 'ﾞ' { '゛' } else { '゜' }),
+            },
+            _ => match HALFWIDTH_KATAKANA.chars().position(|h| h == c) {
+                Some(i) => result.extend(FULLWIDTH_KATAKANA.chars().nth(i)),
+                None => result.push(c),
+            },
+        }
+    }
+
+    result
+}
+
+/// Joins lines that were hard-wrapped in the middle of a sentence, as in Aozora Bunko
+/// texts or OCR output, so words aren't split at the line break.
+///
+/// A line break is kept after sentence-ending punctuation and closing brackets, before
+/// lines starting with an opening bracket, a dash or an indent (a new paragraph),
+/// and around blank lines. Breaks between Latin letters or digits become a space.
+pub fn join_soft_line_breaks(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut result = String::with_capacity(text.len());
+    let mut joined = false;
+
+    for (i, line) in lines.iter().enumerate() {
+        // Spaces in front of a continued line are dropped, they're left over from the wrapping
+        let line = if joined {
+            line.trim_start_matches(' ')
+        } else {
+            line
+        };
+
+        let Some(next) = lines.get(i + 1) else {
+            result.push_str(line);
+            if text.ends_with('\n') {
+                result.push('\n');
+            }
+            break;
+        };
+
+        let end = line.trim_end();
+        let next = next.trim_start_matches(' ');
+
+        let ends_sentence = end.ends_with(|c: char| {
+            TERMINATORS.contains(c)
+                || ELLIPSES.contains(c)
+                || BRACKETS.iter().any(|(_, close)| *close == c)
+        });
+        let starts_paragraph = next.starts_with(|c: char| {
+            c == '　' || c == '―' || c == '—' || BRACKETS.iter().any(|(open, _)| *open == c)
+        });
+
+        joined = !(end.is_empty() || next.trim().is_empty() || ends_sentence || starts_paragraph);
+
+        if !joined {
+            result.push_str(line);
+            result.push('\n');
+            continue;
+        }
+
+        result.push_str(end);
+        let latin = |c: Option<char>| c.is_some_and(|c| c.is_ascii_alphanumeric());
+        if latin(end.chars().last()) && latin(next.chars().next()) {
+            result.push(' ');
+        }
+    }
+
+    result
+}
+
+/// Folds runs of at least two full-width capital letters into half-width
+fn normalize_acronyms(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut run = String::new();
+
+    for c in text.chars().chain(std::iter::once('\0')) {
+        if ('Ａ'..='Ｚ').contains(&c) {
+            run.push(c);
+            continue;
+        }
+
+        if run.chars().count() >= 2 {
+            result.push_str(&normalize_width(&run));
+        } else {
+            result.push_str(&run);
+        }
+        run.clear();
+
+        if c != '\0' {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+fn combine_mark(kana: char, mark: char) -> Option<char> {
+    match mark {
+        'ﾞ' if kana == 'ウ' => Some('ヴ'),
+        'ﾞ' if "カキクケコサシスセソタチツテトハヒフヘホ".contains(kana) => {
+            char::from_u32(kana as u32 + 1)
+        }
+        'ﾟ' if "ハヒフヘホ".contains(kana) => char::from_u32(kana as u32 + 2),
+        _ => None,
+    }
+}
+
+/// Whether `text` is made up of full-width katakana only, including the long vowel mark
+pub(crate) fn is_katakana(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| matches!(c, 'ァ'..='ヺ' | 'ー' | 'ヽ' | 'ヾ'))
+}
+
+/// Whether `text` is made up of capital Latin letters only, in either width
+pub(crate) fn is_acronym(text: &str) -> bool {
+    !text.is_empty()
+        && text
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('Ａ'..='Ｚ').contains(&c))
+}
+
+const LETTER_NAMES: [&str; 26] = [
+    "エイ",
+    "ビー",
+    "シー",
+    "ディー",
+    "イー",
+    "エフ",
+    "ジー",
+    "エイチ",
+    "アイ",
+    "ジェイ",
+    "ケイ",
+    "エル",
+    "エム",
+    "エヌ",
+    "オー",
+    "ピー",
+    "キュー",
+    "アール",
+    "エス",
+    "ティー",
+    "ユー",
+    "ブイ",
+    "ダブリュー",
+    "エックス",
+    "ワイ",
+    "ゼット",
+];
+
+/// Reads an acronym letter by letter, like NHK → エヌエイチケイ
+pub(crate) fn spell_out(acronym: &str) -> String {
+    normalize_width(acronym)
+        .chars()
+        .filter(char::is_ascii_uppercase)
+        .map(|c| LETTER_NAMES[(c as u8 - b'A') as usize])
+        .collect()
+}
@@ -0,0 +1,194 @@
+use crate::furigana::is_kanji;
+use crate::normalize::is_katakana;
+use crate::{PartOfSpeech, PreparedToken, Word, WordOrigin, POS};
+
+impl Word {
+    /// Whether the word is native (和語), Sino-Japanese (漢語), a loanword (外来語) or
+    /// mixes them (混種語), e.g. for the share of kango in a text.
+    ///
+    /// With UniDic this is the dictionary's 語種 of each token. Other dictionaries don't
+    /// have one, so it's guessed from the script: katakana and Latin words are loanwords,
+    /// kana and kanji with okurigana native, and kanji read like on'yomi, e.g. 学校 as
+    /// ガッ・コウ but not 子供 as コドモ, Sino-Japanese.
+    ///
+    /// Particles, auxiliaries and the する of 勉強する don't count unless the word is
+    /// nothing else, so 勉強しました is Sino-Japanese. None for numbers.
+    pub fn origin(&self) -> Option<WordOrigin> {
+        if self.part_of_speech == PartOfSpeech::ProperNoun {
+            return Some(WordOrigin::Proper);
+        }
+
+        let stem: Vec<&PreparedToken> = self
+            .tokens
+            .iter()
+            .enumerate()
+            .filter(|(i, token)| !is_ending(token, *i > 0))
+            .map(|(_, token)| token)
+            .collect();
+        let mut origins = if stem.is_empty() {
+            self.tokens.iter().collect()
+        } else {
+            stem
+        }
+        .into_iter()
+        .filter_map(token_origin);
+        let first = origins.next()?;
+
+        // A name with a suffix like 田中さん is still a name
+        if first == WordOrigin::Proper {
+            return Some(first);
+        }
+        Some(origins.fold(first, |origin, next| {
+            if origin == next || next == WordOrigin::Symbol {
+                origin
+            } else if origin == WordOrigin::Symbol {
+                next
+            } else {
+                WordOrigin::Mixed
+            }
+        }))
+    }
+}
+
+/// Particles, auxiliaries and dependent verbs, as well as verbs after a noun like the
+/// する of 勉強する, which only inflect the word
+fn is_ending(token: &PreparedToken, after_first: bool) -> bool {
+    match token.pos {
+        POS::Joshi | POS::JoDoushi => true,
+        POS::Doushi | POS::Keiyoushi => {
+            token.pos2 == POS::Hijiritsu
+                || (after_first && matches!(token.lemma.as_str(), "する" | "できる" | "出来る"))
+        }
+        _ => false,
+    }
+}
+
+fn token_origin(token: &PreparedToken) -> Option<WordOrigin> {
+    if token.pos2 == POS::Kazu {
+        return None;
+    }
+    if let Some(origin) = token.unidic().and_then(|unidic| unidic.origin) {
+        return Some(origin);
+    }
+    if token.pos2 == POS::KoyuuMeishi {
+        return Some(WordOrigin::Proper);
+    }
+    if token.pos == POS::Kigou {
+        return Some(WordOrigin::Symbol);
+    }
+
+    let text = if token.lemma.is_empty() || token.lemma == "*" {
+        &token.literal
+    } else {
+        &token.lemma
+    };
+    Some(script_origin(text, &token.reading))
+}
+
+/// Guesses the origin of a single token from its script, and for kanji from `reading`
+fn script_origin(text: &str, reading: &str) -> WordOrigin {
+    if is_katakana(text) || text.chars().any(|c| c.is_ascii_alphabetic()) {
+        return WordOrigin::Foreign;
+    }
+
+    let kanji = text.chars().filter(|&c| is_kanji(c)).count();
+    let katakana = text.chars().any(|c| matches!(c, 'ァ'..='ヺ'));
+    match (kanji, katakana) {
+        (0, false) if text.chars().all(|c| !c.is_alphanumeric()) => WordOrigin::Symbol,
+        (_, true) => WordOrigin::Mixed,
+        (kanji, false) if kanji == text.chars().count() && is_on_reading(reading, kanji) => {
+            WordOrigin::SinoJapanese
+        }
+        _ => WordOrigin::Native,
+    }
+}
+
+/// Whether `reading` splits into an on'yomi for each of `kanji` kanji: one mora, or two
+/// ending in ン, ウ, イ, キ, ク, チ, ツ or ッ. A single kanji needs two, since one-mora
+/// kun'yomi like 手 or 目 are common.
+fn is_on_reading(reading: &str, kanji: usize) -> bool {
+    // Small kana belong to the mora before them, as in キョウ
+    let mut morae: Vec<char> = Vec::new();
+    for c in reading.chars() {
+        if !matches!(c, 'ャ' | 'ュ' | 'ョ' | 'ァ' | 'ィ' | 'ゥ' | 'ェ' | 'ォ') || morae.is_empty()
+        {
+            morae.push(c);
+        }
+    }
+    if morae.is_empty() || morae.iter().any(|&c| !matches!(c, 'ァ'..='ヺ' | 'ー')) {
+        return false;
+    }
+    if kanji == 1 {
+        return morae.len() == 2 && is_on_ending(morae[1]);
+    }
+
+    // Whether `morae` split into `left` readings
+    fn splits(morae: &[char], left: usize) -> bool {
+        match (morae, left) {
+            ([], 0) => true,
+            ([], _) | (_, 0) => false,
+            ([_], _) => left == 1,
+            ([_, second, rest @ ..], _) => {
+                splits(&morae[1..], left - 1) || (is_on_ending(*second) && splits(rest, left - 1))
+            }
+        }
+    }
+    splits(&morae, kanji)
+}
+
+fn is_on_ending(mora: char) -> bool {
+    matches!(mora, 'ン' | 'ウ' | 'イ' | 'キ' | 'ク' | 'チ' | 'ツ' | 'ッ')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, words_from};
+    use crate::{DictionarySchema, ParseConfig};
+
+    #[test]
+    fn guesses_the_origin_from_the_script_and_reading() {
+        let origins: Vec<_> = words("勉強食べました東京猫。")
+            .iter()
+            .map(Word::origin)
+            .collect();
+
+        assert_eq!(
+            origins,
+            [
+                Some(WordOrigin::SinoJapanese),
+                Some(WordOrigin::Native),
+                Some(WordOrigin::Proper),
+                Some(WordOrigin::Native),
+                Some(WordOrigin::Symbol)
+            ]
+        );
+        assert_eq!(script_origin("テレビ", "テレビ"), WordOrigin::Foreign);
+        assert_eq!(script_origin("歯ブラシ", "ハブラシ"), WordOrigin::Mixed);
+    }
+
+    #[test]
+    fn tells_on_readings_from_kun_readings() {
+        assert!(is_on_reading("ガッコウ", 2));
+        assert!(is_on_reading("キョウ", 1));
+        assert!(!is_on_reading("コドモ", 2));
+        assert!(!is_on_reading("テ", 1));
+    }
+
+    #[test]
+    fn takes_the_origin_from_unidic() {
+        let config = ParseConfig {
+            schema: Some(DictionarySchema::Unidic),
+            ..Default::default()
+        };
+        let words = words_from(
+            &[(
+                "猫",
+                "名詞,普通名詞,一般,*,*,*,ネコ,猫,猫,ネコ,猫,ネコ,漢,*,*,*,*",
+            )],
+            &config,
+        );
+
+        assert_eq!(words[0].origin(), Some(WordOrigin::SinoJapanese));
+    }
+}
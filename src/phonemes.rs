@@ -0,0 +1,205 @@
+use std::fmt;
+use std::ops::Range;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::to_katakana;
+use crate::romaji::{syllables, RomajiOptions, Syllable};
+use crate::{PartOfSpeech, Word};
+
+/// Phoneme inventories of speech frontends
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PhoneSet {
+    /// Open JTalk: long vowels are repeated (o o), っ is cl and pauses are pau
+    #[default]
+    OpenJTalk,
+    /// Julius: long vowels get a colon (o:), っ is q and pauses are sp
+    Julius,
+}
+
+impl PhoneSet {
+    fn sokuon(self) -> &'static str {
+        match self {
+            PhoneSet::OpenJTalk => "cl",
+            PhoneSet::Julius => "q",
+        }
+    }
+
+    /// Phoneme for the pause after an `AccentPhrase` with `pause` set
+    pub fn pause(self) -> &'static str {
+        match self {
+            PhoneSet::OpenJTalk => "pau",
+            PhoneSet::Julius => "sp",
+        }
+    }
+}
+
+/// A content word and the particles and suffixes that follow it, pronounced as one unit
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AccentPhrase {
+    /// Indices of the words making up the phrase
+    pub words: Range<usize>,
+    pub phonemes: Vec<String>,
+    /// Whether the phrase is followed by punctuation, which is read as a pause
+    pub pause: bool,
+}
+
+impl fmt::Display for AccentPhrase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.phonemes.join(" "))
+    }
+}
+
+/// Converts the transcriptions of `words` into phonemes, split into accent phrases.
+/// Words without a kana transcription, like Latin ones, don't add any phonemes.
+///
+/// ```
+/// use ve::{to_phonemes, PhoneSet, Word};
+///
+/// fn phoneme_line(words: &[Word]) -> String {
+///     let phone_set = PhoneSet::Julius;
+///
+///     to_phonemes(words, phone_set)
+///         .iter()
+///         .map(|phrase| match phrase.pause {
+///             true => format!("{} {}", phrase, phone_set.pause()),
+///             false => phrase.to_string(),
+///         })
+///         .collect::<Vec<_>>()
+///         .join(" | ")
+/// }
+/// ```
+pub fn to_phonemes(words: &[Word], phone_set: PhoneSet) -> Vec<AccentPhrase> {
+    let mut phrases: Vec<AccentPhrase> = Vec::new();
+    let mut attach_next = false;
+
+    for (i, word) in words.iter().enumerate() {
+        if word.part_of_speech == PartOfSpeech::Symbol {
+            if let Some(last) = phrases.last_mut() {
+                last.words.end = i + 1;
+                last.pause |= is_pause(&word.word);
+            }
+            attach_next = false;
+            continue;
+        }
+
+        let attaches = attach_next
+            || matches!(
+                word.part_of_speech,
+                PartOfSpeech::Postposition | PartOfSpeech::Suffix
+            );
+
+        let phrase = match phrases.last_mut() {
+            Some(last) if attaches && !last.pause => last,
+            _ => {
+                phrases.push(AccentPhrase {
+                    words: i..i,
+                    phonemes: Vec::new(),
+                    pause: false,
+                });
+                phrases.last_mut().unwrap()
+            }
+        };
+
+        let transcription = if word.extra.transcription.is_empty() {
+            &word.extra.reading
+        } else {
+            &word.extra.transcription
+        };
+        push_phonemes(&mut phrase.phonemes, transcription, phone_set);
+        phrase.words.end = i + 1;
+
+        attach_next = word.part_of_speech == PartOfSpeech::Prefix;
+    }
+
+    phrases.retain(|phrase| !phrase.phonemes.is_empty());
+    phrases
+}
+
+fn is_pause(symbol: &str) -> bool {
+    symbol
+        .chars()
+        .any(|c| "、。，．,.!?！？…・「」『』（）()".contains(c))
+}
+
+fn push_phonemes(phonemes: &mut Vec<String>, transcription: &str, phone_set: PhoneSet) {
+    for syllable in syllables(&to_katakana(transcription), &RomajiOptions::default()) {
+        match syllable {
+            Syllable::Kana(romaji) => {
+                // Syllables are a consonant (possibly none) followed by a vowel
+                let (consonant, vowel) = romaji.split_at(romaji.len() - 1);
+                if !consonant.is_empty() {
+                    phonemes.push(consonant.into());
+                }
+                phonemes.push(vowel.into());
+            }
+            Syllable::Sokuon => phonemes.push(phone_set.sokuon().into()),
+            Syllable::N => phonemes.push("N".into()),
+            Syllable::Long => {
+                let Some(vowel) = phonemes.last_mut().filter(|p| is_vowel(p)) else {
+                    continue;
+                };
+                match phone_set {
+                    PhoneSet::OpenJTalk => {
+                        let vowel = vowel.clone();
+                        phonemes.push(vowel);
+                    }
+                    PhoneSet::Julius => vowel.push(':'),
+                }
+            }
+            Syllable::Other(_) => (),
+        }
+    }
+}
+
+fn is_vowel(phoneme: &str) -> bool {
+    matches!(phoneme, "a" | "i" | "u" | "e" | "o")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    fn phonemes(transcription: &str, phone_set: PhoneSet) -> String {
+        let mut phonemes = Vec::new();
+        push_phonemes(&mut phonemes, transcription, phone_set);
+        phonemes.join(" ")
+    }
+
+    #[test]
+    fn splits_syllables_into_consonants_and_vowels() {
+        assert_eq!(phonemes("キッテ", PhoneSet::OpenJTalk), "k i cl t e");
+        assert_eq!(phonemes("キッテ", PhoneSet::Julius), "k i q t e");
+        assert_eq!(phonemes("きんえん", PhoneSet::OpenJTalk), "k i N e N");
+    }
+
+    #[test]
+    fn lengthens_vowels_for_each_phone_set() {
+        assert_eq!(phonemes("トーキョー", PhoneSet::OpenJTalk), "t o o ky o o");
+        assert_eq!(phonemes("トーキョー", PhoneSet::Julius), "t o: ky o:");
+    }
+
+    #[test]
+    fn attaches_particles_to_the_phrase_before_them() {
+        let words = words("猫が東京へ。嘘");
+        let phrases = to_phonemes(&words, PhoneSet::OpenJTalk);
+
+        let phrases: Vec<_> = phrases
+            .iter()
+            .map(|phrase| (phrase.to_string(), phrase.words.clone(), phrase.pause))
+            .collect();
+        assert_eq!(
+            phrases,
+            [
+                ("n e k o g a".into(), 0..2, false),
+                ("t o o ky o o e".into(), 2..5, true),
+                ("u s o".into(), 5..6, false)
+            ]
+        );
+    }
+}
@@ -0,0 +1,137 @@
+use std::ops::Range;
+
+use crate::known::lemma;
+use crate::{PartOfSpeech, Sentence, Word};
+
+/// Where the words of a phrase were found in a sentence
+#[derive(Clone, Debug, PartialEq)]
+pub struct PhraseMatch {
+    /// Indices into `Sentence::words` of the matching words, one for each query lemma
+    pub words: Vec<usize>,
+    /// Byte offsets in the text, from the first to the last matching word
+    pub range: Range<usize>,
+}
+
+/// Finds `lemmas` as consecutive words of `sentence`, so ["日本", "行く"] doesn't match
+/// 日本へ行った but ["日本", "へ", "行く"] does. Symbols and whitespace are skipped,
+/// like by `search_tokens`, and words match by their lemma or their surface.
+pub fn find_phrase(sentence: &Sentence, lemmas: &[&str]) -> Vec<PhraseMatch> {
+    find_near(sentence, lemmas, 0)
+}
+
+/// Finds `lemmas` in order in `sentence`, with at most `within` other words between
+/// each of them, so ["日本", "行く"] within 1 matches 日本へ行った.
+///
+/// Returns a match for each word the first lemma matches, with the closest words after it.
+pub fn find_near(sentence: &Sentence, lemmas: &[&str], within: usize) -> Vec<PhraseMatch> {
+    let Some((first, rest)) = lemmas.split_first() else {
+        return Vec::new();
+    };
+
+    // Word indices by position, as `search_tokens` counts them
+    let positions: Vec<usize> = sentence
+        .words
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| {
+            word.part_of_speech != PartOfSpeech::Symbol && !word.word.trim().is_empty()
+        })
+        .map(|(index, _)| index)
+        .collect();
+    let matches =
+        |position: usize, term: &str| is_match(&sentence.words[positions[position]], term);
+
+    let mut found = Vec::new();
+    for start in (0..positions.len()).filter(|&position| matches(position, first)) {
+        let mut matched = vec![start];
+
+        for term in rest {
+            let previous = *matched.last().unwrap();
+            let window = previous + 1..(previous + within + 2).min(positions.len());
+            // The closest word leaves the most room for the following ones
+            match window.into_iter().find(|&position| matches(position, term)) {
+                Some(position) => matched.push(position),
+                None => break,
+            }
+        }
+
+        if matched.len() == lemmas.len() {
+            let words: Vec<usize> = matched
+                .iter()
+                .map(|&position| positions[position])
+                .collect();
+            let range = sentence.words[words[0]].range().start
+                ..sentence.words[*words.last().unwrap()].range().end;
+            found.push(PhraseMatch { words, range });
+        }
+    }
+
+    found
+}
+
+/// Whether `lemmas` are consecutive words of `sentence`
+pub fn contains_phrase(sentence: &Sentence, lemmas: &[&str]) -> bool {
+    !find_phrase(sentence, lemmas).is_empty()
+}
+
+/// Whether `lemmas` are in order in `sentence` with at most `within` words between each
+pub fn contains_near(sentence: &Sentence, lemmas: &[&str], within: usize) -> bool {
+    !find_near(sentence, lemmas, within).is_empty()
+}
+
+fn is_match(word: &Word, term: &str) -> bool {
+    lemma(word) == term || word.word == term
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+    use crate::{parse_text, ParseConfig};
+
+    fn sentence(text: &str) -> Sentence {
+        parse_text(&Lexicon, text, &ParseConfig::default())
+            .unwrap()
+            .remove(0)
+    }
+
+    #[test]
+    fn finds_consecutive_lemmas() {
+        let sentence = sentence("猫が嘘を食べました");
+
+        assert_eq!(
+            find_phrase(&sentence, &["嘘", "を", "食べる"]),
+            [PhraseMatch {
+                words: vec![2, 3, 4],
+                range: 6..27
+            }]
+        );
+        assert!(!contains_phrase(&sentence, &["嘘", "食べる"]));
+        assert!(!contains_phrase(&sentence, &[]));
+    }
+
+    #[test]
+    fn skips_symbols() {
+        assert!(contains_phrase(&sentence("猫、が"), &["猫", "が"]));
+    }
+
+    #[test]
+    fn finds_lemmas_within_a_distance() {
+        let sentence = sentence("猫が嘘を食べました");
+
+        assert!(contains_near(&sentence, &["嘘", "食べる"], 1));
+        assert!(!contains_near(&sentence, &["猫", "食べる"], 2));
+        assert!(!contains_near(&sentence, &["食べる", "嘘"], 4));
+    }
+
+    #[test]
+    fn matches_each_occurrence_of_the_first_lemma() {
+        let sentence = sentence("猫が猫を嘘");
+
+        let matches: Vec<_> = find_near(&sentence, &["猫", "嘘"], 3)
+            .into_iter()
+            .map(|found| found.words)
+            .collect();
+        assert_eq!(matches, [vec![0, 4], vec![2, 4]]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::fmt;
+use std::sync::Arc;
+
+use vibrato::Tokenizer;
+
+use crate::{
+    parse_into_words_with, prepare_tokens_with, tokenize, ParseConfig, PreparedToken, Result,
+    VibratoToken, Word,
+};
+
+/// Turns text into raw tokens, the first stage of a [`Pipeline`]. Everything that takes a
+/// tokenizer, like [`Ve`](crate::Ve) or [`parse_text`](crate::parse_text), takes any `Tokenize`.
+/// Implemented by vibrato's `Tokenizer`, other backends can implement it as well.
+pub trait Tokenize: Send + Sync {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>>;
+}
+
+/// Reads the features of raw tokens, see [`prepare_tokens_with`]
+pub trait Prepare: Send + Sync {
+    fn prepare(
+        &self,
+        tokens: Vec<VibratoToken>,
+        config: &ParseConfig,
+    ) -> Result<Vec<PreparedToken>>;
+}
+
+/// Groups prepared tokens into words and runs the config's `post_processors` over them,
+/// see [`parse_into_words_with`]
+pub trait Group: Send + Sync {
+    fn group(&self, tokens: Vec<PreparedToken>, config: &ParseConfig) -> Result<Vec<Word>>;
+}
+
+impl Tokenize for Tokenizer {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>> {
+        Ok(tokenize(self, text))
+    }
+}
+
+impl<F> Prepare for F
+where
+    F: Fn(Vec<VibratoToken>, &ParseConfig) -> Result<Vec<PreparedToken>> + Send + Sync,
+{
+    fn prepare(
+        &self,
+        tokens: Vec<VibratoToken>,
+        config: &ParseConfig,
+    ) -> Result<Vec<PreparedToken>> {
+        self(tokens, config)
+    }
+}
+
+impl<F> Group for F
+where
+    F: Fn(Vec<PreparedToken>, &ParseConfig) -> Result<Vec<Word>> + Send + Sync,
+{
+    fn group(&self, tokens: Vec<PreparedToken>, config: &ParseConfig) -> Result<Vec<Word>> {
+        self(tokens, config)
+    }
+}
+
+/// The stages of parsing a sentence, `Tokenize → Prepare → Group`, each of which can be
+/// swapped out. Stages that aren't set use the same functions as `parse_with_stats`.
+/// Post-processing is up to the config's [`post_processors`](ParseConfig::post_processors),
+/// which the default `Group` stage runs once the words are grouped.
+///
+/// The text isn't normalized, apply `NormalizeOptions` beforehand if needed.
+///
+/// ```no_run
+/// use ve::{load_dictionary, ParseConfig, PartOfSpeech, Pipeline};
+///
+/// let mut config = ParseConfig::default();
+/// config.post_processors.push(|words| {
+///     words.retain(|word| word.part_of_speech != PartOfSpeech::Symbol)
+/// });
+///
+/// let dictionary = load_dictionary("system.dic.zst").unwrap();
+/// let pipeline = Pipeline::builder(vibrato::Tokenizer::new(dictionary))
+///     .config(config)
+///     .build();
+///
+/// let words = pipeline.parse("猫が好きです。").unwrap();
+/// ```
+#[derive(Clone)]
+pub struct Pipeline {
+    tokenizer: Arc<dyn Tokenize>,
+    preparer: Arc<dyn Prepare>,
+    grouper: Arc<dyn Group>,
+    config: ParseConfig,
+}
+
+/// Assembles a [`Pipeline`], see [`Pipeline::builder`]
+pub struct PipelineBuilder {
+    pipeline: Pipeline,
+}
+
+impl Pipeline {
+    /// Starts a pipeline around `tokenizer` with the default stages and config
+    pub fn builder(tokenizer: impl Tokenize + 'static) -> PipelineBuilder {
+        PipelineBuilder {
+            pipeline: Pipeline {
+                tokenizer: Arc::new(tokenizer),
+                preparer: Arc::new(prepare_tokens_with),
+                grouper: Arc::new(parse_into_words_with),
+                config: ParseConfig::default(),
+            },
+        }
+    }
+
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
+    }
+
+    /// Runs `sentence` through all stages
+    pub fn parse(&self, sentence: &str) -> Result<Vec<Word>> {
+        let raw_tokens = self.tokenizer.tokenize(sentence)?;
+        let tokens = self.preparer.prepare(raw_tokens, &self.config)?;
+        self.grouper.group(tokens, &self.config)
+    }
+}
+
+impl PipelineBuilder {
+    pub fn config(mut self, config: ParseConfig) -> Self {
+        self.pipeline.config = config;
+        self
+    }
+
+    pub fn prepare(mut self, preparer: impl Prepare + 'static) -> Self {
+        self.pipeline.preparer = Arc::new(preparer);
+        self
+    }
+
+    pub fn group(mut self, grouper: impl Group + 'static) -> Self {
+        self.pipeline.grouper = Arc::new(grouper);
+        self
+    }
+
+    /// Detects the dictionary's schema unless the config sets one, see [`ParseConfig::detect_schema`]
+    pub fn build(mut self) -> Pipeline {
+        let pipeline = &mut self.pipeline;
+        pipeline.config.detect_schema(pipeline.tokenizer.as_ref());
+        self.pipeline
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pipeline")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::Word;
+
+type Hook = Arc<dyn Fn(&mut Vec<Word>) + Send + Sync>;
+
+/// Transforms that run over the words once grouping is done, in the order they were added.
+///
+/// ```
+/// use ve::{ParseConfig, PartOfSpeech};
+///
+/// let mut config = ParseConfig::default();
+/// config.post_processors.push(|words| {
+///     for word in words.iter_mut() {
+///         if word.part_of_speech == PartOfSpeech::Pronoun {
+///             word.part_of_speech = PartOfSpeech::Noun;
+///         }
+///     }
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct PostProcessors(Vec<Hook>);
+
+impl PostProcessors {
+    pub fn push<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(&mut Vec<Word>) + Send + Sync + 'static,
+    {
+        self.0.push(Arc::new(hook));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn run(&self, words: &mut Vec<Word>) {
+        for hook in &self.0 {
+            hook(words);
+        }
+    }
+}
+
+impl fmt::Debug for PostProcessors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PostProcessors({} hooks)", self.0.len())
+    }
+}
+
+/// Merges the words in `range` into a single word, keeping the part of speech
+/// and grammar of the first one. The lemma becomes the surfaces of all but the
+/// last word followed by the last word's lemma, e.g. 勉強 + し始めた → 勉強し始める,
+/// and likewise for the lemma's reading.
+pub fn merge_adjacent(words: &mut Vec<Word>, range: Range<usize>) {
+    if range.len() < 2 || range.end > words.len() {
+        return;
+    }
+
+    let mut merged = words.drain(range.start + 1..range.end).collect::<Vec<_>>();
+    let last_lemma = merged
+        .last()
+        .map(|w| w.lemma.clone().unwrap_or_else(|| w.word.clone()));
+    let last_lemma_reading = merged.last().and_then(|w| w.extra.lemma_reading.clone());
+
+    let count = merged.len();
+    let first = &mut words[range.start];
+    let mut lemma = first.word.clone();
+    let mut lemma_reading = first.extra.reading.clone();
+
+    for (i, word) in merged.iter_mut().enumerate() {
+        first.word.push_str(&word.word);
+        first.extra.reading.push_str(&word.extra.reading);
+        if let (Some(reading), Some(other)) = (
+            &mut first.extra.hiragana_reading,
+            &word.extra.hiragana_reading,
+        ) {
+            reading.push_str(other);
+        }
+        first.extra.transcription.push_str(&word.extra.transcription);
+        first.is_oov |= word.is_oov;
+        if let (Some(romaji), Some(other)) = (&mut first.extra.romaji, &word.extra.romaji) {
+            romaji.push_str(other);
+        }
+        if let (Some(difficulty), Some(other)) =
+            (&mut first.extra.difficulty, word.extra.difficulty)
+        {
+            *difficulty = difficulty.max(other);
+        }
+        match (&mut first.extra.ruby, &word.extra.ruby) {
+            (Some(ruby), Some(other)) => ruby.push_str(other),
+            (ruby, _) => *ruby = None,
+        }
+        first.tokens.append(&mut word.tokens);
+
+        if i + 1 < count {
+            lemma.push_str(&word.word);
+            lemma_reading.push_str(&word.extra.reading);
+        }
+    }
+
+    lemma.push_str(&last_lemma.unwrap_or_default());
+    first.lemma = Some(lemma);
+    first.extra.lemma_reading = last_lemma_reading.map(|last| lemma_reading + &last);
+}
+
+/// Merges each run of adjacent words that `is_part` of something into a single word,
+/// if `accept` agrees with the whole run, and hands the merged word to `annotate`.
+/// Single-word runs aren't merged, but still get annotated.
+pub(crate) fn merge_runs(
+    words: &mut Vec<Word>,
+    is_part: impl Fn(&Word) -> bool,
+    accept: impl Fn(&[Word]) -> bool,
+    annotate: impl Fn(&mut Word),
+) {
+    let mut start = 0;
+
+    while start < words.len() {
+        if !is_part(&words[start]) {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start + 1;
+        while end < words.len()
+            && is_part(&words[end])
+            && words[end - 1].range().end == words[end].range().start
+        {
+            end += 1;
+        }
+
+        if accept(&words[start..end]) {
+            merge_adjacent(words, start..end);
+            annotate(&mut words[start]);
+            start += 1;
+        } else {
+            start = end;
+        }
+    }
+}
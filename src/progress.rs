@@ -0,0 +1,117 @@
+use std::time::{Duration, Instant};
+
+/// How far a long-running parse got, passed to the [`ProgressObserver`] after each sentence
+#[derive(Clone, Debug)]
+pub struct Progress {
+    pub sentences: usize,
+    /// Bytes of text parsed so far
+    pub bytes: usize,
+    /// Bytes of text in total, if known up front
+    pub total_bytes: Option<usize>,
+    /// Sentences in total, if known up front. Only used when `total_bytes` isn't.
+    pub total_sentences: Option<usize>,
+    started: Instant,
+}
+
+impl Progress {
+    /// Starts counting, from now on
+    pub fn new(total_bytes: Option<usize>) -> Self {
+        Self {
+            sentences: 0,
+            bytes: 0,
+            total_bytes,
+            total_sentences: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Counts another sentence of `bytes` bytes as parsed
+    pub fn advance(&mut self, bytes: usize) {
+        self.sentences += 1;
+        self.bytes += bytes;
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Share of the text parsed so far, between 0 and 1. None if the total isn't known.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.done_of_total()? {
+            (_, 0) => Some(1.0),
+            (done, total) => Some((done as f64 / total as f64).min(1.0)),
+        }
+    }
+
+    /// Estimated time until the whole text is parsed, assuming the rest goes as fast
+    /// as what was parsed so far
+    pub fn eta(&self) -> Option<Duration> {
+        let (done, total) = self.done_of_total()?;
+        if done == 0 {
+            return None;
+        }
+
+        let remaining = total.saturating_sub(done);
+        Some(self.elapsed().mul_f64(remaining as f64 / done as f64))
+    }
+
+    /// How much was parsed and how much there is, in bytes if their total is known
+    /// and in sentences otherwise
+    fn done_of_total(&self) -> Option<(usize, usize)> {
+        match (self.total_bytes, self.total_sentences) {
+            (Some(total), _) => Some((self.bytes, total)),
+            (None, Some(total)) => Some((self.sentences, total)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Notified of the [`Progress`] of `parse_text` and `parse_pipelined` after each sentence.
+/// Closures taking a `&Progress` can be used directly.
+///
+/// ```
+/// use std::sync::Arc;
+/// use ve::{Hook, ParseConfig, Progress};
+///
+/// let mut config = ParseConfig::default();
+/// config.progress = Some(Hook(Arc::new(|progress: &Progress| {
+///     eprintln!("{} sentences, eta {:?}", progress.sentences, progress.eta())
+/// })));
+/// ```
+pub trait ProgressObserver: Send + Sync {
+    fn on_progress(&self, progress: &Progress);
+}
+
+impl<F> ProgressObserver for F
+where
+    F: Fn(&Progress) + Send + Sync,
+{
+    fn on_progress(&self, progress: &Progress) {
+        self(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_bytes_when_their_total_is_known() {
+        let mut progress = Progress::new(Some(40));
+        progress.total_sentences = Some(2);
+        progress.advance(10);
+
+        assert_eq!(progress.fraction(), Some(0.25));
+    }
+
+    #[test]
+    fn counts_sentences_otherwise() {
+        let mut progress = Progress::new(None);
+        assert_eq!(progress.fraction(), None);
+
+        progress.total_sentences = Some(4);
+        progress.advance(10);
+        assert_eq!(progress.fraction(), Some(0.25));
+        assert!(progress.eta().is_some());
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Words whose reading doesn't decompose per kanji (jukujikun and ateji like 今日 or 土産)
+#[rustfmt::skip]
+const IRREGULAR_READINGS: [(&str, &str); 49] = [
+    ("今日", "キョウ"), ("明日", "アシタ"), ("昨日", "キノウ"), ("一昨日", "オトトイ"),
+    ("明後日", "アサッテ"), ("今朝", "ケサ"), ("今年", "コトシ"), ("大人", "オトナ"),
+    ("一人", "ヒトリ"), ("二人", "フタリ"), ("二十歳", "ハタチ"), ("息子", "ムスコ"),
+    ("素人", "シロウト"), ("玄人", "クロウト"), ("博士", "ハカセ"), ("迷子", "マイゴ"),
+    ("乙女", "オトメ"), ("若人", "ワコウド"), ("仲人", "ナコウド"), ("土産", "ミヤゲ"),
+    ("眼鏡", "メガネ"), ("時計", "トケイ"), ("部屋", "ヘヤ"), ("果物", "クダモノ"),
+    ("景色", "ケシキ"), ("相撲", "スモウ"), ("海老", "エビ"), ("田舎", "イナカ"),
+    ("八百屋", "ヤオヤ"), ("真面目", "マジメ"), ("梅雨", "ツユ"), ("雪崩", "ナダレ"),
+    ("吹雪", "フブキ"), ("時雨", "シグレ"), ("五月雨", "サミダレ"), ("七夕", "タナバタ"),
+    ("為替", "カワセ"), ("足袋", "タビ"), ("竹刀", "シナイ"), ("浴衣", "ユカタ"),
+    ("小豆", "アズキ"), ("芝生", "シバフ"), ("三味線", "シャミセン"), ("風邪", "カゼ"),
+    ("日和", "ヒヨリ"), ("心地", "ココチ"), ("居士", "コジ"), ("太刀", "タチ"),
+    ("山車", "ダシ"),
+];
+
+/// Readings that replace the dictionary's for words with the given surface, in katakana.
+/// Pronunciations are left as the dictionary has them.
+///
+/// Furigana keep words with these readings as a whole instead of splitting them between the
+/// kanji, like they do for a built-in table of irregular readings such as 今日 (キョウ) or
+/// 大人 (オトナ). That table only fills in readings the dictionary doesn't have.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReadingOverrides(HashMap<String, String>);
+
+impl ReadingOverrides {
+    pub fn insert(&mut self, surface: impl Into<String>, reading: impl Into<String>) -> &mut Self {
+        self.0.insert(surface.into(), reading.into());
+        self
+    }
+
+    pub fn remove(&mut self, surface: &str) -> &mut Self {
+        self.0.remove(surface);
+        self
+    }
+
+    pub fn get(&self, surface: &str) -> Option<&str> {
+        self.0.get(surface).map(String::as_str)
+    }
+
+    /// The overridden or else built-in irregular reading, for furigana
+    pub(crate) fn irregular(&self, surface: &str) -> Option<&str> {
+        self.get(surface).or_else(|| irregular_reading(surface))
+    }
+}
+
+/// The built-in reading of a word with an irregular reading, like オトナ for 大人
+pub(crate) fn irregular_reading(surface: &str) -> Option<&'static str> {
+    IRREGULAR_READINGS
+        .iter()
+        .find(|(irregular, _)| *irregular == surface)
+        .map(|(_, reading)| *reading)
+}
+
+impl Extend<(String, String)> for ReadingOverrides {
+    fn extend<T: IntoIterator<Item = (String, String)>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, words_with};
+    use crate::ParseConfig;
+
+    fn readings(text: &str, config: &ParseConfig) -> Vec<String> {
+        words_with(text, config)
+            .into_iter()
+            .map(|word| word.extra.reading)
+            .collect()
+    }
+
+    #[test]
+    fn looks_up_irregular_readings() {
+        assert_eq!(irregular_reading("大人"), Some("オトナ"));
+        assert_eq!(irregular_reading("大"), None);
+    }
+
+    #[test]
+    fn prefers_overrides_to_the_built_in_readings() {
+        let mut overrides = ReadingOverrides::default();
+        overrides.insert("今日", "コンニチ").insert("猫", "ニャン");
+
+        assert_eq!(overrides.irregular("今日"), Some("コンニチ"));
+        assert_eq!(overrides.irregular("猫"), Some("ニャン"));
+        assert_eq!(overrides.irregular("大人"), Some("オトナ"));
+
+        overrides.remove("今日");
+        assert_eq!(overrides.irregular("今日"), Some("キョウ"));
+    }
+
+    #[test]
+    fn overrides_replace_the_dictionary_reading() {
+        let mut config = ParseConfig::default();
+        config.reading_overrides.insert("今日", "コンニチ");
+
+        assert_eq!(readings("今日は", &config), ["コンニチ", "ハ"]);
+        assert_eq!(words("今日")[0].tokens[0].hatsuon, "キョー");
+    }
+
+    #[test]
+    fn irregular_readings_only_fill_in_missing_ones() {
+        let config = ParseConfig::default();
+
+        assert_eq!(readings("土産", &config), ["ミヤゲ"]);
+        assert_eq!(readings("今日", &config), ["キョウ"]);
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Word;
+
+/// Where the words stop adding up to the text they were parsed from
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Divergence {
+    /// Byte offset in the text
+    pub offset: usize,
+    /// Index of the word that doesn't match, None if the text goes on after the last word
+    pub word: Option<usize>,
+    /// The text at `offset`, as long as the word
+    pub expected: String,
+    /// The word, empty if there is none
+    pub found: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.word {
+            Some(index) => write!(
+                f,
+                "word {} at byte {}: expected {:?}, found {:?}",
+                index, self.offset, self.expected, self.found
+            ),
+            None => write!(
+                f,
+                "text left over after the last word at byte {}: {:?}",
+                self.offset, self.expected
+            ),
+        }
+    }
+}
+
+/// Checks that the surfaces of `words` put back together give `input`, which is what all
+/// the grouping should keep intact. Whitespace between words that the tokenizer skipped is
+/// fine, anything else missing, added or changed is returned as the first divergence.
+///
+/// Passes that change surfaces, like `Fillers::Drop`, make the words diverge on purpose.
+pub fn verify_reconstruction(input: &str, words: &[Word]) -> Result<(), Divergence> {
+    let mut offset = 0;
+
+    for (index, word) in words.iter().enumerate() {
+        let rest = &input[offset..];
+        if !rest.starts_with(&word.word) {
+            let trimmed = rest.trim_start();
+            if !word.word.is_empty() && trimmed.starts_with(&word.word) {
+                offset += rest.len() - trimmed.len();
+            } else {
+                return Err(Divergence {
+                    offset,
+                    word: Some(index),
+                    expected: rest.chars().take(word.word.chars().count()).collect(),
+                    found: word.word.clone(),
+                });
+            }
+        }
+        offset += word.word.len();
+    }
+
+    let rest = &input[offset..];
+    if rest.trim().is_empty() {
+        Ok(())
+    } else {
+        Err(Divergence {
+            offset: offset + rest.len() - rest.trim_start().len(),
+            word: None,
+            expected: rest.trim().into(),
+            found: String::new(),
+        })
+    }
+}
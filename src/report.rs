@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::mining::is_content_word;
+use crate::{PartOfSpeech, Sentence, Word};
+
+/// A summary of a parsed corpus, see [`ReportCollector`]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CorpusReport {
+    pub sentences: usize,
+    pub words: usize,
+    pub tokens: usize,
+    /// Tokens that came from vibrato's unknown-word handling
+    pub oov_tokens: usize,
+    /// Words by part of speech, the most frequent first
+    pub parts_of_speech: Vec<(PartOfSpeech, usize)>,
+    /// Number of sentences by their length in words, not counting symbols, shortest first
+    pub sentence_lengths: Vec<(usize, usize)>,
+    /// Lemmas of the content words, the most frequent first
+    pub top_lemmas: Vec<(String, usize)>,
+}
+
+impl CorpusReport {
+    /// Share of tokens that are out of vocabulary, between 0 and 1
+    pub fn oov_rate(&self) -> f64 {
+        if self.tokens == 0 {
+            0.0
+        } else {
+            self.oov_tokens as f64 / self.tokens as f64
+        }
+    }
+
+    /// Average sentence length in words, not counting symbols
+    pub fn mean_sentence_length(&self) -> f64 {
+        let total: usize = self
+            .sentence_lengths
+            .iter()
+            .map(|(length, count)| length * count)
+            .sum();
+
+        if self.sentences == 0 {
+            0.0
+        } else {
+            total as f64 / self.sentences as f64
+        }
+    }
+}
+
+impl fmt::Display for CorpusReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Sentences: {}", self.sentences)?;
+        writeln!(f, "Words: {}", self.words)?;
+        writeln!(f, "Tokens: {}", self.tokens)?;
+        writeln!(
+            f,
+            "Unknown tokens: {} ({:.2}%)",
+            self.oov_tokens,
+            self.oov_rate() * 100.0
+        )?;
+        writeln!(
+            f,
+            "Mean sentence length: {:.1} words",
+            self.mean_sentence_length()
+        )?;
+
+        writeln!(f, "\nParts of speech")?;
+        for (pos, count) in &self.parts_of_speech {
+            let share = *count as f64 / self.words.max(1) as f64 * 100.0;
+            writeln!(
+                f,
+                "  {:<16} {:>8} {:>6.2}%",
+                format!("{:?}", pos),
+                count,
+                share
+            )?;
+        }
+
+        writeln!(f, "\nSentence lengths")?;
+        for (length, count) in &self.sentence_lengths {
+            writeln!(f, "  {:>4} {:>8}", length, count)?;
+        }
+
+        writeln!(f, "\nTop lemmas")?;
+        for (lemma, count) in &self.top_lemmas {
+            writeln!(f, "  {} {}", lemma, count)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Adds up parsed sentences one at a time into a [`CorpusReport`], so a corpus doesn't
+/// have to be kept in memory
+#[derive(Clone, Debug, Default)]
+pub struct ReportCollector {
+    report: CorpusReport,
+    parts_of_speech: HashMap<PartOfSpeech, usize>,
+    sentence_lengths: HashMap<usize, usize>,
+    lemmas: HashMap<String, usize>,
+}
+
+impl ReportCollector {
+    pub fn add(&mut self, words: &[Word]) {
+        let report = &mut self.report;
+        report.sentences += 1;
+        report.words += words.len();
+
+        let mut length = 0;
+        for word in words {
+            report.tokens += word.tokens.len();
+            report.oov_tokens += word.tokens.iter().filter(|t| t.is_oov()).count();
+            *self.parts_of_speech.entry(word.part_of_speech).or_default() += 1;
+
+            if word.part_of_speech != PartOfSpeech::Symbol {
+                length += 1;
+            }
+            if is_content_word(word) {
+                match self.lemmas.get_mut(lemma(word)) {
+                    Some(count) => *count += 1,
+                    None => {
+                        self.lemmas.insert(lemma(word).into(), 1);
+                    }
+                }
+            }
+        }
+
+        *self.sentence_lengths.entry(length).or_default() += 1;
+    }
+
+    /// The report, with the `top` most frequent lemmas
+    pub fn finish(self, top: usize) -> CorpusReport {
+        let mut report = self.report;
+
+        report.parts_of_speech = by_count(self.parts_of_speech, |pos| format!("{:?}", pos));
+        report.top_lemmas = by_count(self.lemmas, String::clone);
+        report.top_lemmas.truncate(top);
+        report.sentence_lengths = self.sentence_lengths.into_iter().collect();
+        report.sentence_lengths.sort_unstable();
+
+        report
+    }
+}
+
+/// Most frequent first, ties ordered by `key` so reports are the same every time
+fn by_count<K, T: Ord>(counts: HashMap<K, usize>, key: impl Fn(&K) -> T) -> Vec<(K, usize)> {
+    let mut counts: Vec<(K, usize)> = counts.into_iter().collect();
+    counts.sort_unstable_by(|(a, a_count), (b, b_count)| {
+        b_count.cmp(a_count).then_with(|| key(a).cmp(&key(b)))
+    });
+    counts
+}
+
+/// A report of sentences that were already parsed, with the `top` most frequent lemmas
+pub fn corpus_report(sentences: &[Sentence], top: usize) -> CorpusReport {
+    let mut collector = ReportCollector::default();
+    for sentence in sentences {
+        collector.add(&sentence.words);
+    }
+    collector.finish(top)
+}
@@ -0,0 +1,488 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::document::BRACKETS;
+use crate::kana::to_katakana;
+use crate::{PartOfSpeech, Word};
+
+/// Romanization systems
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RomajiSystem {
+    /// Modified Hepburn (shi, tsu, fu, n' before vowels)
+    #[default]
+    Hepburn,
+    /// Kunrei-shiki (si, tu, hu, sya)
+    Kunrei,
+    /// Nihon-shiki, Kunrei-shiki that keeps kana apart which sound the same: ぢ di, づ du,
+    /// を wo, ゐ wi, ゑ we
+    Nihon,
+    /// Hepburn as used in Japanese passports, with m before b/m/p and long vowels left unmarked
+    Passport,
+    /// The way words are typed into an IME, spelled after the reading instead of the
+    /// pronunciation: とうきょう → toukyou, こんにちは → konnnitiha with `kunrei_keys`
+    Wapuro,
+}
+
+/// How long vowels (ー in the pronunciation) are written
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LongVowels {
+    /// tōkyō
+    #[default]
+    Macron,
+    /// tookyoo
+    Doubled,
+    /// tôkyô, as Kunrei-shiki officially writes them
+    Circumflex,
+    /// As the kana spell them, toukyou and ookii, like wāpuro romaji does. Romanizes words
+    /// from their reading instead of their pronunciation, ー is doubled.
+    Spelled,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct RomajiOptions {
+    pub system: RomajiSystem,
+    /// Ignored by `RomajiSystem::Passport`, which doesn't mark long vowels,
+    /// and `RomajiSystem::Wapuro`, which types ー as -
+    pub long_vowels: LongVowels,
+    /// With `RomajiSystem::Wapuro`, use the shorter Kunrei-shiki keys (si, ti, tu, hu, sya)
+    pub kunrei_keys: bool,
+}
+
+#[rustfmt::skip]
+const DIGRAPHS: [(&str, &str); 70] = [
+    ("キャ", "kya"), ("キュ", "kyu"), ("キョ", "kyo"),
+    ("ギャ", "gya"), ("ギュ", "gyu"), ("ギョ", "gyo"),
+    ("シャ", "sha"), ("シュ", "shu"), ("ショ", "sho"), ("シェ", "she"),
+    ("ジャ", "ja"), ("ジュ", "ju"), ("ジョ", "jo"), ("ジェ", "je"),
+    ("チャ", "cha"), ("チュ", "chu"), ("チョ", "cho"), ("チェ", "che"),
+    ("ヂャ", "ja"), ("ヂュ", "ju"), ("ヂョ", "jo"),
+    ("ニャ", "nya"), ("ニュ", "nyu"), ("ニョ", "nyo"),
+    ("ヒャ", "hya"), ("ヒュ", "hyu"), ("ヒョ", "hyo"),
+    ("ビャ", "bya"), ("ビュ", "byu"), ("ビョ", "byo"),
+    ("ピャ", "pya"), ("ピュ", "pyu"), ("ピョ", "pyo"),
+    ("ミャ", "mya"), ("ミュ", "myu"), ("ミョ", "myo"),
+    ("リャ", "rya"), ("リュ", "ryu"), ("リョ", "ryo"),
+    ("ファ", "fa"), ("フィ", "fi"), ("フェ", "fe"), ("フォ", "fo"), ("フュ", "fyu"),
+    ("ティ", "ti"), ("ディ", "di"), ("トゥ", "tu"), ("ドゥ", "du"),
+    ("テュ", "tyu"), ("デュ", "dyu"),
+    ("ウィ", "wi"), ("ウェ", "we"), ("ウォ", "wo"),
+    ("ヴァ", "va"), ("ヴィ", "vi"), ("ヴェ", "ve"), ("ヴォ", "vo"), ("ヴュ", "vyu"),
+    ("ツァ", "tsa"), ("ツィ", "tsi"), ("ツェ", "tse"), ("ツォ", "tso"),
+    ("イェ", "ye"), ("クァ", "kwa"), ("グァ", "gwa"),
+    ("スィ", "si"), ("ズィ", "zi"),
+    ("キェ", "kye"), ("ギェ", "gye"), ("ニェ", "nye"),
+];
+
+#[rustfmt::skip]
+const MONOGRAPHS: [(char, &str); 84] = [
+    ('ア', "a"), ('イ', "i"), ('ウ', "u"), ('エ', "e"), ('オ', "o"),
+    ('カ', "ka"), ('キ', "ki"), ('ク', "ku"), ('ケ', "ke"), ('コ', "ko"),
+    ('ガ', "ga"), ('ギ', "gi"), ('グ', "gu"), ('ゲ', "ge"), ('ゴ', "go"),
+    ('サ', "sa"), ('シ', "shi"), ('ス', "su"), ('セ', "se"), ('ソ', "so"),
+    ('ザ', "za"), ('ジ', "ji"), ('ズ', "zu"), ('ゼ', "ze"), ('ゾ', "zo"),
+    ('タ', "ta"), ('チ', "chi"), ('ツ', "tsu"), ('テ', "te"), ('ト', "to"),
+    ('ダ', "da"), ('ヂ', "ji"), ('ヅ', "zu"), ('デ', "de"), ('ド', "do"),
+    ('ナ', "na"), ('ニ', "ni"), ('ヌ', "nu"), ('ネ', "ne"), ('ノ', "no"),
+    ('ハ', "ha"), ('ヒ', "hi"), ('フ', "fu"), ('ヘ', "he"), ('ホ', "ho"),
+    ('バ', "ba"), ('ビ', "bi"), ('ブ', "bu"), ('ベ', "be"), ('ボ', "bo"),
+    ('パ', "pa"), ('ピ', "pi"), ('プ', "pu"), ('ペ', "pe"), ('ポ', "po"),
+    ('マ', "ma"), ('ミ', "mi"), ('ム', "mu"), ('メ', "me"), ('モ', "mo"),
+    ('ヤ', "ya"), ('ユ', "yu"), ('ヨ', "yo"),
+    ('ラ', "ra"), ('リ', "ri"), ('ル', "ru"), ('レ', "re"), ('ロ', "ro"),
+    ('ワ', "wa"), ('ヰ', "i"), ('ヱ', "e"), ('ヲ', "o"), ('ヴ', "vu"),
+    ('ァ', "a"), ('ィ', "i"), ('ゥ', "u"), ('ェ', "e"), ('ォ', "o"),
+    ('ャ', "ya"), ('ュ', "yu"), ('ョ', "yo"), ('ヮ', "wa"),
+    ('ヵ', "ka"), ('ヶ', "ke"),
+];
+
+/// Small kana that are typed with an x prefix when they don't form a digraph
+const SMALL_KANA: &str = "ァィゥェォャュョヮヵヶ";
+
+/// Kunrei-shiki spellings that differ from Hepburn
+#[rustfmt::skip]
+const KUNREI: [(&str, &str); 17] = [
+    ("shi", "si"), ("ji", "zi"), ("chi", "ti"), ("tsu", "tu"), ("fu", "hu"),
+    ("sha", "sya"), ("shu", "syu"), ("sho", "syo"), ("she", "sye"),
+    ("ja", "zya"), ("ju", "zyu"), ("jo", "zyo"), ("je", "zye"),
+    ("cha", "tya"), ("chu", "tyu"), ("cho", "tyo"), ("che", "tye"),
+];
+
+/// Nihon-shiki spellings that differ from Kunrei-shiki, digraphs first
+#[rustfmt::skip]
+const NIHON: [(&str, &str); 8] = [
+    ("ヂャ", "dya"), ("ヂュ", "dyu"), ("ヂョ", "dyo"),
+    ("ヂ", "di"), ("ヅ", "du"), ("ヲ", "wo"), ("ヰ", "wi"), ("ヱ", "we"),
+];
+
+/// How kana are typed with `RomajiSystem::Wapuro` where it differs from Hepburn, digraphs first
+#[rustfmt::skip]
+const WAPURO: [(&str, &str); 14] = [
+    ("ティ", "thi"), ("ディ", "dhi"), ("トゥ", "twu"), ("ドゥ", "dwu"),
+    ("テュ", "thu"), ("デュ", "dhu"), ("スィ", "swi"), ("ズィ", "zwi"),
+    ("ヂャ", "dya"), ("ヂュ", "dyu"), ("ヂョ", "dyo"),
+    ("ヂ", "di"), ("ヅ", "du"), ("ヲ", "wo"),
+];
+
+pub(crate) enum Syllable {
+    Kana(String),
+    /// っ, doubling the following consonant
+    Sokuon,
+    /// ー, lengthening the preceding vowel
+    Long,
+    /// ん
+    N,
+    Other(char),
+}
+
+fn kunrei(romaji: &str) -> &str {
+    KUNREI
+        .iter()
+        .find(|(hepburn, _)| *hepburn == romaji)
+        .map_or(romaji, |(_, kunrei)| kunrei)
+}
+
+pub(crate) fn syllables(katakana: &str, options: &RomajiOptions) -> Vec<Syllable> {
+    let chars: Vec<char> = katakana.chars().collect();
+    let mut syllables = Vec::with_capacity(chars.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let start = i;
+        let digraph: String = chars[i..(i + 2).min(chars.len())].iter().collect();
+
+        let spellings: &[(&str, &str)] = match options.system {
+            RomajiSystem::Nihon => &NIHON,
+            RomajiSystem::Wapuro => &WAPURO,
+            _ => &[],
+        };
+        if let Some((kana, romaji)) = spellings.iter().find(|(kana, _)| digraph.starts_with(kana)) {
+            i += kana.chars().count();
+            syllables.push(Syllable::Kana((*romaji).into()));
+            continue;
+        }
+
+        let romaji = if let Some((_, romaji)) = DIGRAPHS.iter().find(|(kana, _)| *kana == digraph) {
+            i += 2;
+            *romaji
+        } else {
+            let c = chars[i];
+            i += 1;
+            match c {
+                'ッ' => {
+                    syllables.push(Syllable::Sokuon);
+                    continue;
+                }
+                'ー' => {
+                    syllables.push(Syllable::Long);
+                    continue;
+                }
+                'ン' => {
+                    syllables.push(Syllable::N);
+                    continue;
+                }
+                _ => match MONOGRAPHS.iter().find(|(kana, _)| *kana == c) {
+                    Some((_, romaji)) => romaji,
+                    None => {
+                        syllables.push(Syllable::Other(c));
+                        continue;
+                    }
+                },
+            }
+        };
+
+        let romaji = match options.system {
+            RomajiSystem::Kunrei | RomajiSystem::Nihon => kunrei(romaji),
+            RomajiSystem::Wapuro if options.kunrei_keys => kunrei(romaji),
+            _ => romaji,
+        };
+
+        if options.system == RomajiSystem::Wapuro
+            && i - start == 1
+            && SMALL_KANA.contains(chars[start])
+        {
+            syllables.push(Syllable::Kana(format!("x{}", romaji)));
+        } else {
+            syllables.push(Syllable::Kana(romaji.into()));
+        }
+    }
+
+    syllables
+}
+
+fn macron(vowel: char) -> Option<char> {
+    match vowel {
+        'a' => Some('ā'),
+        'i' => Some('ī'),
+        'u' => Some('ū'),
+        'e' => Some('ē'),
+        'o' => Some('ō'),
+        _ => None,
+    }
+}
+
+fn circumflex(vowel: char) -> Option<char> {
+    match vowel {
+        'a' => Some('â'),
+        'i' => Some('î'),
+        'u' => Some('û'),
+        'e' => Some('ê'),
+        'o' => Some('ô'),
+        _ => None,
+    }
+}
+
+/// Romanizes kana, e.g. a word's transcription, which marks long vowels with ー.
+/// Anything that isn't kana is kept as is.
+pub fn romanize(kana: &str, options: &RomajiOptions) -> String {
+    let syllables = syllables(&to_katakana(kana), options);
+    let mut romaji = String::with_capacity(kana.len() * 2);
+
+    for (i, syllable) in syllables.iter().enumerate() {
+        let next = match syllables.get(i + 1) {
+            Some(Syllable::Kana(next)) => Some(next.as_str()),
+            _ => None,
+        };
+
+        match syllable {
+            Syllable::Kana(kana) => romaji.push_str(kana),
+            Syllable::Sokuon if options.system == RomajiSystem::Wapuro => {
+                match next.and_then(|n| n.chars().next()) {
+                    Some(c) if !"aiueox".contains(c) => romaji.push(c),
+                    _ => romaji.push_str("xtu"),
+                }
+            }
+            Syllable::Sokuon => match next {
+                Some(next) if next.starts_with("ch") => romaji.push('t'),
+                Some(next) => romaji.extend(next.chars().next().filter(|c| !"aiueo".contains(*c))),
+                None => (),
+            },
+            Syllable::Long => match options.system {
+                RomajiSystem::Passport => (),
+                RomajiSystem::Wapuro => romaji.push('-'),
+                _ => {
+                    let Some(vowel) = romaji.chars().last().filter(|c| "aiueo".contains(*c)) else {
+                        continue;
+                    };
+                    match options.long_vowels {
+                        LongVowels::Macron => {
+                            romaji.pop();
+                            romaji.extend(macron(vowel));
+                        }
+                        LongVowels::Circumflex => {
+                            romaji.pop();
+                            romaji.extend(circumflex(vowel));
+                        }
+                        LongVowels::Doubled | LongVowels::Spelled => romaji.push(vowel),
+                    }
+                }
+            },
+            Syllable::N if options.system == RomajiSystem::Wapuro => romaji.push_str("nn"),
+            Syllable::N => match next.and_then(|n| n.chars().next()) {
+                Some('b' | 'm' | 'p') if options.system == RomajiSystem::Passport => {
+                    romaji.push('m')
+                }
+                Some('a' | 'i' | 'u' | 'e' | 'o' | 'y')
+                    if options.system != RomajiSystem::Passport =>
+                {
+                    romaji.push_str("n'")
+                }
+                _ => romaji.push('n'),
+            },
+            Syllable::Other(c) => romaji.push(*c),
+        }
+    }
+
+    romaji
+}
+
+/// Romanizes a word from its transcription, or from its reading with `RomajiSystem::Wapuro`
+/// and `LongVowels::Spelled`, which follow the spelling. The particles は, へ and を are wa,
+/// e and o, except with Nihon-shiki, which keeps を as wo.
+pub(crate) fn romanize_word(word: &Word, options: &RomajiOptions) -> String {
+    let spelled = matches!(options.system, RomajiSystem::Wapuro | RomajiSystem::Nihon);
+    if word.part_of_speech == PartOfSpeech::Postposition && !spelled {
+        match word.word.as_str() {
+            "は" => return "wa".into(),
+            "へ" => return "e".into(),
+            "を" => return "o".into(),
+            _ => (),
+        }
+    }
+
+    let kana = match (options.system, options.long_vowels) {
+        (RomajiSystem::Wapuro, _) | (_, LongVowels::Spelled) => &word.extra.reading,
+        _ => &word.extra.transcription,
+    };
+    let kana = if kana.is_empty() { &word.word } else { kana };
+    romanize(kana, options)
+}
+
+/// Romanizes a parsed sentence, with spaces between the words and punctuation attached to
+/// the words next to it, in ASCII where there is an equivalent. Only particles read は, へ
+/// and を as wa, e and o, so 母へ is haha e but 部屋 is heya.
+pub fn romanize_sentence(words: &[Word], options: &RomajiOptions) -> String {
+    let mut romaji = String::new();
+    let mut attach = true;
+
+    for word in words {
+        if word.word.trim().is_empty() {
+            continue;
+        }
+
+        let opening = BRACKETS
+            .iter()
+            .any(|(open, _)| word.word.starts_with(*open));
+        let closing = word.part_of_speech == PartOfSpeech::Symbol && !opening;
+        if !attach && !closing {
+            romaji.push(' ');
+        }
+        attach = opening;
+
+        match word.part_of_speech {
+            PartOfSpeech::Symbol => romaji.extend(word.word.chars().map(ascii_punctuation)),
+            _ => romaji.push_str(&romanize_word(word, options)),
+        }
+    }
+
+    romaji
+}
+
+fn ascii_punctuation(c: char) -> char {
+    match c {
+        '。' | '．' => '.',
+        '、' | '，' => ',',
+        '！' => '!',
+        '？' => '?',
+        '：' => ':',
+        '；' => ';',
+        '「' | '」' | '『' | '』' | '“' | '”' => '"',
+        '（' => '(',
+        '）' => ')',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    fn in_system(kana: &str, system: RomajiSystem) -> String {
+        let options = RomajiOptions {
+            system,
+            ..Default::default()
+        };
+
+        romanize(kana, &options)
+    }
+
+    #[test]
+    fn writes_hepburn_by_default() {
+        let hepburn = RomajiOptions::default();
+        assert_eq!(romanize("シャシン", &hepburn), "shashin");
+        assert_eq!(romanize("マッチャ", &hepburn), "matcha");
+        assert_eq!(romanize("キッテ", &hepburn), "kitte");
+        assert_eq!(romanize("キンエン", &hepburn), "kin'en");
+        assert_eq!(romanize("トーキョー", &hepburn), "tōkyō");
+        assert_eq!(romanize("ふじさん", &hepburn), "fujisan");
+    }
+
+    #[test]
+    fn writes_kunrei_shiki() {
+        assert_eq!(in_system("シャシン", RomajiSystem::Kunrei), "syasin");
+        assert_eq!(in_system("チズ", RomajiSystem::Kunrei), "tizu");
+        assert_eq!(in_system("ハナヂ", RomajiSystem::Kunrei), "hanazi");
+    }
+
+    #[test]
+    fn writes_passport_romaji() {
+        assert_eq!(in_system("トーキョー", RomajiSystem::Passport), "tokyo");
+        assert_eq!(in_system("シンブン", RomajiSystem::Passport), "shimbun");
+        assert_eq!(in_system("キンエン", RomajiSystem::Passport), "kinen");
+    }
+
+    #[test]
+    fn writes_wapuro_keystrokes() {
+        assert_eq!(in_system("コンニチハ", RomajiSystem::Wapuro), "konnnichiha");
+        assert_eq!(in_system("ティー", RomajiSystem::Wapuro), "thi-");
+        assert_eq!(in_system("ディスク", RomajiSystem::Wapuro), "dhisuku");
+        assert_eq!(in_system("トゥ", RomajiSystem::Wapuro), "twu");
+        assert_eq!(in_system("ヂャ", RomajiSystem::Wapuro), "dya");
+        assert_eq!(in_system("ァ", RomajiSystem::Wapuro), "xa");
+
+        let kunrei_keys = RomajiOptions {
+            system: RomajiSystem::Wapuro,
+            kunrei_keys: true,
+            ..Default::default()
+        };
+        assert_eq!(romanize("コンニチハ", &kunrei_keys), "konnnitiha");
+    }
+
+    #[test]
+    fn writes_nihon_shiki() {
+        assert_eq!(in_system("ハナヂ", RomajiSystem::Nihon), "hanadi");
+        assert_eq!(in_system("ツヅク", RomajiSystem::Nihon), "tuduku");
+        assert_eq!(in_system("ヲ", RomajiSystem::Nihon), "wo");
+    }
+
+    #[test]
+    fn spells_the_particle_wo_with_nihon_shiki() {
+        let nihon = RomajiOptions {
+            system: RomajiSystem::Nihon,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            romanize_sentence(&words("猫を東京へ"), &nihon),
+            "neko wo tōkyō e"
+        );
+    }
+
+    #[test]
+    fn marks_long_vowels_as_asked() {
+        let with = |long_vowels| RomajiOptions {
+            long_vowels,
+            ..Default::default()
+        };
+        assert_eq!(romanize("トーキョー", &with(LongVowels::Macron)), "tōkyō");
+        assert_eq!(
+            romanize("トーキョー", &with(LongVowels::Doubled)),
+            "tookyoo"
+        );
+        assert_eq!(
+            romanize("トーキョー", &with(LongVowels::Circumflex)),
+            "tôkyô"
+        );
+    }
+
+    #[test]
+    fn spells_long_vowels_as_written() {
+        let spelled = RomajiOptions {
+            long_vowels: LongVowels::Spelled,
+            ..Default::default()
+        };
+
+        assert_eq!(romanize_sentence(&words("東京"), &spelled), "toukyou");
+    }
+
+    #[test]
+    fn reads_particles_as_pronounced() {
+        let hepburn = RomajiOptions::default();
+
+        assert_eq!(
+            romanize_sentence(&words("猫を東京へ"), &hepburn),
+            "neko o tōkyō e"
+        );
+        assert_eq!(
+            romanize_sentence(&words("猫は嘘。"), &hepburn),
+            "neko wa uso."
+        );
+        assert_eq!(romanize("ヘ", &hepburn), "he");
+    }
+}
@@ -0,0 +1,237 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::furigana::is_kanji;
+use crate::kana::{is_kana, to_hiragana};
+use crate::Word;
+
+/// Settings for `ruby_html`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct RubyOptions {
+    /// Also give words written only in kana their reading, like hiragana over katakana
+    /// words. Off by default, so only words with kanji get furigana.
+    pub kana_words: bool,
+    /// One reading over the whole word, 食べる(たべる), instead of over each kanji, 食(た)べる
+    pub whole_words: bool,
+    /// `<rp>` parentheses around the readings, shown by browsers without ruby support
+    pub parentheses: bool,
+}
+
+impl Default for RubyOptions {
+    fn default() -> Self {
+        Self {
+            kana_words: false,
+            whole_words: false,
+            parentheses: true,
+        }
+    }
+}
+
+/// Writes `sentence` as HTML with `<ruby>漢字<rt>かんじ</rt></ruby>` furigana in hiragana,
+/// escaping the rest of the text.
+///
+/// `words` need to have been parsed from `sentence` with their ranges intact, like
+/// [`parse_text`](crate::parse_text) or [`Ve::parse`](crate::Ve::parse) give them.
+pub fn ruby_html(sentence: &str, words: &[Word], options: &RubyOptions) -> String {
+    let mut output = String::with_capacity(sentence.len() * 2);
+    let mut position = 0;
+
+    for word in words {
+        let range = word.range();
+        if range.start < position || sentence.get(range.clone()) != Some(word.word.as_str()) {
+            // Words that don't match the text, e.g. because of spaces inside them, stay as text
+            continue;
+        }
+        push_escaped(&mut output, &sentence[position..range.start]);
+        position = range.end;
+
+        let has_kanji = word.word.chars().any(is_kanji);
+        let kana_only = word.word.chars().all(is_kana);
+
+        if has_kanji && !options.whole_words {
+            for segment in word.furigana_segments() {
+                match segment.reading {
+                    Some(reading) => push_ruby(&mut output, &segment.text, &reading, options),
+                    None => push_escaped(&mut output, &segment.text),
+                }
+            }
+            continue;
+        }
+
+        let reading = to_hiragana(&word.extra.reading);
+        let annotate = (has_kanji || (kana_only && options.kana_words))
+            && !reading.is_empty()
+            && reading != "*"
+            && reading != word.word;
+        match annotate {
+            true => push_ruby(&mut output, &word.word, &reading, options),
+            false => push_escaped(&mut output, &word.word),
+        }
+    }
+    push_escaped(&mut output, &sentence[position..]);
+
+    output
+}
+
+/// Writes `sentence` with furigana in the bracket notation of Anki and other flashcard apps,
+/// `食[た]べました`. Kanji compounds get one reading, and a space goes before them unless they
+/// start the sentence, which Anki hides but needs to tell where the kanji begin.
+///
+/// `words` need to have been parsed from `sentence` like for [`ruby_html`].
+pub fn anki_furigana(sentence: &str, words: &[Word]) -> String {
+    let mut output = String::with_capacity(sentence.len() * 2);
+    let mut position = 0;
+
+    for word in words {
+        let range = word.range();
+        if range.start < position || sentence.get(range.clone()) != Some(word.word.as_str()) {
+            continue;
+        }
+        output.push_str(&sentence[position..range.start]);
+        position = range.end;
+
+        let segments = word.furigana_segments();
+        for run in segments.chunk_by(|a, b| a.reading.is_some() == b.reading.is_some()) {
+            for segment in run {
+                output.push_str(&segment.text);
+            }
+            if run[0].reading.is_none() {
+                continue;
+            }
+
+            let text: String = run.iter().map(|segment| segment.text.as_str()).collect();
+            if output.len() > text.len() && !output[..output.len() - text.len()].ends_with(' ') {
+                output.insert(output.len() - text.len(), ' ');
+            }
+            output.push('[');
+            for reading in run.iter().filter_map(|segment| segment.reading.as_deref()) {
+                output.push_str(&to_hiragana(reading));
+            }
+            output.push(']');
+        }
+    }
+    output.push_str(&sentence[position..]);
+
+    output
+}
+
+fn push_ruby(output: &mut String, text: &str, reading: &str, options: &RubyOptions) {
+    let (open, close) = match options.parentheses {
+        true => ("<rp>(</rp><rt>", "</rt><rp>)</rp></ruby>"),
+        false => ("<rt>", "</rt></ruby>"),
+    };
+
+    output.push_str("<ruby>");
+    push_escaped(output, text);
+    output.push_str(open);
+    push_escaped(output, &to_hiragana(reading));
+    output.push_str(close);
+}
+
+pub(crate) fn push_escaped(output: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{words, words_from};
+    use crate::ParseConfig;
+
+    fn ruby(text: &str, options: &RubyOptions) -> String {
+        ruby_html(text, &words(text), options)
+    }
+
+    #[test]
+    fn writes_furigana_over_each_kanji() {
+        assert_eq!(
+            ruby("猫が食べました。", &RubyOptions::default()),
+            "<ruby>猫<rp>(</rp><rt>ねこ</rt><rp>)</rp></ruby>が\
+             <ruby>食<rp>(</rp><rt>た</rt><rp>)</rp></ruby>べました。"
+        );
+        assert_eq!(
+            ruby(
+                "勉強 猫",
+                &RubyOptions {
+                    parentheses: false,
+                    ..Default::default()
+                }
+            ),
+            "<ruby>勉<rt>べん</rt></ruby><ruby>強<rt>きょう</rt></ruby> <ruby>猫<rt>ねこ</rt></ruby>"
+        );
+    }
+
+    #[test]
+    fn writes_furigana_over_whole_words() {
+        let options = RubyOptions {
+            whole_words: true,
+            parentheses: false,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ruby("猫が食べました。", &options),
+            "<ruby>猫<rt>ねこ</rt></ruby>が<ruby>食べました<rt>たべました</rt></ruby>。"
+        );
+    }
+
+    #[test]
+    fn annotates_kana_words_only_when_asked() {
+        let tokens = [
+            ("テレビ", "名詞,一般,*,*,*,*,テレビ,テレビ,テレビ"),
+            ("が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ"),
+        ];
+        let words = words_from(&tokens, &ParseConfig::default());
+        let options = RubyOptions {
+            parentheses: false,
+            ..Default::default()
+        };
+
+        assert_eq!(ruby_html("テレビが", &words, &options), "テレビが");
+        assert_eq!(
+            ruby_html(
+                "テレビが",
+                &words,
+                &RubyOptions {
+                    kana_words: true,
+                    ..options
+                }
+            ),
+            "<ruby>テレビ<rt>てれび</rt></ruby>が"
+        );
+    }
+
+    #[test]
+    fn escapes_the_text_and_skips_words_not_in_it() {
+        assert_eq!(ruby("<b>", &RubyOptions::default()), "&lt;b&gt;");
+        assert_eq!(
+            ruby_html("犬が", &words("猫が"), &RubyOptions::default()),
+            "犬が"
+        );
+    }
+
+    #[test]
+    fn writes_anki_furigana() {
+        let anki = |text| anki_furigana(text, &words(text));
+
+        assert_eq!(anki("猫が食べました。"), "猫[ねこ]が 食[た]べました。");
+        assert_eq!(anki("今日は勉強"), "今日[きょう]は 勉強[べんきょう]");
+        assert_eq!(anki("食べ物"), "食[た]べ 物[もの]");
+    }
+
+    #[test]
+    fn separates_adjacent_kanji_words() {
+        assert_eq!(anki_furigana("猫猫", &words("猫猫")), "猫[ねこ] 猫[ねこ]");
+        assert_eq!(anki_furigana("猫 猫", &words("猫 猫")), "猫[ねこ] 猫[ねこ]");
+    }
+}
@@ -0,0 +1,591 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::nominalizers::{is_nominalizer, is_predicate};
+use crate::normalize::{is_acronym, is_katakana};
+use crate::{Grammar, PartOfSpeech, PreparedToken, Word, BA, DE, NA, NI, NN, POS, SA, TE};
+
+/// The grouping rules `parse_into_words` applies when deciding
+/// whether tokens get merged into a single word
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum Rule {
+    /// サ変 nouns followed by する become a single verb (勉強する)
+    SahenVerb,
+    /// Adjectival noun stems followed by だ/ない become adjectives (静かな)
+    AdjectivalNoun,
+    /// Adverbial nouns followed by に become adverbs (特に)
+    AdverbialNoun,
+    /// Auxiliary stems followed by だ become auxiliary verbs (ようだ)
+    AuxiliaryStem,
+    /// Consecutive numbers merge into one number (3 000)
+    MergeNumbers,
+    /// Decimal points and thousands separators between digits
+    /// attach to the preceding number (3.14, 1,000)
+    AttachNumberSeparator,
+    /// Noun suffixes attach to the preceding word (子供たち)
+    AttachSuffix,
+    /// Inflecting auxiliaries attach to the preceding word (食べました)
+    AttachAuxiliary,
+    /// Suffix and non-independent verbs attach to the preceding word (食べている)
+    AttachVerbSuffix,
+    /// The conjunctive particles て/で/ば attach to the preceding word (食べて)
+    AttachConjunctiveParticle,
+    /// The な before a noun attaches to the preceding noun, which becomes an adjective,
+    /// also where the dictionary doesn't tag it as adjectival (最高 + な).
+    /// Off unless enabled, see `Rule::OPT_IN`.
+    AttachNa,
+    /// Nominalizing の and こと attach to the verb or adjective before them, making the
+    /// clause a noun (食べる + の). Off unless enabled, see `Rule::OPT_IN`.
+    AttachNominalizer,
+    /// Unknown katakana words that got split up by vibrato's `max_grouping_len`
+    /// merge back into one word (ズンドコ + ベロンチョ)
+    MergeUnknownKatakana,
+    /// Capital letters spelling out an acronym become a proper noun (Ａ + Ｉ)
+    Acronym,
+    /// Latin-alphabet tokens become foreign words, merging with adjacent
+    /// Latin tokens up to the next whitespace (iPhone + 15 + Pro)
+    MergeLatin,
+}
+
+impl Rule {
+    /// All rules, in the order they're tried by default
+    pub const DEFAULT_ORDER: [Rule; 15] = [
+        Rule::SahenVerb,
+        Rule::AdjectivalNoun,
+        Rule::AdverbialNoun,
+        Rule::AuxiliaryStem,
+        Rule::MergeNumbers,
+        Rule::AttachNumberSeparator,
+        Rule::AttachSuffix,
+        Rule::AttachAuxiliary,
+        Rule::AttachVerbSuffix,
+        Rule::AttachConjunctiveParticle,
+        Rule::AttachNa,
+        Rule::AttachNominalizer,
+        Rule::MergeUnknownKatakana,
+        Rule::Acronym,
+        Rule::MergeLatin,
+    ];
+
+    /// Rules that change the default grouping, so they're off unless enabled in the `RuleSet`
+    pub const OPT_IN: [Rule; 2] = [Rule::AttachNa, Rule::AttachNominalizer];
+
+    /// Checks whether the rule applies to the current token and if so, updates the decision.
+    /// Returns true if the rule fired.
+    pub(crate) fn apply(&self, ctx: &RuleContext, decision: &mut Decision) -> bool {
+        let token = ctx.token;
+
+        match self {
+            Rule::SahenVerb => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+
+                if ctx.is_noun_stem() && following.inflection_type == POS::SahenSuru {
+                    decision.pos = Some(PartOfSpeech::Verb);
+                    decision.eat_next = true;
+                    return true;
+                }
+            }
+            Rule::AdjectivalNoun => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+
+                if ctx.is_noun_stem() {
+                    if following.inflection_type == POS::TokushuDa {
+                        decision.pos = Some(PartOfSpeech::Adjective);
+                        if following.inflection_form == POS::Taigensetsuzoku {
+                            decision.eat_next = true;
+                            decision.eat_lemma = false;
+                        }
+                        return true;
+                    } else if following.inflection_type == POS::TokushuNai {
+                        decision.pos = Some(PartOfSpeech::Adjective);
+                        decision.eat_next = true;
+                        return true;
+                    }
+                } else if ctx.is_dependent_noun() && token.pos3 == POS::Keiyoudoushigokan {
+                    decision.pos = Some(PartOfSpeech::Adjective);
+                    if (following.inflection_type == POS::TokushuDa
+                        && following.inflection_form == POS::Taigensetsuzoku)
+                        || following.pos2 == POS::Rentaika
+                    {
+                        decision.eat_next = true;
+                    }
+                    return true;
+                }
+            }
+            Rule::AdverbialNoun => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+
+                if following.pos == POS::Joshi && following.literal == NI {
+                    if ctx.is_noun_stem() {
+                        decision.pos = Some(PartOfSpeech::Adverb);
+                        decision.eat_next = false;
+                        return true;
+                    } else if ctx.is_dependent_noun() && token.pos3 == POS::Fukushikanou {
+                        decision.pos = Some(PartOfSpeech::Adverb);
+                        decision.eat_next = true;
+                        return true;
+                    }
+                }
+            }
+            Rule::AuxiliaryStem => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+
+                if ctx.is_dependent_noun() && token.pos3 == POS::Jodoushigokan {
+                    if following.inflection_type == POS::TokushuDa {
+                        decision.pos = Some(PartOfSpeech::Verb);
+                        decision.grammar = Some(Grammar::Auxillary);
+
+                        if following.inflection_form == POS::Taigensetsuzoku {
+                            decision.eat_next = true;
+                        }
+                        return true;
+                    } else if following.pos == POS::Joshi && following.pos2 == POS::Fukushika {
+                        decision.pos = Some(PartOfSpeech::Adverb);
+                        decision.eat_next = true;
+                        return true;
+                    }
+                }
+            }
+            Rule::MergeNumbers => {
+                if token.pos == POS::Meishi
+                    && token.pos2 == POS::Kazu
+                    && ctx
+                        .last_word
+                        .is_some_and(|w| w.part_of_speech == PartOfSpeech::Number)
+                {
+                    decision.attach_to_previous = true;
+                    decision.also_attach_to_lemma = true;
+                    return true;
+                }
+            }
+            Rule::AttachNumberSeparator => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+
+                let digits = following.literal.chars().filter(|c| is_digit(*c)).count();
+                let separates = match token.literal.as_str() {
+                    "." | "．" => digits > 0,
+                    "," | "，" => digits == 3,
+                    _ => false,
+                };
+
+                if separates
+                    && digits == following.literal.chars().count()
+                    && following.range.start == token.range.end
+                    && ctx.follows_last_word()
+                    && ctx.last_word.is_some_and(|w| {
+                        w.part_of_speech == PartOfSpeech::Number
+                            && w.word.chars().last().is_some_and(is_digit)
+                    })
+                {
+                    decision.attach_to_previous = true;
+                    decision.also_attach_to_lemma = true;
+                    return true;
+                }
+            }
+            Rule::AttachSuffix => {
+                if token.pos == POS::Meishi
+                    && token.pos2 == POS::Setsubi
+                    && token.pos3 != POS::Jinmei
+                {
+                    if token.pos3 == POS::Tokushu && token.lemma == SA {
+                        decision.update_pos = true;
+                        decision.pos = Some(PartOfSpeech::Noun);
+                    } else {
+                        decision.also_attach_to_lemma = true;
+                    }
+                    decision.attach_to_previous = true;
+                    return true;
+                }
+            }
+            Rule::AttachAuxiliary => {
+                let follows_kakarijoshi = ctx.previous.is_some_and(|p| p.pos2 == POS::Kakarijoshi);
+
+                if token.pos == POS::JoDoushi
+                    && ((!follows_kakarijoshi
+                        && [
+                            POS::TokushuTa,
+                            POS::TokushuNai,
+                            POS::TokushuTai,
+                            POS::TokushuMasu,
+                            POS::TokushuNu,
+                        ]
+                        .contains(&token.inflection_type))
+                        || (token.inflection_type == POS::Fuhenkagata && token.lemma == NN))
+                {
+                    decision.attach_to_previous = true;
+                    return true;
+                }
+            }
+            Rule::AttachVerbSuffix => {
+                if token.pos == POS::Doushi
+                    && (token.pos2 == POS::Setsubi
+                        || (token.pos2 == POS::Hijiritsu && token.inflection_form != POS::MeireiI))
+                {
+                    decision.attach_to_previous = true;
+                    return true;
+                }
+            }
+            Rule::AttachConjunctiveParticle => {
+                if token.pos == POS::Joshi
+                    && token.pos2 == POS::Setsuzokujoshi
+                    && [TE, DE, BA].contains(&token.literal.as_str())
+                {
+                    decision.attach_to_previous = true;
+                    return true;
+                }
+            }
+            Rule::AttachNa => {
+                let Some(following) = ctx.following else {
+                    return false;
+                };
+                // Not the な of なので or なんだ, which can follow any noun
+                let before_noun = following.pos != POS::Joshi
+                    && !(following.pos2 == POS::Hijiritsu
+                        && ["の", NN].contains(&following.literal.as_str()));
+
+                if token.pos == POS::JoDoushi
+                    && token.inflection_type == POS::TokushuDa
+                    && token.inflection_form == POS::Taigensetsuzoku
+                    && token.literal == NA
+                    && before_noun
+                    && ctx.follows_last_word()
+                    && ctx.last_word.is_some_and(|w| {
+                        matches!(
+                            w.part_of_speech,
+                            PartOfSpeech::Noun | PartOfSpeech::Adjective
+                        ) && !w.word.ends_with(NA)
+                    })
+                {
+                    decision.attach_to_previous = true;
+                    decision.update_pos = true;
+                    decision.pos = Some(PartOfSpeech::Adjective);
+                    return true;
+                }
+            }
+            Rule::AttachNominalizer => {
+                if is_nominalizer(token, ctx.following)
+                    && ctx.follows_last_word()
+                    && ctx.last_word.is_some_and(is_predicate)
+                {
+                    decision.attach_to_previous = true;
+                    decision.update_pos = true;
+                    decision.pos = Some(PartOfSpeech::Noun);
+                    return true;
+                }
+            }
+            Rule::MergeUnknownKatakana => {
+                if token.is_oov
+                    && is_katakana(&token.literal)
+                    && ctx
+                        .last_word
+                        .is_some_and(|w| w.is_oov && is_katakana(&w.word))
+                {
+                    decision.attach_to_previous = true;
+                    decision.also_attach_to_lemma = true;
+                    return true;
+                }
+            }
+            Rule::Acronym => {
+                if !is_acronym(&token.literal) {
+                    return false;
+                }
+
+                let adjacent = ctx
+                    .last_word
+                    .filter(|w| ctx.follows_last_word() && is_latin(&w.word));
+
+                match adjacent {
+                    Some(last_word) if is_acronym(&last_word.word) => {
+                        decision.attach_to_previous = true;
+                        decision.also_attach_to_lemma = true;
+                        decision.update_pos = true;
+                        decision.pos = Some(PartOfSpeech::ProperNoun);
+                        return true;
+                    }
+                    // part of something like iPhoneX, which MergeLatin takes care of
+                    Some(_) => return false,
+                    None if token.literal.chars().count() >= 2 => {
+                        decision.pos = Some(PartOfSpeech::ProperNoun);
+                        return true;
+                    }
+                    None => (),
+                }
+            }
+            Rule::MergeLatin => {
+                if !is_latin(&token.literal) {
+                    return false;
+                }
+
+                let adjacent = ctx
+                    .last_word
+                    .filter(|w| ctx.follows_last_word() && is_latin(&w.word));
+
+                // a lone joiner only belongs to the word if Latin follows it too (e-mail)
+                if token.literal.chars().all(is_joiner) {
+                    let joins = ctx.following.is_some_and(|t| {
+                        t.range.start == token.range.end
+                            && t.literal.chars().next().is_some_and(is_alphanumeric)
+                    });
+                    if adjacent.is_none() || !joins {
+                        return false;
+                    }
+                }
+
+                if let Some(last_word) = adjacent {
+                    if has_latin_letter(&token.literal) || has_latin_letter(&last_word.word) {
+                        decision.attach_to_previous = true;
+                        decision.also_attach_to_lemma = true;
+                        decision.update_pos = true;
+                        decision.pos = Some(PartOfSpeech::Foreign);
+                        return true;
+                    }
+                } else if has_latin_letter(&token.literal) {
+                    decision.pos = Some(PartOfSpeech::Foreign);
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+}
+
+/// An ordered registry of the grouping rules to apply.
+///
+/// For each token, rules are tried in order and the first one that applies wins,
+/// so rules earlier in the order take precedence over later ones.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RuleSet {
+    /// Precedence order. Rules missing from it are tried afterwards, in their default order.
+    pub order: Vec<Rule>,
+    /// Rules can be switched off by setting them to false
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub enabled: HashMap<Rule, bool>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self {
+            order: Rule::DEFAULT_ORDER.to_vec(),
+            enabled: HashMap::new(),
+        }
+    }
+}
+
+impl RuleSet {
+    pub fn is_enabled(&self, rule: Rule) -> bool {
+        self.enabled
+            .get(&rule)
+            .copied()
+            .unwrap_or(!Rule::OPT_IN.contains(&rule))
+    }
+
+    pub fn enable(&mut self, rule: Rule) -> &mut Self {
+        self.enabled.insert(rule, true);
+        self
+    }
+
+    pub fn disable(&mut self, rule: Rule) -> &mut Self {
+        self.enabled.insert(rule, false);
+        self
+    }
+
+    /// Moves `rule` in front of `other`, so it's tried first
+    pub fn place_before(&mut self, rule: Rule, other: Rule) -> &mut Self {
+        self.order = self.active_order();
+        self.order.retain(|r| *r != rule);
+
+        let index = self.order.iter().position(|r| *r == other).unwrap_or(0);
+        self.order.insert(index, rule);
+        self
+    }
+
+    /// The enabled rules in the order they're tried
+    pub fn iter(&self) -> impl Iterator<Item = Rule> + '_ {
+        self.active_order()
+            .into_iter()
+            .filter(|rule| self.is_enabled(*rule))
+    }
+
+    fn active_order(&self) -> Vec<Rule> {
+        let mut order: Vec<Rule> = Vec::with_capacity(Rule::DEFAULT_ORDER.len());
+
+        for rule in self.order.iter().chain(Rule::DEFAULT_ORDER.iter()) {
+            if !order.contains(rule) {
+                order.push(*rule);
+            }
+        }
+
+        order
+    }
+}
+
+/// What grouping decided for a token, see [`parse_into_decisions`](crate::parse_into_decisions)
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TokenDecision {
+    pub surface: String,
+    /// The part of speech the token was given, which the word it starts gets
+    pub part_of_speech: PartOfSpeech,
+    /// The rule that decided, None if it's just the token's own part of speech
+    pub rule: Option<Rule>,
+    /// Whether the token takes the one after it into its word
+    pub eat_next: bool,
+    /// Whether the token joins the word before it
+    pub attach_to_previous: bool,
+    /// Whether joining the word before it changes that word's part of speech
+    pub update_pos: bool,
+    /// Whether the token was taken into its word by the token before it, whose part of
+    /// speech it then has
+    pub eaten: bool,
+}
+
+/// What to do with a token, as decided by its part of speech and the grouping rules
+#[derive(Default, Debug)]
+pub(crate) struct Decision {
+    pub pos: Option<PartOfSpeech>,
+    pub grammar: Option<Grammar>,
+    pub eat_next: bool,
+    pub eat_lemma: bool,
+    pub attach_to_previous: bool,
+    pub also_attach_to_lemma: bool,
+    pub update_pos: bool,
+}
+
+/// Everything a rule gets to look at when deciding about a token
+pub(crate) struct RuleContext<'a> {
+    pub token: &'a PreparedToken,
+    pub previous: Option<&'a PreparedToken>,
+    pub following: Option<&'a PreparedToken>,
+    pub last_word: Option<&'a Word>,
+}
+
+impl RuleContext<'_> {
+    /// Nouns that can act as the stem of a verb, adjective or adverb
+    fn is_noun_stem(&self) -> bool {
+        self.token.pos == POS::Meishi
+            && [
+                POS::Fukushikanou,
+                POS::Sahensetsuzoku,
+                POS::Keiyoudoushigokan,
+                POS::Naikeiyoushigokan,
+            ]
+            .contains(&self.token.pos2)
+    }
+
+    fn is_dependent_noun(&self) -> bool {
+        self.token.pos == POS::Meishi && [POS::Hijiritsu, POS::Tokushu].contains(&self.token.pos2)
+    }
+
+    /// Whether the token directly follows the last word, without any whitespace in between
+    fn follows_last_word(&self) -> bool {
+        self.last_word
+            .and_then(|w| w.tokens.last())
+            .is_some_and(|t| t.range.end == self.token.range.start)
+    }
+}
+
+/// Half- or full-width ASCII letters and digits, and the joiners between them
+fn is_latin(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| is_alphanumeric(c) || is_joiner(c))
+}
+
+fn is_alphanumeric(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, 'Ａ'..='Ｚ' | 'ａ'..='ｚ' | '０'..='９')
+}
+
+/// Punctuation that joins Latin words rather than separating them
+fn is_joiner(c: char) -> bool {
+    matches!(c, '-' | '\'' | '.' | '－' | '＇' | '．')
+}
+
+fn has_latin_letter(text: &str) -> bool {
+    text.chars()
+        .any(|c| c.is_ascii_alphabetic() || matches!(c, 'Ａ'..='Ｚ' | 'ａ'..='ｚ'))
+}
+
+fn is_digit(c: char) -> bool {
+    c.is_ascii_digit() || ('０'..='９').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    const NA_TOKEN: (&str, &str) = ("な", "助動詞,*,*,*,特殊・ダ,体言接続,だ,ナ,ナ");
+
+    fn attach_na(tokens: &[(&str, &str)]) -> Vec<(String, PartOfSpeech)> {
+        let mut config = ParseConfig::default();
+        config.rules.enable(Rule::AttachNa);
+
+        words_from(tokens, &config)
+            .into_iter()
+            .map(|word| (word.word, word.part_of_speech))
+            .collect()
+    }
+
+    #[test]
+    fn attaches_na_to_nouns_before_nouns() {
+        assert_eq!(
+            attach_na(&[
+                ("病気", "名詞,一般,*,*,*,*,病気,ビョウキ,ビョーキ"),
+                NA_TOKEN,
+                ("人", "名詞,一般,*,*,*,*,人,ヒト,ヒト"),
+            ]),
+            [
+                ("病気な".into(), PartOfSpeech::Adjective),
+                ("人".into(), PartOfSpeech::Noun)
+            ]
+        );
+    }
+
+    #[test]
+    fn leaves_the_na_of_nanode_and_nanda_alone() {
+        let nouns = [
+            ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+            NA_TOKEN,
+            ("の", "名詞,非自立,一般,*,*,*,の,ノ,ノ"),
+            ("で", "助動詞,*,*,*,特殊・ダ,連用形,だ,デ,デ"),
+        ];
+        assert_eq!(attach_na(&nouns)[0], ("猫".into(), PartOfSpeech::Noun));
+
+        let nouns = [
+            ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+            NA_TOKEN,
+            ("ん", "名詞,非自立,一般,*,*,*,ん,ン,ン"),
+            ("だ", "助動詞,*,*,*,特殊・ダ,基本形,だ,ダ,ダ"),
+        ];
+        assert_eq!(attach_na(&nouns)[0], ("猫".into(), PartOfSpeech::Noun));
+    }
+
+    #[test]
+    fn attach_na_is_opt_in() {
+        let words = words_from(
+            &[
+                ("病気", "名詞,一般,*,*,*,*,病気,ビョウキ,ビョーキ"),
+                NA_TOKEN,
+                ("人", "名詞,一般,*,*,*,*,人,ヒト,ヒト"),
+            ],
+            &ParseConfig::default(),
+        );
+        assert_eq!(words[0].word, "病気");
+    }
+}
@@ -0,0 +1,1197 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Tokenize, VibratoToken, DE, POS, TE};
+
+/// Layout and tag set of a dictionary's feature columns
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum DictionarySchema {
+    /// 9 columns: four POS levels, inflection type and form, lemma, reading, pronunciation
+    #[default]
+    Ipadic,
+    /// mecab-ipadic-NEologd, which has IPADIC's columns and tags and is handled the same way
+    Neologd,
+    /// 17 or more columns, with UniDic's own POS tags, lemma and pronunciation fields
+    Unidic,
+}
+
+/// A token whose features a [`FeatureSchema`] is asked about
+#[derive(Clone, Copy, Debug)]
+pub struct SchemaToken<'a> {
+    pub surface: &'a str,
+    /// The comma-separated features, padded with `*` to at least 6 columns
+    pub features: &'a [&'a str],
+    /// Surface and features of the token before it, if any
+    pub previous: Option<(&'a str, &'a [&'a str])>,
+}
+
+impl SchemaToken<'_> {
+    /// The feature in `column`, empty if the token doesn't have that many
+    pub fn column(&self, column: usize) -> &str {
+        self.features.get(column).copied().unwrap_or_default()
+    }
+}
+
+/// Main parts of speech, the way IPADIC divides them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WordClass {
+    Noun,
+    Prefix,
+    Verb,
+    Adjective,
+    Adverb,
+    Determiner,
+    Conjunction,
+    Particle,
+    Auxiliary,
+    Interjection,
+    Filler,
+    Symbol,
+    Other,
+}
+
+/// Kinds of nouns the grouping rules tell apart
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NounKind {
+    /// Anything not listed below
+    Common,
+    Proper,
+    Pronoun,
+    Number,
+    /// Nouns that don't stand on their own, like こと or the よう of ようだ
+    Dependent,
+    /// Like `Dependent`, for the few IPADIC files under 特殊 (そう of そうだ)
+    Special,
+    /// Suffixes like the たち of 子供たち
+    Suffix,
+    /// The さ of 高さ, which turns adjectives into nouns
+    NominalizingSuffix,
+    /// Nouns used like conjunctions (VS)
+    Conjunctive,
+    /// Nouns that act as dependent verbs (ちょうだい)
+    VerbalDependent,
+}
+
+/// What a noun is the stem of, for nouns that group with the words after them
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NounStem {
+    /// Verbs with する (勉強する)
+    Sahen,
+    /// Adjectives with な (静かな)
+    Adjectival,
+    /// Adjectives with ない (仕方ない)
+    NaiAdjective,
+    /// Adverbs with に (特に)
+    Adverbial,
+    /// Auxiliaries with だ (ようだ)
+    Auxiliary,
+}
+
+/// Kinds of names, for [`EntityOptions`](crate::EntityOptions)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NameKind {
+    Person,
+    Surname,
+    GivenName,
+    Organization,
+    Place,
+}
+
+/// Inflections of auxiliaries and verbs the grouping rules look at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Inflection {
+    /// する
+    Sahen,
+    Da,
+    Desu,
+    Ta,
+    Nai,
+    Tai,
+    Masu,
+    Nu,
+    /// Auxiliaries that don't inflect, like ん
+    Invariable,
+}
+
+/// Conjugation class of a verb (活用型)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VerbClass {
+    /// 五段, with the row of the kana the stem ends in, e.g. カ for 書く or ワ for 買う
+    Godan(char),
+    /// 一段, like 食べる or 見る
+    Ichidan,
+    /// サ変, する and verbs ending in it like 勉強する or 愛する
+    Suru,
+    /// カ変, 来る
+    Kuru,
+    /// Conjugations of classical Japanese (文語), like the 四段 or 二段 verbs
+    Classical,
+}
+
+/// The first six features of a token as the dictionary has them, before they're read into
+/// the simplified tags. `*` where the dictionary has no value.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RawTags {
+    /// e.g. 名詞
+    pub pos1: String,
+    /// e.g. 固有名詞
+    pub pos2: String,
+    /// e.g. 人名
+    pub pos3: String,
+    /// e.g. 姓
+    pub pos4: String,
+    /// e.g. 五段・カ行イ音便
+    pub inflection_type: String,
+    /// e.g. 連用タ接続
+    pub inflection_form: String,
+}
+
+impl RawTags {
+    pub(crate) fn from_features(features: &[&str]) -> Self {
+        let column = |i: usize| features.get(i).copied().unwrap_or("*").to_string();
+
+        Self {
+            pos1: column(0),
+            pos2: column(1),
+            pos3: column(2),
+            pos4: column(3),
+            inflection_type: column(4),
+            inflection_form: column(5),
+        }
+    }
+}
+
+/// Kinds of particles the grouping rules tell apart
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParticleKind {
+    /// て, で, ば and the like
+    Conjunctive,
+    /// は, も and the like
+    Binding,
+    /// The の after adjectival nouns
+    Adnominal,
+    /// The に after adjectival nouns
+    Adverbial,
+}
+
+/// Where a word comes from, UniDic's 語種 (goshu)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WordOrigin {
+    /// 和語, like 山 or 食べる
+    Native,
+    /// 漢語, like 勉強
+    SinoJapanese,
+    /// 外来語, like テレビ
+    Foreign,
+    /// 混種語, mixing the others, like 歯ブラシ
+    Mixed,
+    /// 固有名, names
+    Proper,
+    Symbol,
+}
+
+/// UniDic's columns beyond the ones IPADIC has too
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UnidicFields {
+    /// 語種
+    pub origin: Option<WordOrigin>,
+    /// The lexeme's spelling (語彙素), which variants like 行なう and 行う share. Without the
+    /// gloss UniDic adds to loanwords, so テレビ rather than テレビ-television.
+    pub lemma: Option<String>,
+    /// Dictionary form as written (書字形基本形), e.g. 行なう for 行なっ
+    pub orth_base: Option<String>,
+    /// Pronunciation of the dictionary form (発音形基本形)
+    pub pron_base: Option<String>,
+    /// Mora of the accent nucleus, 0 for flat words (アクセント型). UniDic 3 only, and
+    /// where it lists several accents, the first.
+    pub accent_type: Option<u8>,
+    /// How the accent changes when the word is attached to the one before it
+    /// (アクセント結合型), e.g. `C2` or `動詞%F2@1`. UniDic 3 only.
+    pub accent_connection: Option<String>,
+}
+
+/// Answers what the grouping rules need to know about a token from its features,
+/// so dictionaries with other feature layouts only need to implement this trait.
+///
+/// Everything is asked about tokens of the word class the question is about, e.g.
+/// `noun_kind` only about nouns. `IpadicSchema` and `UnidicSchema` are built in.
+pub trait FeatureSchema: Send + Sync {
+    /// None for parts of speech the schema doesn't know, which are reported
+    fn word_class(&self, token: &SchemaToken) -> Option<WordClass>;
+    fn noun_kind(&self, token: &SchemaToken) -> NounKind;
+    fn noun_stem(&self, token: &SchemaToken) -> Option<NounStem>;
+    /// For proper nouns and name suffixes like さん
+    fn name_kind(&self, token: &SchemaToken) -> Option<NameKind>;
+    /// Whether a verb or adjective depends on the word before it, like the いる of 食べている
+    fn is_dependent(&self, token: &SchemaToken) -> bool;
+    /// Whether a verb or adjective is a suffix, like the がる of 嫌がる
+    fn is_suffix(&self, token: &SchemaToken) -> bool;
+    fn particle_kind(&self, token: &SchemaToken) -> Option<ParticleKind>;
+    fn is_sentence_end(&self, token: &SchemaToken) -> bool;
+    fn inflection(&self, token: &SchemaToken) -> Option<Inflection>;
+    /// Whether the token is in the form that goes before nouns, like the な of 静かな
+    fn is_attributive(&self, token: &SchemaToken) -> bool;
+    fn is_imperative(&self, token: &SchemaToken) -> bool;
+
+    /// The dictionary form, empty or `*` if there's none
+    fn lemma<'a>(&self, token: &SchemaToken<'a>) -> &'a str;
+    /// The reading in katakana, empty or `*` if there's none
+    fn reading<'a>(&self, token: &SchemaToken<'a>) -> &'a str;
+    /// The pronunciation in katakana, empty or `*` if there's none
+    fn pronunciation<'a>(&self, token: &SchemaToken<'a>) -> &'a str;
+
+    /// Whether a noun suffix is a counter (助数詞), like the 枚 of 3枚
+    fn is_counter(&self, _token: &SchemaToken) -> bool {
+        false
+    }
+
+    /// The conjugation class of a verb, None for other schemas
+    fn verb_class(&self, _token: &SchemaToken) -> Option<VerbClass> {
+        None
+    }
+
+    /// Columns only UniDic has, None for other schemas
+    fn unidic_fields(&self, _token: &SchemaToken) -> Option<UnidicFields> {
+        None
+    }
+
+    /// Whether the answers above only depend on the first six columns, not on the surface or
+    /// the token before, so tokens with the same six columns can share their tags
+    fn tags_from_columns(&self) -> bool {
+        false
+    }
+}
+
+/// Main POS tags only UniDic uses
+const UNIDIC_POS: [&str; 6] = ["形状詞", "接頭辞", "接尾辞", "補助記号", "代名詞", "空白"];
+const UNIDIC_POS2: [&str; 3] = ["普通名詞", "数詞", "非自立可能"];
+const IPADIC_POS: [&str; 1] = ["接頭詞"];
+const IPADIC_POS2: [&str; 9] = [
+    "サ変接続",
+    "形容動詞語幹",
+    "自立",
+    "非自立",
+    "接尾",
+    "数",
+    "句点",
+    "連体化",
+    "副詞化",
+];
+
+/// IPADIC splits this band name up, NEologd has an entry for it
+const NEOLOGD_PROBE: &str = "ゲスの極み乙女";
+const PROBE: &str = "東京へ行って、静かな部屋で勉強しました。";
+
+impl DictionarySchema {
+    /// Guesses the schema from the features of some tokens, by the tags only one of the
+    /// dictionaries uses and by how many columns known words have.
+    /// None if the tokens don't give it away either way.
+    ///
+    /// NEologd can't be told apart from IPADIC by its features, see [`detect_schema`].
+    pub fn detect(tokens: &[VibratoToken]) -> Option<Self> {
+        let mut votes = 0i32;
+
+        for token in tokens {
+            let features = split_features(&token.feature);
+            let pos = features[0];
+            let pos2 = features.get(1).copied().unwrap_or_default();
+
+            if UNIDIC_POS.contains(&pos) || UNIDIC_POS2.contains(&pos2) {
+                votes += 1;
+            } else if IPADIC_POS.contains(&pos) || IPADIC_POS2.contains(&pos2) {
+                votes -= 1;
+            } else if !token.is_oov {
+                // Unknown words come with fewer columns in both
+                match features.len() {
+                    9 => votes -= 1,
+                    n if n >= 17 => votes += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        match votes.cmp(&0) {
+            Ordering::Greater => Some(Self::Unidic),
+            Ordering::Less => Some(Self::Ipadic),
+            Ordering::Equal => None,
+        }
+    }
+
+    /// The built-in schema that reads this layout
+    pub fn feature_schema(&self) -> &'static dyn FeatureSchema {
+        match self {
+            Self::Ipadic | Self::Neologd => &IpadicSchema,
+            Self::Unidic => &UnidicSchema,
+        }
+    }
+}
+
+/// Guesses the schema of a tokenizer's dictionary by tokenizing a probe sentence, e.g. right
+/// after loading it. NEologd is recognized by having an entry for a name IPADIC splits up.
+pub fn detect_schema(tokenizer: &(impl Tokenize + ?Sized)) -> Option<DictionarySchema> {
+    let schema = DictionarySchema::detect(&tokenizer.tokenize(PROBE).ok()?)?;
+
+    if schema == DictionarySchema::Ipadic {
+        let probe = tokenizer.tokenize(NEOLOGD_PROBE).ok()?;
+        if probe.len() == 1 && !probe[0].is_oov {
+            return Some(DictionarySchema::Neologd);
+        }
+    }
+
+    Some(schema)
+}
+
+/// The tags of a token as IPADIC would have them, which the grouping rules work with:
+/// pos1-4, inflection type and form. Whatever the schema has no answer for is read
+/// from the features like IPADIC tags, so pos overrides can still match them.
+pub(crate) fn tags(schema: &dyn FeatureSchema, token: &SchemaToken) -> [POS; 6] {
+    let mut tags = [0, 1, 2, 3, 4, 5].map(|column| POS::from(token.features[column]));
+
+    let Some(class) = schema.word_class(token) else {
+        return tags;
+    };
+    tags[0] = match class {
+        WordClass::Noun => POS::Meishi,
+        WordClass::Prefix => POS::Settoushi,
+        WordClass::Verb => POS::Doushi,
+        WordClass::Adjective => POS::Keiyoushi,
+        WordClass::Adverb => POS::Fukushi,
+        WordClass::Determiner => POS::Rentaishi,
+        WordClass::Conjunction => POS::Setsuzokushi,
+        WordClass::Particle => POS::Joshi,
+        WordClass::Auxiliary => POS::JoDoushi,
+        WordClass::Interjection => POS::Kandoushi,
+        WordClass::Filler => POS::Firaa,
+        WordClass::Symbol => POS::Kigou,
+        WordClass::Other => POS::Sonota,
+    };
+
+    match class {
+        WordClass::Noun => {
+            let stem = schema.noun_stem(token).map(|stem| match stem {
+                NounStem::Sahen => POS::Sahensetsuzoku,
+                NounStem::Adjectival => POS::Keiyoudoushigokan,
+                NounStem::NaiAdjective => POS::Naikeiyoushigokan,
+                NounStem::Adverbial => POS::Fukushikanou,
+                NounStem::Auxiliary => POS::Jodoushigokan,
+            });
+            let name = schema.name_kind(token);
+
+            match schema.noun_kind(token) {
+                NounKind::Common => {
+                    if let Some(stem) = stem {
+                        tags[1] = stem;
+                    }
+                }
+                NounKind::Proper => tags[1] = POS::KoyuuMeishi,
+                NounKind::Pronoun => tags[1] = POS::DaiMeishi,
+                NounKind::Number => tags[1] = POS::Kazu,
+                NounKind::Conjunctive => tags[1] = POS::Setsuzokushiteki,
+                NounKind::VerbalDependent => tags[1] = POS::Doushihijiritsuteki,
+                NounKind::NominalizingSuffix => {
+                    tags[1] = POS::Setsubi;
+                    tags[2] = POS::Tokushu;
+                }
+                kind @ (NounKind::Dependent | NounKind::Special | NounKind::Suffix) => {
+                    tags[1] = match kind {
+                        NounKind::Dependent => POS::Hijiritsu,
+                        NounKind::Special => POS::Tokushu,
+                        _ => POS::Setsubi,
+                    };
+                    if let Some(stem) = stem {
+                        tags[2] = stem;
+                    }
+                }
+            }
+
+            if let Some(name) = name {
+                tags[2] = match name {
+                    NameKind::Organization => POS::Soshiki,
+                    NameKind::Place => POS::Chiiki,
+                    _ => POS::Jinmei,
+                };
+                match name {
+                    NameKind::Surname => tags[3] = POS::Sei,
+                    NameKind::GivenName => tags[3] = POS::Mei,
+                    _ => {}
+                }
+            }
+        }
+        WordClass::Verb | WordClass::Adjective => {
+            if schema.is_suffix(token) {
+                tags[1] = POS::Setsubi;
+            } else if schema.is_dependent(token) {
+                tags[1] = POS::Hijiritsu;
+            }
+        }
+        WordClass::Particle => {
+            if let Some(kind) = schema.particle_kind(token) {
+                tags[1] = match kind {
+                    ParticleKind::Conjunctive => POS::Setsuzokujoshi,
+                    ParticleKind::Binding => POS::Kakarijoshi,
+                    ParticleKind::Adnominal => POS::Rentaika,
+                    ParticleKind::Adverbial => POS::Fukushika,
+                };
+            }
+        }
+        WordClass::Symbol if schema.is_sentence_end(token) => tags[1] = POS::Kuten,
+        _ => {}
+    }
+
+    if let Some(inflection) = schema.inflection(token) {
+        tags[4] = match inflection {
+            Inflection::Sahen => POS::SahenSuru,
+            Inflection::Da => POS::TokushuDa,
+            Inflection::Desu => POS::TokushuDesu,
+            Inflection::Ta => POS::TokushuTa,
+            Inflection::Nai => POS::TokushuNai,
+            Inflection::Tai => POS::TokushuTai,
+            Inflection::Masu => POS::TokushuMasu,
+            Inflection::Nu => POS::TokushuNu,
+            Inflection::Invariable => POS::Fuhenkagata,
+        };
+    }
+    if schema.is_attributive(token) {
+        tags[5] = POS::Taigensetsuzoku;
+    } else if schema.is_imperative(token) {
+        tags[5] = POS::MeireiI;
+    }
+
+    tags
+}
+
+/// The tags of the tokens of a sentence read so far, keyed by their six tag columns, so
+/// tokens of the same kind only have them read once. Only used with schemas whose tags
+/// come from those columns alone, see [`FeatureSchema::tags_from_columns`].
+#[derive(Default)]
+pub(crate) struct TagCache<'a> {
+    tags: HashMap<&'a str, [POS; 6]>,
+}
+
+impl<'a> TagCache<'a> {
+    /// The tags of `token`, whose feature string is `feature`
+    pub fn tags(
+        &mut self,
+        schema: &dyn FeatureSchema,
+        feature: &'a str,
+        token: &SchemaToken,
+    ) -> [POS; 6] {
+        match tag_prefix(feature) {
+            Some(prefix) if schema.tags_from_columns() => self
+                .tags
+                .entry(prefix)
+                .or_insert_with(|| tags(schema, token))
+                .clone(),
+            _ => tags(schema, token),
+        }
+    }
+}
+
+/// The part of a feature string with the six tag columns. None if one of them is quoted.
+fn tag_prefix(feature: &str) -> Option<&str> {
+    let end = feature
+        .match_indices(',')
+        .nth(5)
+        .map_or(feature.len(), |(i, _)| i);
+    let prefix = &feature[..end];
+
+    (!prefix.contains('"')).then_some(prefix)
+}
+
+/// IPADIC's tags and its 9 columns, also used for NEologd
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IpadicSchema;
+
+impl FeatureSchema for IpadicSchema {
+    fn word_class(&self, token: &SchemaToken) -> Option<WordClass> {
+        Some(match token.column(0) {
+            "名詞" => WordClass::Noun,
+            "接頭詞" => WordClass::Prefix,
+            "動詞" => WordClass::Verb,
+            "形容詞" => WordClass::Adjective,
+            "副詞" => WordClass::Adverb,
+            "連体詞" => WordClass::Determiner,
+            "接続詞" => WordClass::Conjunction,
+            "助詞" => WordClass::Particle,
+            "助動詞" => WordClass::Auxiliary,
+            "感動詞" => WordClass::Interjection,
+            "フィラー" => WordClass::Filler,
+            "記号" => WordClass::Symbol,
+            "その他" => WordClass::Other,
+            _ => return None,
+        })
+    }
+
+    fn noun_kind(&self, token: &SchemaToken) -> NounKind {
+        match (token.column(1), token.column(2)) {
+            ("固有名詞", _) => NounKind::Proper,
+            ("代名詞", _) => NounKind::Pronoun,
+            ("数", _) => NounKind::Number,
+            ("非自立", _) => NounKind::Dependent,
+            ("特殊", _) => NounKind::Special,
+            ("接尾", "特殊") => NounKind::NominalizingSuffix,
+            ("接尾", _) => NounKind::Suffix,
+            ("接続詞的", _) => NounKind::Conjunctive,
+            ("動詞非自立的", _) => NounKind::VerbalDependent,
+            _ => NounKind::Common,
+        }
+    }
+
+    fn noun_stem(&self, token: &SchemaToken) -> Option<NounStem> {
+        [token.column(1), token.column(2)]
+            .into_iter()
+            .find_map(|tag| match tag {
+                "サ変接続" => Some(NounStem::Sahen),
+                "形容動詞語幹" => Some(NounStem::Adjectival),
+                "ナイ形容詞語幹" => Some(NounStem::NaiAdjective),
+                "副詞可能" => Some(NounStem::Adverbial),
+                "助動詞語幹" => Some(NounStem::Auxiliary),
+                _ => None,
+            })
+    }
+
+    fn name_kind(&self, token: &SchemaToken) -> Option<NameKind> {
+        match (token.column(2), token.column(3)) {
+            ("人名", "姓") => Some(NameKind::Surname),
+            ("人名", "名") => Some(NameKind::GivenName),
+            ("人名", _) => Some(NameKind::Person),
+            ("組織", _) => Some(NameKind::Organization),
+            ("地域", _) => Some(NameKind::Place),
+            _ => None,
+        }
+    }
+
+    fn is_dependent(&self, token: &SchemaToken) -> bool {
+        token.column(1) == "非自立"
+    }
+
+    fn is_suffix(&self, token: &SchemaToken) -> bool {
+        token.column(1) == "接尾"
+    }
+
+    fn is_counter(&self, token: &SchemaToken) -> bool {
+        (token.column(1), token.column(2)) == ("接尾", "助数詞")
+    }
+
+    fn verb_class(&self, token: &SchemaToken) -> Option<VerbClass> {
+        let conjugation = token.column(4);
+        if let Some(row) = conjugation.strip_prefix("五段・") {
+            row.chars().next().map(VerbClass::Godan)
+        } else if conjugation.starts_with("一段") {
+            Some(VerbClass::Ichidan)
+        } else if conjugation.starts_with("サ変") {
+            Some(VerbClass::Suru)
+        } else if conjugation.starts_with("カ変") {
+            Some(VerbClass::Kuru)
+        } else if ["四段", "上二", "下二", "文語"]
+            .iter()
+            .any(|classical| conjugation.starts_with(classical))
+        {
+            Some(VerbClass::Classical)
+        } else {
+            None
+        }
+    }
+
+    fn particle_kind(&self, token: &SchemaToken) -> Option<ParticleKind> {
+        match token.column(1) {
+            "接続助詞" => Some(ParticleKind::Conjunctive),
+            "係助詞" => Some(ParticleKind::Binding),
+            "連体化" => Some(ParticleKind::Adnominal),
+            "副詞化" => Some(ParticleKind::Adverbial),
+            _ => None,
+        }
+    }
+
+    fn is_sentence_end(&self, token: &SchemaToken) -> bool {
+        token.column(1) == "句点"
+    }
+
+    fn inflection(&self, token: &SchemaToken) -> Option<Inflection> {
+        match token.column(4) {
+            "サ変・スル" => Some(Inflection::Sahen),
+            "特殊・ダ" => Some(Inflection::Da),
+            "特殊・デス" => Some(Inflection::Desu),
+            "特殊・タ" => Some(Inflection::Ta),
+            "特殊・ナイ" => Some(Inflection::Nai),
+            "特殊・タイ" => Some(Inflection::Tai),
+            "特殊・マス" => Some(Inflection::Masu),
+            "特殊・ヌ" => Some(Inflection::Nu),
+            "不変化型" => Some(Inflection::Invariable),
+            _ => None,
+        }
+    }
+
+    fn is_attributive(&self, token: &SchemaToken) -> bool {
+        token.column(5) == "体言接続"
+    }
+
+    fn is_imperative(&self, token: &SchemaToken) -> bool {
+        token.column(5) == "命令ｉ"
+    }
+
+    fn lemma<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        token.features.get(6).copied().unwrap_or_default()
+    }
+
+    fn reading<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        token.features.get(7).copied().unwrap_or_default()
+    }
+
+    fn pronunciation<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        token.features.get(8).copied().unwrap_or_default()
+    }
+
+    fn tags_from_columns(&self) -> bool {
+        true
+    }
+}
+
+/// UniDic's tags and columns (pos1-4, cType, cForm, lForm, lemma, orth, pron, orthBase,
+/// pronBase, goshu, ...), as in unidic-cwj and unidic-csj
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UnidicSchema;
+
+impl FeatureSchema for UnidicSchema {
+    fn word_class(&self, token: &SchemaToken) -> Option<WordClass> {
+        Some(match (token.column(0), token.column(1)) {
+            ("名詞" | "代名詞" | "形状詞", _) => WordClass::Noun,
+            ("接尾辞", "動詞的") => WordClass::Verb,
+            ("接尾辞", "形容詞的") => WordClass::Adjective,
+            ("接尾辞", _) => WordClass::Noun,
+            ("接頭辞", _) => WordClass::Prefix,
+            ("動詞", _) => WordClass::Verb,
+            ("形容詞", _) => WordClass::Adjective,
+            ("副詞", _) => WordClass::Adverb,
+            ("連体詞", _) => WordClass::Determiner,
+            ("接続詞", _) => WordClass::Conjunction,
+            ("助詞", _) => WordClass::Particle,
+            ("助動詞", _) => WordClass::Auxiliary,
+            ("感動詞", "フィラー") => WordClass::Filler,
+            ("感動詞", _) => WordClass::Interjection,
+            ("補助記号" | "記号" | "空白", _) => WordClass::Symbol,
+            _ => return None,
+        })
+    }
+
+    fn noun_kind(&self, token: &SchemaToken) -> NounKind {
+        match (token.column(0), token.column(1)) {
+            ("代名詞", _) => NounKind::Pronoun,
+            ("接尾辞", _) => NounKind::Suffix,
+            // Nouns like 人 or 枚 that count when they follow a number
+            _ if self.is_counter(token) => NounKind::Suffix,
+            (_, "固有名詞") => NounKind::Proper,
+            (_, "数詞") => NounKind::Number,
+            (_, "助動詞語幹") => NounKind::Dependent,
+            _ => NounKind::Common,
+        }
+    }
+
+    fn noun_stem(&self, token: &SchemaToken) -> Option<NounStem> {
+        match (token.column(0), token.column(1), token.column(2)) {
+            (_, "助動詞語幹", _) => Some(NounStem::Auxiliary),
+            ("形状詞", _, _) => Some(NounStem::Adjectival),
+            (_, _, "サ変可能" | "サ変形状詞可能") => Some(NounStem::Sahen),
+            (_, _, "形状詞可能") => Some(NounStem::Adjectival),
+            (_, _, "副詞可能") => Some(NounStem::Adverbial),
+            _ => None,
+        }
+    }
+
+    fn name_kind(&self, token: &SchemaToken) -> Option<NameKind> {
+        match (token.column(1), token.column(2), token.column(3)) {
+            ("固有名詞", "人名", "姓") => Some(NameKind::Surname),
+            ("固有名詞", "人名", "名") => Some(NameKind::GivenName),
+            ("固有名詞", "人名", _) => Some(NameKind::Person),
+            ("固有名詞", "地名", _) => Some(NameKind::Place),
+            // さん and the like, which IPADIC files under 人名
+            ("名詞的", _, _) => token
+                .previous
+                .is_some_and(|(_, features)| features.get(2) == Some(&"人名"))
+                .then_some(NameKind::Person),
+            _ => None,
+        }
+    }
+
+    /// UniDic tags verbs like いる as possibly dependent wherever they're used,
+    /// IPADIC only after the て form
+    fn is_dependent(&self, token: &SchemaToken) -> bool {
+        token.column(1) == "非自立可能"
+            && token.previous.is_some_and(|(surface, features)| {
+                features.get(1) == Some(&"接続助詞") && [TE, DE].contains(&surface)
+            })
+    }
+
+    fn is_suffix(&self, token: &SchemaToken) -> bool {
+        token.column(0) == "接尾辞"
+    }
+
+    /// Counters are suffixes in UniDic, or nouns that can be counters after a number
+    fn is_counter(&self, token: &SchemaToken) -> bool {
+        match (token.column(0), token.column(2)) {
+            ("接尾辞", "助数詞") => true,
+            ("名詞", "助数詞可能") => token
+                .previous
+                .is_some_and(|(_, features)| features.get(1) == Some(&"数詞")),
+            _ => false,
+        }
+    }
+
+    fn verb_class(&self, token: &SchemaToken) -> Option<VerbClass> {
+        let conjugation = token.column(4);
+        if let Some(row) = conjugation.strip_prefix("五段-") {
+            row.chars().next().map(VerbClass::Godan)
+        } else if conjugation.starts_with("上一段") || conjugation.starts_with("下一段") {
+            Some(VerbClass::Ichidan)
+        } else if conjugation == "サ行変格" {
+            Some(VerbClass::Suru)
+        } else if conjugation == "カ行変格" {
+            Some(VerbClass::Kuru)
+        } else if conjugation.starts_with("文語") {
+            Some(VerbClass::Classical)
+        } else {
+            None
+        }
+    }
+
+    fn particle_kind(&self, token: &SchemaToken) -> Option<ParticleKind> {
+        match token.column(1) {
+            "接続助詞" => Some(ParticleKind::Conjunctive),
+            "係助詞" => Some(ParticleKind::Binding),
+            _ => None,
+        }
+    }
+
+    fn is_sentence_end(&self, token: &SchemaToken) -> bool {
+        token.column(1) == "句点"
+    }
+
+    fn inflection(&self, token: &SchemaToken) -> Option<Inflection> {
+        match token.column(4) {
+            "サ行変格" => Some(Inflection::Sahen),
+            "助動詞-ダ" => Some(Inflection::Da),
+            "助動詞-デス" => Some(Inflection::Desu),
+            "助動詞-タ" => Some(Inflection::Ta),
+            "助動詞-ナイ" => Some(Inflection::Nai),
+            "助動詞-タイ" => Some(Inflection::Tai),
+            "助動詞-マス" => Some(Inflection::Masu),
+            "助動詞-ヌ" => Some(Inflection::Nu),
+            "無変化型" => Some(Inflection::Invariable),
+            _ => None,
+        }
+    }
+
+    fn is_attributive(&self, token: &SchemaToken) -> bool {
+        token.column(5).starts_with("連体形")
+    }
+
+    fn is_imperative(&self, token: &SchemaToken) -> bool {
+        token.column(5).starts_with("命令形")
+    }
+
+    /// orthBase, the lemma as written, unlike the lemma column's 為る for する
+    fn lemma<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        match token.features.get(10).copied() {
+            None | Some("" | "*") => token.features.get(7).copied().unwrap_or_default(),
+            Some(orth_base) => orth_base,
+        }
+    }
+
+    /// UniDic 3 has the reading as kana, older versions only the pronunciation
+    fn reading<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        match token.features.get(20).copied() {
+            None | Some("" | "*") => self.pronunciation(token),
+            Some(kana) => kana,
+        }
+    }
+
+    fn pronunciation<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+        token.features.get(9).copied().unwrap_or_default()
+    }
+
+    fn unidic_fields(&self, token: &SchemaToken) -> Option<UnidicFields> {
+        let column = |index| match token.column(index) {
+            "" | "*" => None,
+            value => Some(value),
+        };
+
+        Some(UnidicFields {
+            origin: column(12).and_then(|goshu| match goshu {
+                "和" => Some(WordOrigin::Native),
+                "漢" => Some(WordOrigin::SinoJapanese),
+                "外" => Some(WordOrigin::Foreign),
+                "混" => Some(WordOrigin::Mixed),
+                "固" => Some(WordOrigin::Proper),
+                "記号" => Some(WordOrigin::Symbol),
+                _ => None,
+            }),
+            lemma: column(7).map(|lemma| match lemma.split_once('-') {
+                Some((lemma, gloss)) if gloss.is_ascii() => lemma.into(),
+                _ => lemma.into(),
+            }),
+            orth_base: column(10).map(Into::into),
+            pron_base: column(11).map(Into::into),
+            accent_type: column(24)
+                .and_then(|accent| accent.split(',').next())
+                .and_then(|accent| accent.parse().ok()),
+            accent_connection: column(25).map(Into::into),
+        })
+    }
+}
+
+/// Splits a feature string into its columns. Columns with commas in them are quoted,
+/// like UniDic's `","` for the comma itself or accents like `"1,0"`.
+pub(crate) fn split_features(feature: &str) -> Vec<&str> {
+    let mut features = Vec::new();
+    let mut rest = feature;
+
+    loop {
+        if let Some(quoted) = rest.strip_prefix('"') {
+            match quoted.find("\",") {
+                Some(end) => {
+                    features.push(&quoted[..end]);
+                    rest = &quoted[end + 2..];
+                    continue;
+                }
+                None if quoted.len() > 1 && quoted.ends_with('"') => {
+                    features.push(&quoted[..quoted.len() - 1]);
+                    return features;
+                }
+                // A lone quote, not a quoted column
+                None => {}
+            }
+        }
+
+        match rest.split_once(',') {
+            Some((column, tail)) => {
+                features.push(column);
+                rest = tail;
+            }
+            None => {
+                features.push(rest);
+                return features;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::testing::{words_from, Lexicon};
+    use crate::{Hook, ParseConfig, PartOfSpeech, Word};
+
+    /// IPADIC's schema, counting how often a token's tags are read
+    #[derive(Default)]
+    struct CountingSchema(AtomicUsize);
+
+    impl FeatureSchema for CountingSchema {
+        fn word_class(&self, token: &SchemaToken) -> Option<WordClass> {
+            self.0.fetch_add(1, Ordering::Relaxed);
+            IpadicSchema.word_class(token)
+        }
+        fn noun_kind(&self, token: &SchemaToken) -> NounKind {
+            IpadicSchema.noun_kind(token)
+        }
+        fn noun_stem(&self, token: &SchemaToken) -> Option<NounStem> {
+            IpadicSchema.noun_stem(token)
+        }
+        fn name_kind(&self, token: &SchemaToken) -> Option<NameKind> {
+            IpadicSchema.name_kind(token)
+        }
+        fn is_dependent(&self, token: &SchemaToken) -> bool {
+            IpadicSchema.is_dependent(token)
+        }
+        fn is_suffix(&self, token: &SchemaToken) -> bool {
+            IpadicSchema.is_suffix(token)
+        }
+        fn particle_kind(&self, token: &SchemaToken) -> Option<ParticleKind> {
+            IpadicSchema.particle_kind(token)
+        }
+        fn is_sentence_end(&self, token: &SchemaToken) -> bool {
+            IpadicSchema.is_sentence_end(token)
+        }
+        fn inflection(&self, token: &SchemaToken) -> Option<Inflection> {
+            IpadicSchema.inflection(token)
+        }
+        fn is_attributive(&self, token: &SchemaToken) -> bool {
+            IpadicSchema.is_attributive(token)
+        }
+        fn is_imperative(&self, token: &SchemaToken) -> bool {
+            IpadicSchema.is_imperative(token)
+        }
+        fn lemma<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+            IpadicSchema.lemma(token)
+        }
+        fn reading<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+            IpadicSchema.reading(token)
+        }
+        fn pronunciation<'a>(&self, token: &SchemaToken<'a>) -> &'a str {
+            IpadicSchema.pronunciation(token)
+        }
+        fn tags_from_columns(&self) -> bool {
+            true
+        }
+    }
+
+    fn read_tags<'a>(
+        cache: &mut TagCache<'a>,
+        schema: &dyn FeatureSchema,
+        surface: &str,
+        feature: &'a str,
+    ) -> [POS; 6] {
+        let features = split_features(feature);
+        let token = SchemaToken {
+            surface,
+            features: &features,
+            previous: None,
+        };
+
+        cache.tags(schema, feature, &token)
+    }
+
+    #[test]
+    fn reads_the_tags_of_tokens_of_the_same_kind_once() {
+        let schema = CountingSchema::default();
+        let mut cache = TagCache::default();
+
+        let cat = read_tags(&mut cache, &schema, "猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ");
+        let town = read_tags(&mut cache, &schema, "町", "名詞,一般,*,*,*,*,町,マチ,マチ");
+        assert_eq!(cat, town);
+        assert_eq!(cat[0], POS::Meishi);
+        assert_eq!(schema.0.load(Ordering::Relaxed), 1);
+
+        read_tags(&mut cache, &schema, "が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ");
+        assert_eq!(schema.0.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn reads_the_tags_of_every_token_for_schemas_with_context() {
+        let mut cache = TagCache::default();
+        let feature = "名詞,普通名詞,一般,*,*,*,ネコ,猫,猫,ネコ,猫,ネコ,和,*,*,*,*";
+
+        read_tags(&mut cache, &UnidicSchema, "猫", feature);
+        assert!(cache.tags.is_empty());
+    }
+
+    fn token(feature: &str, is_oov: bool) -> VibratoToken {
+        VibratoToken {
+            surface: String::new(),
+            feature: feature.into(),
+            is_oov,
+            range: 0..0,
+        }
+    }
+
+    /// `Lexicon` with an entry for the name only NEologd has
+    struct Neologd;
+
+    impl Tokenize for Neologd {
+        fn tokenize(&self, text: &str) -> crate::Result<Vec<VibratoToken>> {
+            if text == NEOLOGD_PROBE {
+                return Ok(vec![token(
+                    "名詞,固有名詞,一般,*,*,*,ゲスの極み乙女,*,*",
+                    false,
+                )]);
+            }
+            Lexicon.tokenize(text)
+        }
+    }
+
+    #[test]
+    fn detects_the_schema_from_tags_and_columns() {
+        let ipadic = [
+            token("名詞,サ変接続,*,*,*,*,勉強,ベンキョウ,ベンキョー", false),
+            token("名詞,一般,*,*,*,*,猫,ネコ,ネコ", false),
+        ];
+        let unidic = [
+            token("名詞,普通名詞,一般,*,*,*,ネコ,猫,猫,ネコ,猫,ネコ,和,*,*,*,*", false),
+            token("動詞,一般,*,*,下一段-バ行,連用形-一般,タベル,食べる,食べ,タベ,食べる,タベル,和,*,*,*,*", false),
+        ];
+
+        assert_eq!(
+            DictionarySchema::detect(&ipadic),
+            Some(DictionarySchema::Ipadic)
+        );
+        assert_eq!(
+            DictionarySchema::detect(&unidic),
+            Some(DictionarySchema::Unidic)
+        );
+        assert_eq!(
+            DictionarySchema::detect(&[token("名詞,一般,*,*,*,*,*", true)]),
+            None
+        );
+    }
+
+    #[test]
+    fn detects_neologd_by_its_names() {
+        assert_eq!(detect_schema(&Lexicon), Some(DictionarySchema::Ipadic));
+        assert_eq!(detect_schema(&Neologd), Some(DictionarySchema::Neologd));
+    }
+
+    const UNIDIC: [(&str, &str); 4] = [
+        ("勉強", "名詞,普通名詞,サ変可能,*,*,*,ベンキョウ,勉強,勉強,ベンキョー,勉強,ベンキョー,漢,*,*,*,*"),
+        ("し", "動詞,非自立可能,*,*,サ行変格,連用形-一般,為る,する,し,シ,する,スル,和,*,*,*,*"),
+        ("まし", "助動詞,*,*,*,助動詞-マス,連用形-一般,ます,ます,まし,マシ,ます,マス,和,*,*,*,*"),
+        ("た", "助動詞,*,*,*,助動詞-タ,終止形-一般,た,た,た,タ,た,タ,和,*,*,*,*"),
+    ];
+
+    #[test]
+    fn reads_unidic_tags_as_ipadic_tags() {
+        let features = split_features(UNIDIC[0].1);
+        let token = SchemaToken {
+            surface: "勉強",
+            features: &features,
+            previous: None,
+        };
+        let [pos, pos2, ..] = tags(&UnidicSchema, &token);
+        assert_eq!((pos, pos2), (POS::Meishi, POS::Sahensetsuzoku));
+
+        let features = split_features(UNIDIC[1].1);
+        let token = SchemaToken {
+            surface: "し",
+            features: &features,
+            previous: None,
+        };
+        assert_eq!(tags(&UnidicSchema, &token)[4], POS::SahenSuru);
+    }
+
+    #[test]
+    fn groups_words_read_through_any_schema() {
+        let config = ParseConfig {
+            schema: Some(DictionarySchema::Unidic),
+            ..Default::default()
+        };
+        let words = words_from(&UNIDIC, &config);
+
+        assert_eq!(words.len(), 1);
+        assert_eq!(words[0].word, "勉強しました");
+        assert_eq!(words[0].part_of_speech, PartOfSpeech::Verb);
+    }
+
+    #[test]
+    fn reads_tokens_with_the_configured_schema() {
+        let schema = Arc::new(CountingSchema::default());
+        let config = ParseConfig {
+            feature_schema: Some(Hook(schema.clone())),
+            ..Default::default()
+        };
+
+        words_from(&[("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ")], &config);
+        assert_eq!(schema.0.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn splits_quoted_columns() {
+        assert_eq!(split_features("a,\"1,0\",b"), ["a", "1,0", "b"]);
+        assert_eq!(
+            split_features("補助記号,読点,*,*,*,*,\",\""),
+            ["補助記号", "読点", "*", "*", "*", "*", ","]
+        );
+        assert_eq!(split_features("\",a"), ["\"", "a"]);
+    }
+
+    #[test]
+    fn reads_unidic_fields() {
+        let config = ParseConfig {
+            schema: Some(DictionarySchema::Unidic),
+            ..Default::default()
+        };
+        let feature =
+            "名詞,普通名詞,一般,*,*,*,テレビ,テレビ-television,テレビ,テレビ,テレビ,テレビ,外,\
+                       *,*,*,*,*,*,体,テレビ,テレビ,テレビ,テレビ,\"1,0\",C1,*,1,2";
+        let words = words_from(&[("テレビ", feature)], &config);
+
+        assert_eq!(
+            words[0].tokens[0].unidic(),
+            Some(&UnidicFields {
+                origin: Some(WordOrigin::Foreign),
+                lemma: Some("テレビ".into()),
+                orth_base: Some("テレビ".into()),
+                pron_base: Some("テレビ".into()),
+                accent_type: Some(1),
+                accent_connection: Some("C1".into())
+            })
+        );
+    }
+
+    #[test]
+    fn has_no_unidic_fields_with_other_schemas() {
+        let words = words_from(
+            &[("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ")],
+            &ParseConfig::default(),
+        );
+
+        assert_eq!(words[0].tokens[0].unidic(), None);
+    }
+
+    #[test]
+    fn reads_the_conjugation_class_of_verbs() {
+        let classes = words_from(
+            &[
+                (
+                    "書く",
+                    "動詞,自立,*,*,五段・カ行イ音便,基本形,書く,カク,カク",
+                ),
+                ("。", "記号,句点,*,*,*,*,。,。,。"),
+                ("見る", "動詞,自立,*,*,一段,基本形,見る,ミル,ミル"),
+                ("。", "記号,句点,*,*,*,*,。,。,。"),
+                ("来る", "動詞,自立,*,*,カ変・来ル,基本形,来る,クル,クル"),
+                ("。", "記号,句点,*,*,*,*,。,。,。"),
+                ("愛す", "動詞,自立,*,*,五段・サ行,基本形,愛す,アイス,アイス"),
+                ("。", "記号,句点,*,*,*,*,。,。,。"),
+                ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+            ],
+            &ParseConfig::default(),
+        )
+        .iter()
+        .map(Word::verb_class)
+        .collect::<Vec<_>>();
+
+        assert_eq!(
+            classes,
+            [
+                Some(VerbClass::Godan('カ')),
+                None,
+                Some(VerbClass::Ichidan),
+                None,
+                Some(VerbClass::Kuru),
+                None,
+                Some(VerbClass::Godan('サ')),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_the_conjugation_class_of_unidic_verbs() {
+        let config = ParseConfig {
+            schema: Some(DictionarySchema::Unidic),
+            ..Default::default()
+        };
+        let words = words_from(&UNIDIC, &config);
+        assert_eq!(words[0].verb_class(), Some(VerbClass::Suru));
+
+        let words = words_from(
+            &[(
+                "食べ",
+                "動詞,一般,*,*,下一段-バ行,連用形-一般,タベル,食べる,食べ,タベ,食べる,タベル,和,*,*,*,*",
+            )],
+            &config,
+        );
+        assert_eq!(words[0].verb_class(), Some(VerbClass::Ichidan));
+    }
+}
@@ -0,0 +1,217 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::known::lemma;
+use crate::{
+    parse_text, ExpansionFilter, FilterChain, KanaFilter, ParseConfig, PartOfSpeech, Result,
+    Tokenize,
+};
+
+/// A term for an inverted index, in the shape of Lucene's token attributes
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SearchToken {
+    /// What gets indexed: the surface of the word, as changed by the filters
+    pub term: String,
+    /// The word as it is in the text
+    pub surface: String,
+    /// The lemma of the word, for `LemmaFilter`
+    pub lemma: String,
+    /// Readings of the word and its lemma, for `ExpansionFilter`
+    pub reading: String,
+    pub lemma_reading: Option<String>,
+    /// Byte offset of the word in the text
+    pub start: usize,
+    pub end: usize,
+    /// Positions since the previous token, more than 1 where tokens were removed
+    /// and 0 for tokens stacked on the previous one
+    pub position_increment: usize,
+    /// Position of the token, counting removed tokens
+    pub position: usize,
+    pub part_of_speech: PartOfSpeech,
+}
+
+/// Parses `text` into terms for a search index and runs them through `filters`.
+/// Symbols and whitespace are dropped without taking up a position, tokens removed by
+/// the filters leave a gap like in Lucene.
+pub fn search_tokens(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+    filters: &FilterChain,
+) -> Result<Vec<SearchToken>> {
+    let mut tokens = Vec::new();
+
+    for sentence in parse_text(tokenizer, text, config)? {
+        for word in &sentence.words {
+            if word.part_of_speech == PartOfSpeech::Symbol || word.word.trim().is_empty() {
+                continue;
+            }
+
+            let range = word.range();
+            tokens.push(SearchToken {
+                term: word.word.clone(),
+                surface: word.word.clone(),
+                lemma: lemma(word).into(),
+                reading: word.extra.reading.clone(),
+                lemma_reading: word.extra.lemma_reading.clone(),
+                start: range.start,
+                end: range.end,
+                position_increment: 1,
+                position: tokens.len(),
+                part_of_speech: word.part_of_speech,
+            });
+        }
+    }
+
+    Ok(filters.apply(tokens))
+}
+
+/// Whether text is analyzed for the index or for a query
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum AnalysisMode {
+    Index,
+    Query,
+}
+
+/// How text is parsed and filtered in one `AnalysisMode`
+#[derive(Clone, Debug, Default)]
+pub struct AnalysisProfile {
+    pub config: ParseConfig,
+    pub filters: FilterChain,
+}
+
+/// Separate profiles for indexing and querying, e.g. to expand readings or keep
+/// compounds split (with grouping rules turned off) only in the index.
+///
+/// By default both lemmatize, remove stopwords and write kana in katakana, and the index
+/// also stacks the surfaces and readings of words, so queries in kana find kanji.
+#[derive(Clone, Debug)]
+pub struct SearchAnalyzer {
+    pub index: AnalysisProfile,
+    pub query: AnalysisProfile,
+}
+
+impl Default for SearchAnalyzer {
+    fn default() -> Self {
+        let mut index = AnalysisProfile::default();
+        index
+            .filters
+            .push(ExpansionFilter::default())
+            .push(KanaFilter::Katakana);
+
+        let mut query = AnalysisProfile::default();
+        query.filters.push(KanaFilter::Katakana);
+
+        Self { index, query }
+    }
+}
+
+impl SearchAnalyzer {
+    pub fn profile(&self, mode: AnalysisMode) -> &AnalysisProfile {
+        match mode {
+            AnalysisMode::Index => &self.index,
+            AnalysisMode::Query => &self.query,
+        }
+    }
+
+    /// Search tokens of `text` with the profile of `mode`
+    pub fn analyze(
+        &self,
+        tokenizer: &(impl Tokenize + ?Sized),
+        text: &str,
+        mode: AnalysisMode,
+    ) -> Result<Vec<SearchToken>> {
+        let profile = self.profile(mode);
+        search_tokens(tokenizer, text, &profile.config, &profile.filters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+
+    #[test]
+    fn gives_words_positions_and_offsets() {
+        let tokens = search_tokens(
+            &Lexicon,
+            "猫が嘘。 食べました",
+            &ParseConfig::default(),
+            &FilterChain::new(),
+        )
+        .unwrap();
+
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|token| (token.term.as_str(), token.start..token.end, token.position))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                ("猫", 0..3, 0),
+                ("が", 3..6, 1),
+                ("嘘", 6..9, 2),
+                ("食べました", 13..28, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_lemmas_and_readings() {
+        let tokens = search_tokens(
+            &Lexicon,
+            "食べました",
+            &ParseConfig::default(),
+            &FilterChain::new(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            tokens,
+            [SearchToken {
+                term: "食べました".into(),
+                surface: "食べました".into(),
+                lemma: "食べる".into(),
+                reading: "タベマシタ".into(),
+                lemma_reading: Some("タベル".into()),
+                start: 0,
+                end: 15,
+                position_increment: 1,
+                position: 0,
+                part_of_speech: PartOfSpeech::Verb
+            }]
+        );
+    }
+
+    #[test]
+    fn expands_readings_only_in_the_index() {
+        let analyzer = SearchAnalyzer::default();
+        let terms = |mode| -> Vec<String> {
+            analyzer
+                .analyze(&Lexicon, "嘘が", mode)
+                .unwrap()
+                .into_iter()
+                .map(|token| token.term)
+                .collect()
+        };
+
+        assert_eq!(terms(AnalysisMode::Index), ["嘘", "ウソ"]);
+        assert_eq!(terms(AnalysisMode::Query), ["嘘"]);
+    }
+
+    #[test]
+    fn parses_each_mode_with_its_own_config() {
+        let mut analyzer = SearchAnalyzer::default();
+        analyzer.query.config.reading_overrides.insert("嘘", "ホラ");
+        analyzer.query.filters.push(ExpansionFilter::default());
+
+        let tokens = analyzer
+            .analyze(&Lexicon, "嘘", AnalysisMode::Query)
+            .unwrap();
+        assert_eq!(tokens[1].term, "ホラ");
+        assert_eq!(analyzer.profile(AnalysisMode::Index).filters.len(), 4);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::ruby::push_escaped;
+use crate::{PartOfSpeech, Word};
+
+/// Writes `sentence` as HTML with each word wrapped in a span with its part of speech as
+/// class and its lemma and reading as data attributes, so web apps can color words and
+/// attach popups to them:
+///
+/// ```html
+/// <span class="pos-verb" data-lemma="食べる" data-reading="タベタ">食べた</span>
+/// ```
+///
+/// The class is `pos-` and the [`PartOfSpeech`] in kebab case, like `pos-proper-noun`.
+/// `words` need to have been parsed from `sentence` like for [`ruby_html`](crate::ruby_html).
+pub fn spans_html(sentence: &str, words: &[Word]) -> String {
+    let mut output = String::with_capacity(sentence.len() * 4);
+    let mut position = 0;
+
+    for word in words {
+        let range = word.range();
+        if range.start < position || sentence.get(range.clone()) != Some(word.word.as_str()) {
+            continue;
+        }
+        push_escaped(&mut output, &sentence[position..range.start]);
+        position = range.end;
+
+        output.push_str("<span class=\"");
+        output.push_str(&pos_class(word.part_of_speech));
+        output.push_str("\" data-lemma=\"");
+        push_escaped(&mut output, word.lemma.as_deref().unwrap_or(&word.word));
+        output.push_str("\" data-reading=\"");
+        push_escaped(&mut output, &word.extra.reading);
+        output.push_str("\">");
+        push_escaped(&mut output, &word.word);
+        output.push_str("</span>");
+    }
+    push_escaped(&mut output, &sentence[position..]);
+
+    output
+}
+
+/// `pos-proper-noun` for `ProperNoun`
+fn pos_class(pos: PartOfSpeech) -> String {
+    let mut class = String::from("pos");
+    for c in format!("{:?}", pos).chars() {
+        if c.is_ascii_uppercase() {
+            class.push('-');
+        }
+        class.push(c.to_ascii_lowercase());
+    }
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    #[test]
+    fn wraps_each_word_in_a_span() {
+        assert_eq!(
+            spans_html("猫が <食べました>", &words("猫が <食べました>")),
+            "<span class=\"pos-noun\" data-lemma=\"猫\" data-reading=\"ネコ\">猫</span>\
+             <span class=\"pos-postposition\" data-lemma=\"が\" data-reading=\"ガ\">が</span> \
+             <span class=\"pos-symbol\" data-lemma=\"&lt;\" data-reading=\"\">&lt;</span>\
+             <span class=\"pos-verb\" data-lemma=\"食べる\" data-reading=\"タベマシタ\">食べました</span>\
+             <span class=\"pos-symbol\" data-lemma=\"&gt;\" data-reading=\"\">&gt;</span>"
+        );
+    }
+
+    #[test]
+    fn writes_the_part_of_speech_in_kebab_case() {
+        assert_eq!(pos_class(PartOfSpeech::Noun), "pos-noun");
+        assert_eq!(pos_class(PartOfSpeech::ProperNoun), "pos-proper-noun");
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PartOfSpeech, Sentence, Word};
+
+/// A word to learn, with the sentence it came up in
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Card {
+    pub lemma: String,
+    /// Reading of the lemma, or of the word if the lemma's isn't known
+    pub reading: String,
+    pub part_of_speech: PartOfSpeech,
+    /// The sentence as it is in the text
+    pub sentence: String,
+    /// The sentence with the word marked, e.g. 学校へ<b>行った</b>
+    pub context: String,
+}
+
+/// Settings for `make_cards`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct CardOptions {
+    /// Put before the word in `Card::context`
+    pub mark_start: String,
+    /// Put after the word in `Card::context`
+    pub mark_end: String,
+    /// Only make a card for the first occurrence of each lemma and part of speech
+    pub dedupe: bool,
+}
+
+impl Default for CardOptions {
+    fn default() -> Self {
+        Self {
+            mark_start: "<b>".into(),
+            mark_end: "</b>".into(),
+            dedupe: true,
+        }
+    }
+}
+
+/// Makes a card for each word of `sentences` that `select` picks,
+/// e.g. the ones not in a known-word list
+pub fn make_cards<F>(sentences: &[Sentence], select: F, options: &CardOptions) -> Vec<Card>
+where
+    F: Fn(&Word) -> bool,
+{
+    let mut cards = Vec::new();
+    let mut seen = HashSet::new();
+
+    for sentence in sentences {
+        for word in sentence.words.iter().filter(|word| select(word)) {
+            let lemma = word.lemma.clone().unwrap_or_else(|| word.word.clone());
+            if options.dedupe && !seen.insert((lemma.clone(), word.part_of_speech)) {
+                continue;
+            }
+
+            let range = word.range();
+            let start = range.start - sentence.span.start;
+            let end = range.end - sentence.span.start;
+            let context = format!(
+                "{}{}{}{}{}",
+                &sentence.text[..start],
+                options.mark_start,
+                &sentence.text[start..end],
+                options.mark_end,
+                &sentence.text[end..]
+            );
+
+            cards.push(Card {
+                lemma,
+                reading: word
+                    .extra
+                    .lemma_reading
+                    .clone()
+                    .unwrap_or_else(|| word.extra.reading.clone()),
+                part_of_speech: word.part_of_speech,
+                sentence: sentence.text.clone(),
+                context,
+            });
+        }
+    }
+
+    cards
+}
+
+/// Writes cards as tab-separated lemma, reading, part of speech, sentence and context,
+/// one card per line, which Anki and other SRS apps can import
+pub fn cards_to_tsv(cards: &[Card]) -> String {
+    let field = |text: &str| text.replace(['\t', '\n', '\r'], " ");
+
+    cards
+        .iter()
+        .map(|card| {
+            format!(
+                "{}\t{}\t{:?}\t{}\t{}\n",
+                field(&card.lemma),
+                field(&card.reading),
+                card.part_of_speech,
+                field(&card.sentence),
+                field(&card.context)
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::Lexicon;
+    use crate::{parse_text, ParseConfig};
+
+    fn cards(text: &str, options: &CardOptions) -> Vec<Card> {
+        let sentences = parse_text(&Lexicon, text, &ParseConfig::default()).unwrap();
+
+        make_cards(
+            &sentences,
+            |word| matches!(word.part_of_speech, PartOfSpeech::Noun | PartOfSpeech::Verb),
+            options,
+        )
+    }
+
+    #[test]
+    fn marks_the_word_in_its_sentence() {
+        let cards = cards("猫が嘘。猫を食べました。", &CardOptions::default());
+
+        assert_eq!(
+            cards,
+            [
+                Card {
+                    lemma: "猫".into(),
+                    reading: "ネコ".into(),
+                    part_of_speech: PartOfSpeech::Noun,
+                    sentence: "猫が嘘。".into(),
+                    context: "<b>猫</b>が嘘。".into()
+                },
+                Card {
+                    lemma: "嘘".into(),
+                    reading: "ウソ".into(),
+                    part_of_speech: PartOfSpeech::Noun,
+                    sentence: "猫が嘘。".into(),
+                    context: "猫が<b>嘘</b>。".into()
+                },
+                Card {
+                    lemma: "食べる".into(),
+                    reading: "タベル".into(),
+                    part_of_speech: PartOfSpeech::Verb,
+                    sentence: "猫を食べました。".into(),
+                    context: "猫を<b>食べました</b>。".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_repeated_lemmas_unless_deduplicating() {
+        let options = CardOptions {
+            mark_start: "[".into(),
+            mark_end: "]".into(),
+            dedupe: false,
+        };
+        let contexts: Vec<_> = cards("猫が嘘。猫。", &options)
+            .into_iter()
+            .map(|card| card.context)
+            .collect();
+
+        assert_eq!(contexts, ["[猫]が嘘。", "猫が[嘘]。", "[猫]。"]);
+    }
+
+    #[test]
+    fn writes_one_card_per_line() {
+        let mut card = cards("猫。", &CardOptions::default()).remove(0);
+        card.sentence = "猫\tねこ\n".into();
+
+        assert_eq!(
+            cards_to_tsv(&[card]),
+            "猫\tネコ\tNoun\t猫 ねこ \t<b>猫</b>。\n"
+        );
+    }
+}
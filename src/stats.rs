@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parse, prepare, ParseConfig, Result, Tokenize, Warning, Word};
+
+/// Counts and per-stage timings of a single `parse_with_stats` call
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ParseStats {
+    pub tokens: usize,
+    pub words: usize,
+    /// Tokens that came from vibrato's unknown-word handling
+    pub oov_tokens: usize,
+    pub tokenize_time: Duration,
+    pub prepare_time: Duration,
+    pub group_time: Duration,
+}
+
+impl ParseStats {
+    pub fn total_time(&self) -> Duration {
+        self.tokenize_time + self.prepare_time + self.group_time
+    }
+
+    /// Share of tokens that are out of vocabulary, between 0 and 1
+    pub fn oov_rate(&self) -> f64 {
+        if self.tokens == 0 {
+            0.0
+        } else {
+            self.oov_tokens as f64 / self.tokens as f64
+        }
+    }
+}
+
+/// Tokenizes, prepares and groups `text` in one go, measuring each stage along the way
+pub fn parse_with_stats(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+) -> Result<(Vec<Word>, ParseStats)> {
+    parse_with_warnings(tokenizer, text, config).map(|(words, stats, _)| (words, stats))
+}
+
+/// Like `parse_with_stats`, also returning the warnings of `Strictness::Lenient`
+pub(crate) fn parse_with_warnings(
+    tokenizer: &(impl Tokenize + ?Sized),
+    text: &str,
+    config: &ParseConfig,
+) -> Result<(Vec<Word>, ParseStats, Vec<Warning>)> {
+    let mut stats = ParseStats::default();
+
+    let start = Instant::now();
+    let raw_tokens = tokenizer.tokenize(text)?;
+    stats.tokenize_time = start.elapsed();
+    stats.tokens = raw_tokens.len();
+    stats.oov_tokens = raw_tokens.iter().filter(|t| t.is_oov).count();
+
+    let start = Instant::now();
+    let (tokens, mut warnings) = prepare(raw_tokens, config, config.strictness)?;
+    stats.prepare_time = start.elapsed();
+
+    let start = Instant::now();
+    let (words, group_warnings) = parse(tokens, config, config.strictness)?;
+    stats.group_time = start.elapsed();
+    stats.words = words.len();
+    warnings.extend(group_warnings);
+
+    Ok((words, stats, warnings))
+}
@@ -0,0 +1,283 @@
+use std::time::Duration;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{parse_text, ParseConfig, Result, Tokenize, Word};
+
+/// A subtitle shown from `start` to `end`
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cue {
+    pub start: Duration,
+    pub end: Duration,
+    /// The text without styling, lines separated by `\n`
+    pub text: String,
+}
+
+/// A cue and the words of its text
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AnnotatedCue {
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+    /// Words with `Word::range` pointing into `text`
+    pub words: Vec<Word>,
+}
+
+/// Reads the cues of an SRT or ASS/SSA file, telling them apart by the file's sections
+pub fn read_subtitles(source: &str) -> Vec<Cue> {
+    let is_ass = source
+        .lines()
+        .any(|line| matches!(line.trim(), "[Script Info]" | "[Events]"));
+
+    if is_ass {
+        read_ass(source)
+    } else {
+        read_srt(source)
+    }
+}
+
+/// Reads the cues of an SRT file. Blocks without a valid timing line are skipped,
+/// formatting tags like `<i>` are removed from the text.
+pub fn read_srt(source: &str) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut lines = source.trim_start_matches('\u{feff}').lines().peekable();
+
+    while lines.peek().is_some() {
+        let block: Vec<&str> = lines
+            .by_ref()
+            .skip_while(|line| line.trim().is_empty())
+            .take_while(|line| !line.trim().is_empty())
+            .collect();
+
+        // The index line is optional, the timing line is what starts a cue
+        let Some(timing) = block.iter().position(|line| line.contains("-->")) else {
+            continue;
+        };
+        let Some((start, end)) = block[timing].split_once("-->") else {
+            continue;
+        };
+        // Positioning like `X1:40` can follow the end time
+        let end = end.split_whitespace().next().unwrap_or_default();
+
+        if let (Some(start), Some(end)) = (parse_timestamp(start), parse_timestamp(end)) {
+            let text = block[timing + 1..].join("\n");
+            cues.push(Cue {
+                start,
+                end,
+                text: strip_tags(&text, '<', '>'),
+            });
+        }
+    }
+
+    cues
+}
+
+/// Reads the `Dialogue` lines of an ASS/SSA file's `[Events]` section, in the order
+/// given by its `Format` line. Override tags like `{\i1}` are removed from the text
+/// and `\N` turned into line breaks.
+pub fn read_ass(source: &str) -> Vec<Cue> {
+    let mut cues = Vec::new();
+    let mut in_events = false;
+    // Start, End and Text columns, with the defaults of ASS files
+    let mut columns = (1, 2, 9);
+
+    for line in source.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            in_events = line.eq_ignore_ascii_case("[Events]");
+            continue;
+        }
+        if !in_events {
+            continue;
+        }
+
+        if let Some(format) = line.strip_prefix("Format:") {
+            let names: Vec<&str> = format.split(',').map(str::trim).collect();
+            let column = |name: &str| names.iter().position(|n| n.eq_ignore_ascii_case(name));
+
+            if let (Some(start), Some(end), Some(text)) =
+                (column("Start"), column("End"), column("Text"))
+            {
+                columns = (start, end, text);
+            }
+        } else if let Some(dialogue) = line.strip_prefix("Dialogue:") {
+            let (start, end, text) = columns;
+            // The text is the last column and may contain commas itself
+            let fields: Vec<&str> = dialogue.splitn(text + 1, ',').collect();
+            if fields.len() <= text {
+                continue;
+            }
+
+            if let (Some(start), Some(end)) =
+                (parse_timestamp(fields[start]), parse_timestamp(fields[end]))
+            {
+                let text = fields[text]
+                    .replace("\\N", "\n")
+                    .replace("\\n", "\n")
+                    .replace("\\h", " ");
+                cues.push(Cue {
+                    start,
+                    end,
+                    text: strip_tags(&text, '{', '}'),
+                });
+            }
+        }
+    }
+
+    cues
+}
+
+/// Parses timestamps like `00:01:02,500` (SRT) or `0:01:02.50` (ASS)
+fn parse_timestamp(timestamp: &str) -> Option<Duration> {
+    let mut parts = timestamp.trim().rsplitn(3, ':');
+    let seconds = parts.next()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let hours: u64 = parts.next().unwrap_or("0").parse().ok()?;
+
+    let (whole, fraction) = seconds.split_once([',', '.']).unwrap_or((seconds, ""));
+    let whole: u64 = whole.parse().ok()?;
+
+    // A fraction like 5 or 50 (ASS centiseconds) is half a second, same as 500
+    if !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = if fraction.is_empty() {
+        0
+    } else {
+        // All ASCII, so cutting it after the nanoseconds stays on a char boundary
+        let digits = &fraction[..fraction.len().min(9)];
+        digits.parse::<u32>().ok()? * 10u32.pow(9 - digits.len() as u32)
+    };
+
+    Some(Duration::new(hours * 3600 + minutes * 60 + whole, nanos))
+}
+
+fn strip_tags(text: &str, open: char, close: char) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        if c == open {
+            in_tag = true;
+        } else if c == close && in_tag {
+            in_tag = false;
+        } else if !in_tag {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Parses the text of each cue, keeping its timing
+pub fn annotate_cues(
+    tokenizer: &(impl Tokenize + ?Sized),
+    cues: Vec<Cue>,
+    config: &ParseConfig,
+) -> Result<Vec<AnnotatedCue>> {
+    cues.into_iter()
+        .map(|cue| {
+            let words = parse_text(tokenizer, &cue.text, config)?
+                .into_iter()
+                .flat_map(|sentence| sentence.words)
+                .collect();
+
+            Ok(AnnotatedCue {
+                start: cue.start,
+                end: cue.end,
+                text: cue.text,
+                words,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start_ms: u64, end_ms: u64, text: &str) -> Cue {
+        Cue {
+            start: Duration::from_millis(start_ms),
+            end: Duration::from_millis(end_ms),
+            text: text.into(),
+        }
+    }
+
+    #[test]
+    fn reads_srt_cues() {
+        let srt = "\u{feff}1\n00:00:01,000 --> 00:00:02,500\n<i>こんにちは</i>\n二行目\n\n\
+                   2\n01:02:03,040 --> 01:02:04,000 X1:40\n猫\n";
+
+        assert_eq!(
+            read_srt(srt),
+            [
+                cue(1_000, 2_500, "こんにちは\n二行目"),
+                cue(3_723_040, 3_724_000, "猫")
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_fractions_of_any_length() {
+        let srt = "00:00:01,5 --> 00:00:01,25\nA\n\n00:00:01 --> 00:00:02,1234567891\nB\n";
+
+        let cues = read_srt(srt);
+        assert_eq!(cues[0], cue(1_500, 1_250, "A"));
+        assert_eq!(cues[1].end, Duration::new(2, 123_456_789));
+    }
+
+    #[test]
+    fn skips_cues_with_malformed_timestamps() {
+        let srt = "00:00:01,５００ --> 00:00:02,000\nA\n\n\
+                   00:00:01,000 --> 00:0x:02,000\nB\n\n\
+                   00:00:01,000 -> 00:00:02,000\nC\n\n\
+                   00:00:03,000 --> 00:00:04,000\nD\n";
+
+        assert_eq!(read_srt(srt), [cue(3_000, 4_000, "D")]);
+    }
+
+    #[test]
+    fn reads_ass_dialogue() {
+        let ass = "[Script Info]\nTitle: Test\n\n\
+                   [Events]\n\
+                   Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text\n\
+                   Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\\i1}猫{\\i0}です\\N二行目, ね\n\
+                   Comment: 0,0:00:04.00,0:00:05.00,Default,,0,0,0,,コメント\n\
+                   Dialogue: 0,0:00:04.00,0:00:0５.00,Default,,0,0,0,,壊れた\n";
+
+        assert_eq!(read_ass(ass), [cue(1_500, 3_000, "猫です\n二行目, ね")]);
+    }
+
+    #[test]
+    fn reads_ass_columns_in_the_order_of_the_format_line() {
+        let ass = "[Events]\nFormat: Start, End, Text\nDialogue: 0:00:01.00,0:00:02.00,猫\n";
+
+        assert_eq!(read_ass(ass), [cue(1_000, 2_000, "猫")]);
+    }
+
+    #[test]
+    fn tells_srt_and_ass_apart() {
+        let ass = "[Events]\nFormat: Start, End, Text\nDialogue: 0:00:01.00,0:00:02.00,猫\n";
+        let srt = "1\n00:00:01,000 --> 00:00:02,000\n猫\n";
+
+        assert_eq!(read_subtitles(ass), read_subtitles(srt));
+    }
+
+    #[test]
+    fn reads_timestamps_with_either_separator() {
+        assert_eq!(
+            parse_timestamp("0:00:01.50"),
+            Some(Duration::from_millis(1_500))
+        );
+        assert_eq!(
+            parse_timestamp("00:00:01,500"),
+            Some(Duration::from_millis(1_500))
+        );
+        assert_eq!(parse_timestamp("00:00:01,５"), None);
+    }
+}
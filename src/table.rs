@@ -0,0 +1,188 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Word;
+
+/// What a column of a [`TableWriter`] shows about each word
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WordColumn {
+    Surface,
+    /// Empty for words without one
+    Lemma,
+    Reading,
+    Transcription,
+    /// Like `Noun` or `ProperNoun`
+    Pos,
+    /// Number of tokens grouped into the word
+    Tokens,
+}
+
+impl WordColumn {
+    pub const ALL: [WordColumn; 6] = [
+        WordColumn::Surface,
+        WordColumn::Lemma,
+        WordColumn::Reading,
+        WordColumn::Transcription,
+        WordColumn::Pos,
+        WordColumn::Tokens,
+    ];
+
+    /// The column's name in the header row
+    pub fn name(&self) -> &'static str {
+        match self {
+            WordColumn::Surface => "surface",
+            WordColumn::Lemma => "lemma",
+            WordColumn::Reading => "reading",
+            WordColumn::Transcription => "transcription",
+            WordColumn::Pos => "pos",
+            WordColumn::Tokens => "tokens",
+        }
+    }
+
+    fn value<'a>(&self, word: &'a Word) -> Cow<'a, str> {
+        match self {
+            WordColumn::Surface => word.word.as_str().into(),
+            WordColumn::Lemma => word.lemma.as_deref().unwrap_or_default().into(),
+            WordColumn::Reading => word.extra.reading.as_str().into(),
+            WordColumn::Transcription => word.extra.transcription.as_str().into(),
+            WordColumn::Pos => format!("{:?}", word.part_of_speech).into(),
+            WordColumn::Tokens => word.tokens.len().to_string().into(),
+        }
+    }
+}
+
+/// Writes words as CSV or TSV, a row each, for spreadsheets and a quick look at a corpus.
+/// Shows all of [`WordColumn::ALL`] unless other columns are chosen.
+///
+/// ```
+/// use ve::{TableWriter, WordColumn};
+///
+/// let mut writer = TableWriter::tsv(Vec::new()).columns(&[WordColumn::Surface, WordColumn::Pos]);
+/// writer.write_header().unwrap();
+/// assert_eq!(writer.into_inner(), b"surface\tpos\n");
+/// ```
+pub struct TableWriter<W: Write> {
+    writer: W,
+    delimiter: char,
+    columns: Vec<WordColumn>,
+}
+
+impl<W: Write> TableWriter<W> {
+    /// Comma-separated, quoting values like RFC 4180 where needed
+    pub fn csv(writer: W) -> Self {
+        Self {
+            writer,
+            delimiter: ',',
+            columns: WordColumn::ALL.to_vec(),
+        }
+    }
+
+    /// Tab-separated, with tabs and line breaks in values replaced by spaces
+    pub fn tsv(writer: W) -> Self {
+        Self {
+            delimiter: '\t',
+            ..Self::csv(writer)
+        }
+    }
+
+    pub fn columns(mut self, columns: &[WordColumn]) -> Self {
+        self.columns = columns.to_vec();
+        self
+    }
+
+    pub fn write_header(&mut self) -> io::Result<()> {
+        let names: Vec<&str> = self.columns.iter().map(WordColumn::name).collect();
+        self.write_row(&names)
+    }
+
+    /// Writes a row for each of the words
+    pub fn write_words(&mut self, words: &[Word]) -> io::Result<()> {
+        for word in words {
+            let values: Vec<Cow<str>> = self.columns.iter().map(|c| c.value(word)).collect();
+            self.write_row(&values)?;
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_row(&mut self, values: &[impl AsRef<str>]) -> io::Result<()> {
+        let mut row = String::new();
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                row.push(self.delimiter);
+            }
+            row.push_str(&self.escape(value.as_ref()));
+        }
+        row.push('\n');
+
+        self.writer.write_all(row.as_bytes())
+    }
+
+    fn escape<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        let special = |c: char| c == self.delimiter || c == '\n' || c == '\r' || c == '"';
+        if !value.contains(special) {
+            return value.into();
+        }
+
+        match self.delimiter {
+            '\t' => value.replace(['\t', '\n', '\r'], " ").into(),
+            _ => format!("\"{}\"", value.replace('"', "\"\"")).into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words;
+
+    fn written(mut writer: TableWriter<Vec<u8>>, text: &str) -> String {
+        writer.write_header().unwrap();
+        writer.write_words(&words(text)).unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn writes_all_columns_by_default() {
+        assert_eq!(
+            written(TableWriter::csv(Vec::new()), "食べました"),
+            "surface,lemma,reading,transcription,pos,tokens\n\
+             食べました,食べる,タベマシタ,タベマシタ,Verb,3\n"
+        );
+    }
+
+    #[test]
+    fn writes_the_chosen_columns() {
+        let writer = TableWriter::tsv(Vec::new()).columns(&[WordColumn::Pos, WordColumn::Surface]);
+
+        assert_eq!(
+            written(writer, "猫が"),
+            "pos\tsurface\nNoun\t猫\nPostposition\tが\n"
+        );
+    }
+
+    #[test]
+    fn escapes_values_for_the_format() {
+        let csv = TableWriter::csv(Vec::new());
+        assert_eq!(csv.escape("猫"), "猫");
+        assert_eq!(csv.escape("a,b"), "\"a,b\"");
+        assert_eq!(csv.escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv.escape("a\nb"), "\"a\nb\"");
+
+        let tsv = TableWriter::tsv(Vec::new());
+        assert_eq!(tsv.escape("a\tb\nc"), "a b c");
+        assert_eq!(tsv.escape("a,\"b\""), "a,\"b\"");
+    }
+}
@@ -0,0 +1,241 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PartOfSpeech, Word, POS};
+
+/// Verbs that change state in an instant, which ている makes resultative rather than
+/// progressive: 結婚している means being married, not getting married
+const CHANGE_OF_STATE: [&str; 26] = [
+    "死ぬ",
+    "結婚する",
+    "離婚する",
+    "知る",
+    "着く",
+    "来る",
+    "行く",
+    "止まる",
+    "始まる",
+    "終わる",
+    "座る",
+    "立つ",
+    "起きる",
+    "開く",
+    "閉まる",
+    "壊れる",
+    "消える",
+    "落ちる",
+    "太る",
+    "痩せる",
+    "似る",
+    "割れる",
+    "決まる",
+    "届く",
+    "入る",
+    "出る",
+];
+
+/// Whether a predicate is in the past, from the た it ends in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Tense {
+    /// 食べた, 高かった
+    Past,
+    /// 食べる, 高い, also for the future
+    NonPast,
+}
+
+/// What a verb's auxiliary chain says about how the action unfolds
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Aspect {
+    /// Ongoing, with ている: 食べている
+    Progressive,
+    /// A state left by the action, with てある or the ている of verbs like 結婚する:
+    /// 書いてある, 結婚している
+    Resultative,
+    /// Done and over with, with てしまう: 食べてしまった, 食べちゃった
+    Perfect,
+    /// Done in advance, with ておく: 買っておく, 買っとく
+    Preparatory,
+}
+
+/// Sets `WordExtra::tense` on verbs and adjectives and `WordExtra::aspect` on verbs
+pub(crate) fn mark_tense(words: &mut [Word]) {
+    for word in words {
+        if !matches!(
+            word.part_of_speech,
+            PartOfSpeech::Verb | PartOfSpeech::Adjective
+        ) {
+            continue;
+        }
+
+        // The た of 食べた, not the conditional たら of 食べたら
+        let past =
+            word.tokens.iter().rev().find(|token| {
+                token.pos == POS::JoDoushi && token.inflection_type == POS::TokushuTa
+            });
+        word.extra.tense = Some(match past {
+            Some(token) if matches!(token.literal.as_str(), "た" | "だ") => Tense::Past,
+            _ => Tense::NonPast,
+        });
+
+        if word.part_of_speech == PartOfSpeech::Verb {
+            word.extra.aspect = aspect(word);
+        }
+    }
+}
+
+/// The aspect of the last dependent verb in the word, so 食べてしまっている is progressive
+fn aspect(word: &Word) -> Option<Aspect> {
+    word.tokens.iter().skip(1).rev().find_map(|token| {
+        if token.pos != POS::Doushi || token.pos2 != POS::Hijiritsu {
+            return None;
+        }
+        match token.lemma.as_str() {
+            "いる" | "てる" | "でる" | "おる" => {
+                if CHANGE_OF_STATE.contains(&main_verb(word).as_str()) {
+                    Some(Aspect::Resultative)
+                } else {
+                    Some(Aspect::Progressive)
+                }
+            }
+            "ある" => Some(Aspect::Resultative),
+            "しまう" | "ちゃう" | "じゃう" | "ちまう" | "じまう" => {
+                Some(Aspect::Perfect)
+            }
+            "おく" | "とく" | "どく" => Some(Aspect::Preparatory),
+            _ => None,
+        }
+    })
+}
+
+/// Dictionary form of the verb the auxiliaries attach to, 結婚する for 結婚している
+fn main_verb(word: &Word) -> String {
+    let mut lemma = String::new();
+    for token in &word.tokens {
+        lemma.push_str(&token.lemma);
+        if token.pos == POS::Doushi {
+            break;
+        }
+    }
+    lemma
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    const TABE: (&str, &str) = ("食べ", "動詞,自立,*,*,一段,連用形,食べる,タベ,タベ");
+    const TE: (&str, &str) = ("て", "助詞,接続助詞,*,*,*,*,て,テ,テ");
+    const IRU: (&str, &str) = ("いる", "動詞,非自立,*,*,一段,基本形,いる,イル,イル");
+    const TA: (&str, &str) = ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ");
+
+    fn tense_and_aspect(tokens: &[(&str, &str)]) -> Vec<(String, Option<Tense>, Option<Aspect>)> {
+        words_from(tokens, &ParseConfig::default())
+            .into_iter()
+            .map(|word| (word.word, word.extra.tense, word.extra.aspect))
+            .collect()
+    }
+
+    #[test]
+    fn marks_past_tense() {
+        assert_eq!(
+            tense_and_aspect(&[TABE, TA]),
+            [("食べた".into(), Some(Tense::Past), None)]
+        );
+        assert_eq!(
+            tense_and_aspect(&[
+                (
+                    "高かっ",
+                    "形容詞,自立,*,*,形容詞・アウオ段,連用タ接続,高い,タカカッ,タカカッ"
+                ),
+                TA,
+            ]),
+            [("高かった".into(), Some(Tense::Past), None)]
+        );
+    }
+
+    #[test]
+    fn conditional_tara_is_not_past() {
+        assert_eq!(
+            tense_and_aspect(&[TABE, ("たら", "助動詞,*,*,*,特殊・タ,仮定形,た,タラ,タラ")]),
+            [("食べたら".into(), Some(Tense::NonPast), None)]
+        );
+    }
+
+    #[test]
+    fn marks_aspect_from_dependent_verbs() {
+        assert_eq!(
+            tense_and_aspect(&[TABE, TE, IRU]),
+            [(
+                "食べている".into(),
+                Some(Tense::NonPast),
+                Some(Aspect::Progressive)
+            )]
+        );
+        assert_eq!(
+            tense_and_aspect(&[
+                TABE,
+                TE,
+                (
+                    "しまっ",
+                    "動詞,非自立,*,*,五段・ワ行促音便,連用タ接続,しまう,シマッ,シマッ"
+                ),
+                TA,
+            ]),
+            [(
+                "食べてしまった".into(),
+                Some(Tense::Past),
+                Some(Aspect::Perfect)
+            )]
+        );
+        assert_eq!(
+            tense_and_aspect(&[
+                (
+                    "買っ",
+                    "動詞,自立,*,*,五段・ワ行促音便,連用タ接続,買う,カッ,カッ"
+                ),
+                (
+                    "とく",
+                    "動詞,非自立,*,*,五段・カ行イ音便,基本形,とく,トク,トク"
+                ),
+            ]),
+            [(
+                "買っとく".into(),
+                Some(Tense::NonPast),
+                Some(Aspect::Preparatory)
+            )]
+        );
+    }
+
+    #[test]
+    fn teiru_of_change_of_state_verbs_is_resultative() {
+        assert_eq!(
+            tense_and_aspect(&[
+                (
+                    "座っ",
+                    "動詞,自立,*,*,五段・ラ行,連用タ接続,座る,スワッ,スワッ"
+                ),
+                TE,
+                IRU,
+            ]),
+            [(
+                "座っている".into(),
+                Some(Tense::NonPast),
+                Some(Aspect::Resultative)
+            )]
+        );
+    }
+
+    #[test]
+    fn leaves_other_words_alone() {
+        assert_eq!(
+            tense_and_aspect(&[("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ")]),
+            [("猫".into(), None, None)]
+        );
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    parse_into_words_with, prepare_tokens_with, ParseConfig, Result, Tokenize, VibratoToken, Word,
+};
+
+/// IPADIC features of the words the unit tests use
+const LEXICON: &[(&str, &str)] = &[
+    ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ"),
+    ("嘘", "名詞,一般,*,*,*,*,嘘,ウソ,ウソ"),
+    (
+        "東京",
+        "名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー",
+    ),
+    ("今日", "名詞,副詞可能,*,*,*,*,今日,キョウ,キョー"),
+    ("大人", "名詞,一般,*,*,*,*,大人,オトナ,オトナ"),
+    ("土産", "名詞,一般,*,*,*,*,*"),
+    ("勉強", "名詞,サ変接続,*,*,*,*,勉強,ベンキョウ,ベンキョー"),
+    ("食べ物", "名詞,一般,*,*,*,*,食べ物,タベモノ,タベモノ"),
+    ("食べ", "動詞,自立,*,*,一段,連用形,食べる,タベ,タベ"),
+    ("まし", "助動詞,*,*,*,特殊・マス,連用形,ます,マシ,マシ"),
+    ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+    ("が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ"),
+    ("を", "助詞,格助詞,一般,*,*,*,を,ヲ,ヲ"),
+    ("へ", "助詞,格助詞,一般,*,*,*,へ,ヘ,エ"),
+    ("は", "助詞,係助詞,*,*,*,*,は,ハ,ワ"),
+    ("。", "記号,句点,*,*,*,*,。,。,。"),
+];
+
+/// Tokenizes by the longest word of `LEXICON` at each position, making an unknown
+/// symbol of every other character and skipping whitespace
+pub(crate) struct Lexicon;
+
+impl Tokenize for Lexicon {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>> {
+        let mut tokens = Vec::new();
+        let mut start = 0;
+
+        while let Some(c) = text[start..].chars().next() {
+            let known = LEXICON
+                .iter()
+                .filter(|(surface, _)| text[start..].starts_with(surface))
+                .max_by_key(|(surface, _)| surface.len());
+            let (surface, feature, is_oov) = match known {
+                Some((surface, feature)) => (*surface, *feature, false),
+                None => (
+                    &text[start..start + c.len_utf8()],
+                    "記号,一般,*,*,*,*,*",
+                    true,
+                ),
+            };
+
+            let end = start + surface.len();
+            if !c.is_whitespace() {
+                tokens.push(VibratoToken {
+                    surface: surface.into(),
+                    feature: feature.into(),
+                    is_oov,
+                    range: start..end,
+                });
+            }
+            start = end;
+        }
+
+        Ok(tokens)
+    }
+}
+
+/// The words of `text` as `Lexicon` tokenizes it
+pub(crate) fn words(text: &str) -> Vec<Word> {
+    words_with(text, &ParseConfig::default())
+}
+
+/// Like `words`, parsed with `config`
+pub(crate) fn words_with(text: &str, config: &ParseConfig) -> Vec<Word> {
+    let tokens = prepare_tokens_with(Lexicon.tokenize(text).unwrap(), config).unwrap();
+
+    parse_into_words_with(tokens, config).unwrap()
+}
+
+/// The words of tokens with the given surfaces and features, parsed with `config`,
+/// for features of dictionaries other than `Lexicon`'s
+pub(crate) fn words_from(tokens: &[(&str, &str)], config: &ParseConfig) -> Vec<Word> {
+    let mut start = 0;
+    let tokens = tokens
+        .iter()
+        .map(|(surface, feature)| {
+            let range = start..start + surface.len();
+            start = range.end;
+            VibratoToken {
+                surface: surface.to_string(),
+                feature: feature.to_string(),
+                is_oov: false,
+                range,
+            }
+        })
+        .collect();
+    let tokens = prepare_tokens_with(tokens, config).unwrap();
+
+    parse_into_words_with(tokens, config).unwrap()
+}
@@ -0,0 +1,53 @@
+use std::fs::File;
+use std::path::Path;
+
+use vibrato::{Dictionary, Tokenizer};
+
+use crate::error::TokenizeError;
+use crate::VibratoToken;
+
+/// Loads a zstd-compressed vibrato dictionary, like the ones distributed with vibrato
+pub fn load_dictionary(path: impl AsRef<Path>) -> Result<Dictionary, TokenizeError> {
+    let path = path.as_ref();
+    let io_error = |source| TokenizeError::Io {
+        path: path.into(),
+        source,
+    };
+
+    let file = File::open(path).map_err(io_error)?;
+    let reader = zstd::Decoder::new(file).map_err(io_error)?;
+
+    Ok(Dictionary::read(reader)?)
+}
+
+/// Where the `bundled-dict` feature embeds the dictionary from, at build time
+#[cfg(feature = "bundled-dict")]
+const BUNDLED_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/system.dic.zst");
+
+#[cfg(feature = "bundled-dict")]
+static BUNDLED: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/system.dic.zst"));
+
+/// The IPADIC dictionary embedded into the binary with the `bundled-dict` feature, so demos
+/// and tests work without a dictionary file next to them.
+///
+/// It's embedded from the `system.dic.zst` checked into the crate's directory when building.
+/// That's the whole dictionary, so the binary gets about 8 MB bigger.
+#[cfg(feature = "bundled-dict")]
+pub fn bundled_dictionary() -> Result<Dictionary, TokenizeError> {
+    let reader = zstd::Decoder::new(BUNDLED).map_err(|source| TokenizeError::Io {
+        path: BUNDLED_PATH.into(),
+        source,
+    })?;
+
+    Ok(Dictionary::read(reader)?)
+}
+
+/// Tokenizes `text` into tokens that can be passed to `prepare_tokens`
+pub fn tokenize(tokenizer: &Tokenizer, text: &str) -> Vec<VibratoToken> {
+    let mut worker = tokenizer.new_worker();
+
+    worker.reset_sentence(text);
+    worker.tokenize();
+
+    worker.token_iter().map(|t| t.into()).collect()
+}
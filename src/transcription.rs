@@ -0,0 +1,63 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::expand_long_vowels;
+
+/// How to normalize `WordExtra::transcription`, which IPADIC writes with
+/// long vowel marks and ヲ, for consumers like TTS engines that expect otherwise.
+/// Everything is kept as is by default.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct TranscriptionOptions {
+    /// Replace ー with the vowel it extends (トーキョー → トオキョオ)
+    pub expand_long_vowels: bool,
+    /// Write ヲ as オ, the way it's pronounced
+    pub wo_as_o: bool,
+}
+
+impl TranscriptionOptions {
+    pub fn apply(&self, transcription: &str) -> String {
+        let mut transcription = if self.expand_long_vowels {
+            expand_long_vowels(transcription)
+        } else {
+            transcription.into()
+        };
+
+        if self.wo_as_o {
+            transcription = transcription.replace('ヲ', "オ");
+        }
+
+        transcription
+    }
+}
+
+/// Interjections that end in the particle は, read ワ like it
+const GREETINGS: [&str; 4] = ["こんにちは", "こんばんは", "では", "それでは"];
+
+/// Whether an interjection ends in a particle, see [`pronounce_particle`]
+pub(crate) fn is_greeting(surface: &str) -> bool {
+    GREETINGS.contains(&surface)
+}
+
+/// Pronunciation of particles spelled with は or へ, which are read ワ and エ,
+/// for dictionaries that don't transcribe them that way. Also covers greetings
+/// ending in the particle, like こんにちは, see [`is_greeting`].
+pub(crate) fn pronounce_particle(surface: &str, hatsuon: &str, reading: &str) -> String {
+    let hatsuon = if hatsuon.is_empty() || hatsuon == "*" {
+        reading
+    } else {
+        hatsuon
+    };
+
+    let pronounced = match surface.chars().last() {
+        Some('は') => 'ワ',
+        Some('へ') => 'エ',
+        _ => return hatsuon.into(),
+    };
+
+    match hatsuon.strip_suffix(['ハ', 'ヘ']) {
+        Some(rest) => format!("{}{}", rest, pronounced),
+        None => hatsuon.into(),
+    }
+}
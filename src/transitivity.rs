@@ -0,0 +1,196 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{PartOfSpeech, Word, POS};
+
+/// Whether a verb of a transitive/intransitive pair takes an object
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Transitivity {
+    /// 他動詞, like 開ける in ドアを開ける
+    Transitive,
+    /// 自動詞, like 開く in ドアが開く
+    Intransitive,
+}
+
+/// Transitive verbs and their intransitive counterparts. A verb with two counterparts,
+/// like 起こす with 起きる and 起こる, is listed twice and pairs with the first.
+const PAIRS: [(&str, &str); 81] = [
+    ("開ける", "開く"),
+    ("閉める", "閉まる"),
+    ("始める", "始まる"),
+    ("終える", "終わる"),
+    ("止める", "止まる"),
+    ("付ける", "付く"),
+    ("つける", "つく"),
+    ("点ける", "点く"),
+    ("消す", "消える"),
+    ("入れる", "入る"),
+    ("出す", "出る"),
+    ("落とす", "落ちる"),
+    ("壊す", "壊れる"),
+    ("割る", "割れる"),
+    ("折る", "折れる"),
+    ("切る", "切れる"),
+    ("変える", "変わる"),
+    ("決める", "決まる"),
+    ("集める", "集まる"),
+    ("上げる", "上がる"),
+    ("下げる", "下がる"),
+    ("起こす", "起きる"),
+    ("起こす", "起こる"),
+    ("残す", "残る"),
+    ("直す", "直る"),
+    ("治す", "治る"),
+    ("戻す", "戻る"),
+    ("返す", "返る"),
+    ("回す", "回る"),
+    ("通す", "通る"),
+    ("渡す", "渡る"),
+    ("倒す", "倒れる"),
+    ("育てる", "育つ"),
+    ("建てる", "建つ"),
+    ("立てる", "立つ"),
+    ("並べる", "並ぶ"),
+    ("続ける", "続く"),
+    ("届ける", "届く"),
+    ("見つける", "見つかる"),
+    ("助ける", "助かる"),
+    ("伝える", "伝わる"),
+    ("増やす", "増える"),
+    ("減らす", "減る"),
+    ("冷やす", "冷える"),
+    ("沸かす", "沸く"),
+    ("焼く", "焼ける"),
+    ("溶かす", "溶ける"),
+    ("汚す", "汚れる"),
+    ("隠す", "隠れる"),
+    ("乾かす", "乾く"),
+    ("動かす", "動く"),
+    ("加える", "加わる"),
+    ("曲げる", "曲がる"),
+    ("混ぜる", "混ざる"),
+    ("覚ます", "覚める"),
+    ("離す", "離れる"),
+    ("外す", "外れる"),
+    ("抜く", "抜ける"),
+    ("揺らす", "揺れる"),
+    ("濡らす", "濡れる"),
+    ("下ろす", "下りる"),
+    ("降ろす", "降りる"),
+    ("流す", "流れる"),
+    ("掛ける", "掛かる"),
+    ("かける", "かかる"),
+    ("預ける", "預かる"),
+    ("固める", "固まる"),
+    ("広げる", "広がる"),
+    ("売る", "売れる"),
+    ("焦がす", "焦げる"),
+    ("無くす", "無くなる"),
+    ("なくす", "なくなる"),
+    ("亡くす", "亡くなる"),
+    ("片付ける", "片付く"),
+    ("向ける", "向く"),
+    ("当てる", "当たる"),
+    ("破る", "破れる"),
+    ("下す", "下る"),
+    ("冷ます", "冷める"),
+    ("生やす", "生える"),
+    ("逃がす", "逃げる"),
+];
+
+/// Whether `lemma` is the transitive or intransitive verb of a pair, and its counterpart,
+/// e.g. `(Intransitive, "開ける")` for 開く
+pub fn verb_pair(lemma: &str) -> Option<(Transitivity, &'static str)> {
+    PAIRS.iter().find_map(|&(transitive, intransitive)| {
+        if lemma == transitive {
+            Some((Transitivity::Transitive, intransitive))
+        } else if lemma == intransitive {
+            Some((Transitivity::Intransitive, transitive))
+        } else {
+            None
+        }
+    })
+}
+
+impl Word {
+    /// Whether the word is the transitive or intransitive verb of a pair like 開ける/開く.
+    /// None for verbs without a counterpart.
+    pub fn transitivity(&self) -> Option<Transitivity> {
+        self.verb_pair().map(|(transitivity, _)| transitivity)
+    }
+
+    /// The other verb of the word's transitive/intransitive pair, e.g. 開く for 開けた
+    pub fn paired_verb(&self) -> Option<&'static str> {
+        self.verb_pair().map(|(_, paired)| paired)
+    }
+
+    fn verb_pair(&self) -> Option<(Transitivity, &'static str)> {
+        if self.part_of_speech != PartOfSpeech::Verb {
+            return None;
+        }
+        let verb = self.tokens.iter().find(|token| token.pos == POS::Doushi)?;
+        verb_pair(&verb.lemma)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    #[test]
+    fn finds_both_verbs_of_a_pair() {
+        assert_eq!(
+            verb_pair("開ける"),
+            Some((Transitivity::Transitive, "開く"))
+        );
+        assert_eq!(
+            verb_pair("開く"),
+            Some((Transitivity::Intransitive, "開ける"))
+        );
+        assert_eq!(verb_pair("食べる"), None);
+    }
+
+    #[test]
+    fn verbs_with_two_counterparts_pair_with_the_first() {
+        assert_eq!(
+            verb_pair("起こす"),
+            Some((Transitivity::Transitive, "起きる"))
+        );
+        assert_eq!(
+            verb_pair("起こる"),
+            Some((Transitivity::Intransitive, "起こす"))
+        );
+    }
+
+    #[test]
+    fn no_verb_is_both_transitive_and_intransitive() {
+        for (transitive, _) in PAIRS {
+            assert!(
+                PAIRS
+                    .iter()
+                    .all(|&(_, intransitive)| intransitive != transitive),
+                "{transitive}"
+            );
+        }
+    }
+
+    #[test]
+    fn reads_the_pair_of_inflected_verbs() {
+        let words = words_from(
+            &[
+                ("開け", "動詞,自立,*,*,一段,連用形,開ける,アケ,アケ"),
+                ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+                ("ドア", "名詞,一般,*,*,*,*,ドア,ドア,ドア"),
+            ],
+            &ParseConfig::default(),
+        );
+
+        assert_eq!(words[0].transitivity(), Some(Transitivity::Transitive));
+        assert_eq!(words[0].paired_verb(), Some("開く"));
+        assert_eq!(words[1].transitivity(), None);
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::kana::{to_katakana, vowel_of};
+use crate::{PartOfSpeech, PreparedToken, Word, POS};
+
+/// The voice of a verb, from the suffixes grouped into it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Voice {
+    /// 食べる
+    Active,
+    /// With れる or られる: 書かれる, 食べられる
+    Passive,
+    /// With せる or させる: 書かせる, 食べさせる
+    Causative,
+    /// Both: 食べさせられる, 書かされる
+    CausativePassive,
+    /// With られる, or れる after an ichidan verb (ら抜き): 食べられる, 食べれる.
+    /// Potential verbs like 書ける are verbs of their own in the dictionary and stay active.
+    Potential,
+}
+
+/// Sets `WordExtra::voice` on verbs.
+///
+/// られる can be passive or potential. It's taken as passive when the clause has an agent
+/// marked with に or によって, as potential when it only has a が and isn't in the past,
+/// and as passive otherwise.
+pub(crate) fn mark_voice(words: &mut [Word]) {
+    for i in 0..words.len() {
+        if words[i].part_of_speech != PartOfSpeech::Verb {
+            continue;
+        }
+
+        let voice = voice(&words[i], &clause_particles(&words[..i]));
+        words[i].extra.voice = Some(voice);
+    }
+}
+
+fn voice(word: &Word, particles: &[&str]) -> Voice {
+    let mut causative = false;
+    let mut passive = false;
+    let mut potential = false;
+    let mut rareru = false;
+
+    for (previous, token) in word.tokens.iter().zip(word.tokens.iter().skip(1)) {
+        if token.pos != POS::Doushi || token.pos2 != POS::Setsubi {
+            continue;
+        }
+        match token.lemma.as_str() {
+            "せる" | "させる" | "す" => causative = true,
+            "れる" if previous.lemma == "する" || ends_in_a(previous) => passive = true,
+            // Only ichidan verbs end in anything but an a-row kana before れる
+            "れる" => potential = true,
+            "られる" => rareru = true,
+            _ => (),
+        }
+    }
+
+    if causative && (passive || rareru) {
+        Voice::CausativePassive
+    } else if causative {
+        Voice::Causative
+    } else if passive {
+        Voice::Passive
+    } else if potential {
+        Voice::Potential
+    } else if rareru {
+        if particles.iter().any(|p| matches!(*p, "に" | "によって")) {
+            Voice::Passive
+        } else if particles.contains(&"が") && !is_past(word) {
+            Voice::Potential
+        } else {
+            Voice::Passive
+        }
+    } else {
+        Voice::Active
+    }
+}
+
+/// Whether the stem before れる is the a-row 未然形 of a godan verb, like the 書か of 書かれる
+fn ends_in_a(token: &PreparedToken) -> bool {
+    to_katakana(&token.literal)
+        .chars()
+        .last()
+        .and_then(vowel_of)
+        .is_some_and(|vowel| vowel == 'ア')
+}
+
+fn is_past(word: &Word) -> bool {
+    word.tokens
+        .iter()
+        .any(|token| token.pos == POS::JoDoushi && token.inflection_type == POS::TokushuTa)
+}
+
+/// The particles between the previous predicate or sentence end and the verb
+fn clause_particles(before: &[Word]) -> Vec<&str> {
+    before
+        .iter()
+        .rev()
+        .take_while(|word| {
+            !matches!(
+                word.part_of_speech,
+                PartOfSpeech::Verb | PartOfSpeech::Adjective
+            ) && !word.tokens.iter().any(PreparedToken::ends_sentence)
+        })
+        .filter(|word| word.part_of_speech == PartOfSpeech::Postposition)
+        .map(|word| word.word.as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::words_from;
+    use crate::ParseConfig;
+
+    const TABE: (&str, &str) = ("食べ", "動詞,自立,*,*,一段,未然形,食べる,タベ,タベ");
+    const RARERU: (&str, &str) = ("られる", "動詞,接尾,*,*,一段,基本形,られる,ラレル,ラレル");
+
+    fn voices(tokens: &[(&str, &str)]) -> Vec<(String, Option<Voice>)> {
+        words_from(tokens, &ParseConfig::default())
+            .into_iter()
+            .map(|word| (word.word, word.extra.voice))
+            .collect()
+    }
+
+    fn voice_of_last(tokens: &[(&str, &str)]) -> Option<Voice> {
+        voices(tokens).pop().and_then(|(_, voice)| voice)
+    }
+
+    #[test]
+    fn marks_voice_from_suffixes() {
+        assert_eq!(
+            voices(&[("食べる", "動詞,自立,*,*,一段,基本形,食べる,タベル,タベル")]),
+            [("食べる".into(), Some(Voice::Active))]
+        );
+        assert_eq!(
+            voice_of_last(&[
+                (
+                    "書か",
+                    "動詞,自立,*,*,五段・カ行イ音便,未然形,書く,カカ,カカ"
+                ),
+                ("れる", "動詞,接尾,*,*,一段,基本形,れる,レル,レル"),
+            ]),
+            Some(Voice::Passive)
+        );
+        assert_eq!(
+            voice_of_last(&[
+                TABE,
+                ("させる", "動詞,接尾,*,*,一段,基本形,させる,サセル,サセル"),
+            ]),
+            Some(Voice::Causative)
+        );
+        assert_eq!(
+            voice_of_last(&[
+                TABE,
+                ("させ", "動詞,接尾,*,*,一段,未然形,させる,サセ,サセ"),
+                RARERU,
+            ]),
+            Some(Voice::CausativePassive)
+        );
+    }
+
+    #[test]
+    fn ra_nuki_reru_is_potential() {
+        assert_eq!(
+            voice_of_last(&[TABE, ("れる", "動詞,接尾,*,*,一段,基本形,れる,レル,レル")]),
+            Some(Voice::Potential)
+        );
+    }
+
+    #[test]
+    fn tells_passive_and_potential_rareru_apart_by_the_clause() {
+        const GA: (&str, &str) = ("が", "助詞,格助詞,一般,*,*,*,が,ガ,ガ");
+        const NEKO: (&str, &str) = ("猫", "名詞,一般,*,*,*,*,猫,ネコ,ネコ");
+
+        assert_eq!(
+            voice_of_last(&[
+                NEKO,
+                ("に", "助詞,格助詞,一般,*,*,*,に,ニ,ニ"),
+                TABE,
+                RARERU
+            ]),
+            Some(Voice::Passive)
+        );
+        assert_eq!(
+            voice_of_last(&[NEKO, GA, TABE, RARERU]),
+            Some(Voice::Potential)
+        );
+        assert_eq!(
+            voice_of_last(&[
+                NEKO,
+                GA,
+                TABE,
+                ("られ", "動詞,接尾,*,*,一段,連用形,られる,ラレ,ラレ"),
+                ("た", "助動詞,*,*,*,特殊・タ,基本形,た,タ,タ"),
+            ]),
+            Some(Voice::Passive)
+        );
+        assert_eq!(voice_of_last(&[TABE, RARERU]), Some(Voice::Passive));
+    }
+
+    #[test]
+    fn leaves_other_words_alone() {
+        assert_eq!(
+            voices(&[(
+                "高い",
+                "形容詞,自立,*,*,形容詞・アウオ段,基本形,高い,タカイ,タカイ"
+            )]),
+            [("高い".into(), None)]
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{ParseConfig, ParseEvent};
+
+/// Something about a token that was worked around instead of failing, see [`Strictness`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Warning {
+    pub surface: String,
+    /// Index of the sentence the token is in, counting sentence-ending punctuation
+    pub sentence: usize,
+    pub reason: WarningReason,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WarningReason {
+    /// The feature string had fewer than the 6 POS and inflection columns, the rest was filled with `*`
+    MissingFeatures { found: usize },
+    /// The main part of speech was `*`
+    UnsetPos,
+    /// The main part of speech isn't one of the IPADIC ones, or one UniDic's are mapped to
+    UnknownPos { pos: String },
+    /// The token's part of speech couldn't be mapped, the word became `PartOfSpeech::Unknown`
+    UnrecognizedPos,
+    /// A rule wanted to merge the following token, but the input ended
+    MissingFollowingToken,
+}
+
+impl Warning {
+    pub(crate) fn new(surface: &str, sentence: usize, reason: WarningReason) -> Self {
+        Self {
+            surface: surface.into(),
+            sentence,
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            WarningReason::MissingFeatures { found } => write!(
+                f,
+                "Couldn't read all features from token '{}' (found {} columns). Make sure you're using an IPADIC or UniDic dictionary, or set ParseConfig::schema",
+                self.surface, found
+            ),
+            WarningReason::UnknownPos { ref pos } => write!(
+                f,
+                "The main POS '{}' of token '{}' isn't an IPADIC part of speech",
+                pos, self.surface
+            ),
+            WarningReason::UnsetPos => write!(
+                f,
+                "The main POS of token '{}' couldn't be identified",
+                self.surface
+            ),
+            WarningReason::UnrecognizedPos => write!(
+                f,
+                "Part of speech couldn't be recognized for token {}",
+                self.surface
+            ),
+            WarningReason::MissingFollowingToken => write!(
+                f,
+                "eat_next was set for token {} despite there being no following token",
+                self.surface
+            ),
+        }
+    }
+}
+
+/// How to deal with tokens that can't be prepared or grouped properly,
+/// like short feature rows or parts of speech that couldn't be identified
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Strictness {
+    /// Fail with an error
+    #[default]
+    Strict,
+    /// Work around them and report a [`Warning`]
+    Lenient,
+    /// Work around them silently
+    Permissive,
+}
+
+/// Collects the warnings of a single prepare or parse pass, according to the strictness
+pub(crate) struct Reporter<'a> {
+    strictness: Strictness,
+    config: &'a ParseConfig,
+    pub warnings: Vec<Warning>,
+}
+
+impl<'a> Reporter<'a> {
+    pub fn new(config: &'a ParseConfig, strictness: Strictness) -> Self {
+        Self {
+            strictness,
+            config,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Hands back the warning as the error with `Strictness::Strict`
+    pub fn report(
+        &mut self,
+        surface: &str,
+        sentence: usize,
+        reason: WarningReason,
+    ) -> Result<(), Warning> {
+        let warning = Warning::new(surface, sentence, reason);
+
+        match self.strictness {
+            Strictness::Strict => return Err(warning),
+            Strictness::Lenient => {
+                self.config.observe(ParseEvent::Warning(&warning));
+                self.warnings.push(warning);
+            }
+            Strictness::Permissive => (),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+#![allow(dead_code)]
+
+use ve::{ParseConfig, Pipeline, Word};
+use vibrato::{SystemDictionaryBuilder, Tokenizer};
+
+/// IPADIC entries of the words the tests use, so they run without a real dictionary
+const LEXICON: &str = "\
+猫,0,0,100,名詞,一般,*,*,*,*,猫,ネコ,ネコ
+町,0,0,100,名詞,一般,*,*,*,*,町,マチ,マチ
+嘘,0,0,100,名詞,一般,*,*,*,*,嘘,ウソ,ウソ
+静か,0,0,100,名詞,形容動詞語幹,*,*,*,*,静か,シズカ,シズカ
+本当,0,0,100,名詞,形容動詞語幹,*,*,*,*,本当,ホントウ,ホントー
+東京,0,0,100,名詞,固有名詞,地域,一般,*,*,東京,トウキョウ,トーキョー
+食べ,0,0,100,動詞,自立,*,*,一段,連用形,食べる,タベ,タベ
+待っ,0,0,100,動詞,自立,*,*,五段・タ行,連用タ接続,待つ,マッ,マッ
+出来,0,0,100,動詞,自立,*,*,一段,未然形,出来る,デキ,デキ
+来,0,0,100,動詞,自立,*,*,カ変・来ル,未然形,来る,コ,コ
+なかっ,0,0,100,助動詞,*,*,*,特殊・ナイ,連用タ接続,ない,ナカッ,ナカッ
+まし,0,0,100,助動詞,*,*,*,特殊・マス,連用形,ます,マシ,マシ
+た,0,0,100,助動詞,*,*,*,特殊・タ,基本形,た,タ,タ
+な,0,0,100,助動詞,*,*,*,特殊・ダ,体言接続,だ,ナ,ナ
+が,0,0,100,助詞,格助詞,一般,*,*,*,が,ガ,ガ
+を,0,0,100,助詞,格助詞,一般,*,*,*,を,ヲ,ヲ
+へ,0,0,100,助詞,格助詞,一般,*,*,*,へ,ヘ,エ
+は,0,0,100,助詞,係助詞,*,*,*,*,は,ハ,ワ
+て,0,0,100,助詞,接続助詞,*,*,*,*,て,テ,テ
+。,0,0,100,記号,句点,*,*,*,*,。,。,。
+、,0,0,100,記号,読点,*,*,*,*,、,、,、
+";
+
+const MATRIX: &str = "1 1\n0 0 0\n";
+
+const CHAR_DEF: &str = "\
+DEFAULT 0 1 0
+SPACE 0 1 0
+NUMERIC 1 1 0
+ALPHA 1 1 0
+HIRAGANA 0 1 2
+KATAKANA 1 1 2
+KANJI 0 0 2
+
+0x0020 SPACE
+0x0030..0x0039 NUMERIC
+0x0041..0x005A ALPHA
+0x0061..0x007A ALPHA
+0x3041..0x309F HIRAGANA
+0x30A1..0x30FF KATAKANA
+0x4E00..0x9FFF KANJI
+";
+
+const UNK_DEF: &str = "\
+DEFAULT,0,0,10000,記号,一般,*,*,*,*,*
+SPACE,0,0,10000,記号,空白,*,*,*,*,*
+NUMERIC,0,0,10000,名詞,数,*,*,*,*,*
+ALPHA,0,0,10000,名詞,固有名詞,組織,*,*,*,*
+HIRAGANA,0,0,10000,名詞,一般,*,*,*,*,*
+KATAKANA,0,0,10000,名詞,一般,*,*,*,*,*
+KANJI,0,0,10000,名詞,一般,*,*,*,*,*
+";
+
+/// A tokenizer with a dictionary of only the words in `LEXICON`
+pub fn tokenizer() -> Tokenizer {
+    let dictionary = SystemDictionaryBuilder::from_readers(
+        LEXICON.as_bytes(),
+        MATRIX.as_bytes(),
+        CHAR_DEF.as_bytes(),
+        UNK_DEF.as_bytes(),
+    )
+    .unwrap();
+
+    Tokenizer::new(dictionary).ignore_space(true).unwrap()
+}
+
+pub fn parse(text: &str, config: ParseConfig) -> Vec<Word> {
+    Pipeline::builder(tokenizer())
+        .config(config)
+        .build()
+        .parse(text)
+        .unwrap()
+}
+
+/// The surfaces of the words
+pub fn surfaces(words: &[Word]) -> Vec<&str> {
+    words.iter().map(|word| word.word.as_str()).collect()
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use common::{surfaces, tokenizer};
+use std::sync::{Arc, Mutex};
+
+use ve::{
+    parse_pipelined, parse_text, Hook, ParseConfig, Pipeline, Progress, Result, Tokenize, Ve,
+    VibratoToken,
+};
+
+/// A tokenizer other than vibrato's, standing in for the Lindera and MeCab backends
+struct Backend(vibrato::Tokenizer);
+
+impl Tokenize for Backend {
+    fn tokenize(&self, text: &str) -> Result<Vec<VibratoToken>> {
+        Ok(ve::tokenize(&self.0, text))
+    }
+}
+
+#[test]
+fn takes_any_tokenizer() {
+    let backend = Backend(tokenizer());
+
+    let sentences = parse_text(&backend, "猫が食べました。町", &ParseConfig::default()).unwrap();
+    assert_eq!(
+        surfaces(&sentences[0].words),
+        ["猫", "が", "食べました", "。"]
+    );
+
+    let ve = Ve::new(backend, ParseConfig::default());
+    assert_eq!(surfaces(&ve.parse("町").unwrap()), ["町"]);
+}
+
+#[test]
+fn runs_the_config_post_processors() {
+    let mut config = ParseConfig::default();
+    config.post_processors.push(|words| words.truncate(1));
+
+    let pipeline = Pipeline::builder(Backend(tokenizer()))
+        .config(config)
+        .build();
+
+    assert_eq!(
+        surfaces(&pipeline.parse("猫が食べました。").unwrap()),
+        ["猫"]
+    );
+}
+
+#[test]
+fn reports_the_progress_of_sentences_of_known_count() {
+    let fractions = Arc::new(Mutex::new(Vec::new()));
+    let mut config = ParseConfig::default();
+    let reported = fractions.clone();
+    config.progress = Some(Hook(Arc::new(move |progress: &Progress| {
+        reported.lock().unwrap().push(progress.fraction())
+    })));
+
+    let sentences = vec!["猫が食べました。", "町"];
+    parse_pipelined(&tokenizer(), sentences, &config, |_, _| {}).unwrap();
+
+    assert_eq!(*fractions.lock().unwrap(), [Some(0.5), Some(1.0)]);
+}
@@ -0,0 +1,114 @@
+mod common;
+
+use common::{parse, surfaces};
+use ve::{ParseConfig, PartOfSpeech, Rule, RuleSet};
+
+fn parse_with_rules(text: &str, rules: RuleSet) -> Vec<ve::Word> {
+    parse(
+        text,
+        ParseConfig {
+            rules,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn tries_rules_in_the_default_order() {
+    let rules: Vec<Rule> = RuleSet::default().iter().collect();
+    let expected: Vec<Rule> = Rule::DEFAULT_ORDER
+        .into_iter()
+        .filter(|rule| !Rule::OPT_IN.contains(rule))
+        .collect();
+
+    assert_eq!(rules, expected);
+}
+
+#[test]
+fn places_rules_before_others() {
+    let mut rules = RuleSet::default();
+    rules.place_before(Rule::MergeLatin, Rule::SahenVerb);
+    rules.place_before(Rule::AttachAuxiliary, Rule::AttachSuffix);
+
+    let order: Vec<Rule> = rules.iter().take(2).collect();
+    assert_eq!(order, [Rule::MergeLatin, Rule::SahenVerb]);
+
+    let order: Vec<Rule> = rules.iter().collect();
+    let position = |rule| order.iter().position(|r| *r == rule).unwrap();
+    assert_eq!(
+        position(Rule::AttachAuxiliary) + 1,
+        position(Rule::AttachSuffix)
+    );
+    assert_eq!(order.len(), Rule::DEFAULT_ORDER.len() - Rule::OPT_IN.len());
+}
+
+#[test]
+fn tries_rules_missing_from_the_order_afterwards() {
+    let rules = RuleSet {
+        order: vec![Rule::AttachAuxiliary],
+        ..Default::default()
+    };
+
+    let order: Vec<Rule> = rules.iter().collect();
+    assert_eq!(order[0], Rule::AttachAuxiliary);
+    assert_eq!(order[1], Rule::SahenVerb);
+}
+
+#[test]
+fn skips_disabled_rules() {
+    assert_eq!(
+        surfaces(&parse("猫が食べました。", ParseConfig::default())),
+        ["猫", "が", "食べました", "。"]
+    );
+
+    let mut rules = RuleSet::default();
+    rules.disable(Rule::AttachAuxiliary);
+    assert!(!rules.iter().any(|rule| rule == Rule::AttachAuxiliary));
+    assert_eq!(
+        surfaces(&parse_with_rules("猫が食べました。", rules)),
+        ["猫", "が", "食べ", "まし", "た", "。"]
+    );
+}
+
+#[test]
+fn opt_in_rules_only_apply_when_enabled() {
+    let mut rules = RuleSet::default();
+    rules.disable(Rule::AdjectivalNoun);
+    assert_eq!(
+        surfaces(&parse_with_rules("静かな町", rules.clone())),
+        ["静か", "な", "町"]
+    );
+
+    rules.enable(Rule::AttachNa);
+    let words = parse_with_rules("静かな町", rules);
+    assert_eq!(surfaces(&words), ["静かな", "町"]);
+    assert_eq!(words[0].part_of_speech, PartOfSpeech::Adjective);
+}
+
+#[test]
+fn earlier_rules_take_precedence() {
+    // Acronym and MergeLatin both apply to NHK, Acronym comes first by default
+    let words = parse("NHK", ParseConfig::default());
+    assert_eq!(words[0].part_of_speech, PartOfSpeech::ProperNoun);
+
+    let mut rules = RuleSet::default();
+    rules.place_before(Rule::MergeLatin, Rule::Acronym);
+    let words = parse_with_rules("NHK", rules);
+    assert_eq!(words[0].part_of_speech, PartOfSpeech::Foreign);
+}
+
+#[test]
+fn keeps_punctuation_out_of_latin_words() {
+    assert_eq!(
+        surfaces(&parse("OK！", ParseConfig::default())),
+        ["OK", "！"]
+    );
+    assert_eq!(
+        surfaces(&parse("NHK（テレビ）", ParseConfig::default())),
+        ["NHK", "（", "テレビ", "）"]
+    );
+    assert_eq!(
+        surfaces(&parse("e-mail", ParseConfig::default())),
+        ["e-mail"]
+    );
+}
@@ -0,0 +1,102 @@
+use ve::{join_soft_line_breaks, split_sentences};
+
+fn sentences(text: &str) -> Vec<&str> {
+    split_sentences(text)
+        .into_iter()
+        .map(|span| &text[span])
+        .collect()
+}
+
+#[test]
+fn splits_after_terminators_and_line_breaks() {
+    assert_eq!(
+        sentences("今日は雨です。明日は晴れ\n  明後日は？ "),
+        ["今日は雨です。", "明日は晴れ", "明後日は？"]
+    );
+}
+
+#[test]
+fn keeps_quotes_together() {
+    assert_eq!(
+        sentences("彼は「もう行く。また明日。」と言った。次の文。"),
+        ["彼は「もう行く。また明日。」と言った。", "次の文。"]
+    );
+    assert_eq!(
+        sentences("『本当に？』と聞かれた。"),
+        ["『本当に？』と聞かれた。"]
+    );
+}
+
+#[test]
+fn keeps_parentheses_together() {
+    assert_eq!(
+        sentences("東京（日本の首都。人口が多い。）に住んでいる。そうです。"),
+        [
+            "東京（日本の首都。人口が多い。）に住んでいる。",
+            "そうです。"
+        ]
+    );
+    assert_eq!(
+        sentences("これは例です(たぶん。)。"),
+        ["これは例です(たぶん。)。"]
+    );
+}
+
+#[test]
+fn splits_between_lines_of_dialogue() {
+    assert_eq!(
+        sentences("「おはよう！」「おはよう。元気？」「うん。」"),
+        ["「おはよう！」", "「おはよう。元気？」", "「うん。」"]
+    );
+    assert_eq!(
+        sentences("「行こう。」 「どこへ？」"),
+        ["「行こう。」", "「どこへ？」"]
+    );
+}
+
+#[test]
+fn treats_runs_of_terminators_as_one() {
+    assert_eq!(
+        sentences("本当？！ええ…！まさか……。そうか!?"),
+        ["本当？！", "ええ…！", "まさか……。", "そうか!?"]
+    );
+}
+
+#[test]
+fn doesnt_split_on_ellipses_alone() {
+    assert_eq!(
+        sentences("えっと…そうだね。うん……"),
+        ["えっと…そうだね。", "うん……"]
+    );
+}
+
+#[test]
+fn line_breaks_close_unbalanced_brackets() {
+    assert_eq!(
+        sentences("「閉じていない。まだ続く\n次の行。最後の文。"),
+        ["「閉じていない。まだ続く", "次の行。", "最後の文。"]
+    );
+    assert_eq!(
+        sentences("閉じただけ」の文。次。"),
+        ["閉じただけ」の文。", "次。"]
+    );
+}
+
+#[test]
+fn spans_point_into_the_text() {
+    let text = "  一つ目。\n\n二つ目！  ";
+    let spans = split_sentences(text);
+
+    assert_eq!(spans, [2..14, 16..28]);
+    assert_eq!(&text[spans[1].clone()], "二つ目！");
+}
+
+#[test]
+fn joins_lines_wrapped_mid_sentence() {
+    let text = "吾輩は猫で\nある。名前はまだ\n  無い。\n「どこで生れたか\nとんと見当がつかぬ。」\n　何でも薄暗い\n\nじめじめ\nした所で。\nsoft\nwrap";
+
+    assert_eq!(
+        join_soft_line_breaks(text),
+        "吾輩は猫である。名前はまだ無い。\n「どこで生れたかとんと見当がつかぬ。」\n　何でも薄暗い\n\nじめじめした所で。\nsoft wrap"
+    );
+}
@@ -0,0 +1,50 @@
+mod common;
+
+use common::parse;
+use ve::ParseConfig;
+
+fn lemmas(text: &str) -> Vec<Option<String>> {
+    parse(text, ParseConfig::default())
+        .into_iter()
+        .map(|word| word.lemma)
+        .collect()
+}
+
+#[test]
+fn words_have_their_dictionary_form_as_lemma() {
+    assert_eq!(
+        lemmas("猫が食べました。"),
+        [
+            Some("猫".into()),
+            Some("が".into()),
+            Some("食べる".into()),
+            Some("。".into())
+        ]
+    );
+}
+
+#[test]
+fn unknown_words_are_their_own_lemma() {
+    assert_eq!(lemmas("犬"), [Some("犬".into())]);
+}
+
+fn lemma_readings(text: &str) -> Vec<Option<String>> {
+    parse(text, ParseConfig::default())
+        .into_iter()
+        .map(|word| word.extra.lemma_reading)
+        .collect()
+}
+
+#[test]
+fn reads_the_dictionary_form_of_kuru() {
+    assert_eq!(lemma_readings("来なかった")[0].as_deref(), Some("クル"));
+}
+
+#[test]
+fn reads_compounds_with_ku_as_written() {
+    assert_eq!(
+        lemmas("出来なかった。"),
+        [Some("出来る".into()), Some("。".into())]
+    );
+    assert_eq!(lemma_readings("出来なかった")[0].as_deref(), Some("デキル"));
+}